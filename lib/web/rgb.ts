@@ -151,6 +151,12 @@ export const decodeInvoice = async (
   invoice: string
 ): Promise<RgbInvoiceResponse> => JSON.parse(await BMC.decode_invoice(invoice));
 
+export const decodeFullInvoice = async (
+  nostrHexSk: string,
+  invoice: string
+): Promise<RgbInvoiceResponse> =>
+  JSON.parse(await BMC.decode_full_invoice(nostrHexSk, invoice));
+
 export const createOffer = async (
   nostrHexSk: string,
   request: RgbOfferRequest
@@ -747,6 +753,19 @@ export interface TransferType {
 export interface RgbInvoiceResponse {
   contractId: string;
   amount: bigint;
+  decimalAmount?: string;
+  iface?: string;
+  operation?: string;
+  assignment?: string;
+  beneficiary: string;
+  beneficiaryType: "blinded" | "witness";
+  chain?: string;
+  transports: string[];
+  expiry?: number;
+  unknownQuery: Record<string, string>;
+  knownContract: boolean;
+  ticker?: string;
+  precision?: number;
 }
 
 export interface BatchRgbTransferResponse {
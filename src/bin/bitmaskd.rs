@@ -10,46 +10,67 @@ use axum::{
     extract::Path,
     headers::{authorization::Bearer, Authorization, CacheControl},
     http::StatusCode,
-    response::{IntoResponse, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     routing::{delete, get, post, put},
     Json, Router, TypedHeader,
 };
 use bitcoin_30::secp256k1::{ecdh::SharedSecret, PublicKey, SecretKey};
 use bitmask_core::{
-    bitcoin::{save_mnemonic, sign_and_publish_psbt_file},
+    bitcoin::{
+        is_watching_payments, poll_payment_events, save_mnemonic, sign_and_publish_psbt_file,
+        start_payment_watcher, stop_payment_watcher, verify_proof_of_reserves,
+    },
     carbonado::{
         handle_file,
         metrics::{metrics, metrics_csv},
         server_retrieve, server_store, store,
     },
     constants::{
-        get_marketplace_nostr_key, get_marketplace_seed, get_network, get_udas_utxo, switch_network,
+        get_marketplace_nostr_key, get_marketplace_seed, get_network, get_udas_utxo,
+        switch_network, PAYMENT_WATCHER_MIN_INTERVAL,
     },
     proxy::{
-        handle_file as proxy_handle_file, proxy_consig_retrieve, proxy_consig_store,
+        check_transport_endpoint, handle_file as proxy_handle_file, proxy_consig_retrieve,
+        proxy_consig_retrieve_at, proxy_consig_store, proxy_consig_store_at,
         proxy_media_data_store, proxy_media_retrieve, proxy_metadata_retrieve,
     },
     rgb::{
-        accept_transfer, clear_watcher as rgb_clear_watcher, create_invoice, create_psbt,
-        create_watcher, full_transfer_asset, get_contract, import as rgb_import, issue_contract,
-        list_contracts, list_interfaces, list_schemas, list_transfers as list_rgb_transfers,
-        reissue_contract, remove_transfer as remove_rgb_transfer,
+        accept_transfer, airdrop_asset, burn_asset, cancel_invoice as cancel_rgb_invoice,
+        cancel_transfer as cancel_rgb_transfer, check_reorgs,
+        cleanup_invoices as cleanup_rgb_invoices, clear_watcher as rgb_clear_watcher,
+        constants::RGB_RECEIVER_MIN_INTERVAL,
+        create_invoice, create_psbt, create_watcher, full_transfer_asset, get_contract,
+        import as rgb_import, inflate_asset, inspect_transfer, issue_contract, list_contracts,
+        list_interfaces, list_invoices as list_rgb_invoices, list_schemas,
+        list_transfers as list_rgb_transfers, preview_issue_contract, preview_transfer,
+        receive_invoice_consignments, receive_transfers as receive_rgb_transfers,
+        receiver::{is_receiving, start_receiver, stop_receiver},
+        reissue_contract, remove_transfer as remove_rgb_transfer, replace_asset,
         save_transfer as save_rgb_transfer,
         structs::{
             RgbProxyConsigCarbonadoReq, RgbProxyConsigFileReq, RgbProxyConsigUpload,
-            RgbProxyMediaCarbonadoReq, RgbProxyMediaFileReq,
+            RgbProxyMediaCarbonadoReq, RgbProxyMediaFileReq, RgbProxyTransportConsigReq,
+            RgbProxyTransportRetrieveReq,
         },
-        transfer_asset, watcher_address, watcher_details as rgb_watcher_details,
-        watcher_next_address, watcher_next_utxo, watcher_utxo,
+        transfer_asset, update_transfer_state as update_rgb_transfer_state, verify_contract_terms,
+        watcher_address, watcher_details as rgb_watcher_details, watcher_next_address,
+        watcher_next_utxo, watcher_utxo,
     },
     structs::{
-        AcceptRequest, FileMetadata, FullRgbTransferRequest, ImportRequest, InvoiceRequest,
-        IssueRequest, MediaEncode, MediaExtractRequest, MediaItemRequest, PsbtFeeRequest,
-        PsbtRequest, ReIssueRequest, RgbRemoveTransferRequest, RgbSaveTransferRequest,
-        RgbTransferRequest, SecretString, SelfFullRgbTransferRequest, SelfInvoiceRequest,
-        SelfIssueRequest, SignPsbtRequest, WatcherRequest,
+        AcceptRequest, CancelInvoiceRequest, FileMetadata, FullRgbTransferRequest, ImportRequest,
+        InvoiceRequest, InvoicesRequest, IssueRequest, MediaEncode, MediaExtractRequest,
+        MediaItemRequest, PaymentEvent, PaymentUnwatchRequest, PaymentWatcherRequest,
+        PsbtFeeRequest, PsbtRequest, ReIssueRequest, RgbAirdropRequest, RgbBurnRequest,
+        RgbCancelTransferRequest, RgbReceiverRequest, RgbRemoveTransferRequest, RgbReorgRequest,
+        RgbSaveTransferRequest, RgbTransferRequest, RgbTransferStateRequest, SecretString,
+        SelfFullRgbTransferRequest, SelfInvoiceRequest, SelfIssueRequest, SignPsbtRequest,
+        TransferState, VerifyReservesRequest, VerifyTermsRequest, WatcherRequest,
     },
 };
+use futures::{stream, StreamExt};
 use log::{debug, error, info};
 use tokio::{fs, time::sleep};
 use tower_http::cors::CorsLayer;
@@ -65,6 +86,17 @@ async fn issue(
     Ok((StatusCode::OK, Json(issue_res)))
 }
 
+async fn preview_issue(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<IssueRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /issue/preview {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let preview_res = preview_issue_contract(nostr_hex_sk, request).await?;
+    Ok((StatusCode::OK, Json(preview_res)))
+}
+
 async fn reissue(
     TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
     Json(request): Json<ReIssueRequest>,
@@ -95,6 +127,12 @@ async fn self_issue(Json(issue): Json<SelfIssueRequest>) -> Result<impl IntoResp
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta: issue.meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_res = issue_contract(sk, request).await?;
@@ -114,6 +152,116 @@ async fn invoice(
     Ok((StatusCode::OK, Json(invoice_res)))
 }
 
+async fn cancel_invoice(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<CancelInvoiceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("DELETE /invoice {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let cancel_res = cancel_rgb_invoice(nostr_hex_sk, request).await?;
+
+    Ok((StatusCode::OK, Json(cancel_res)))
+}
+
+async fn cleanup_invoices(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /invoice/cleanup");
+
+    let nostr_hex_sk = auth.token();
+    let cleanup_res = cleanup_rgb_invoices(nostr_hex_sk).await?;
+
+    Ok((StatusCode::OK, Json(cleanup_res)))
+}
+
+async fn list_invoices(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<InvoicesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /invoices {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let invoices_res = list_rgb_invoices(nostr_hex_sk, request).await?;
+
+    Ok((StatusCode::OK, Json(invoices_res)))
+}
+
+async fn receive_transfers(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbReceiverRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /receiver {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let receiver_res = receive_rgb_transfers(nostr_hex_sk, request).await?;
+
+    Ok((StatusCode::OK, Json(receiver_res)))
+}
+
+async fn reorgs(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbReorgRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /transfers/reorgs {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let reorgs_res = check_reorgs(nostr_hex_sk, request).await?;
+
+    Ok((StatusCode::OK, Json(reorgs_res)))
+}
+
+async fn watch_transfers(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbReceiverRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /receiver/watch {request:?}");
+
+    let nostr_hex_sk = auth.token().to_owned();
+    if !start_receiver(&nostr_hex_sk).await {
+        return Ok((StatusCode::OK, Json(false)));
+    }
+
+    // Shorter intervals are raised, so the endpoints are never polled in a busy loop
+    let interval = Duration::from_secs(request.interval.max(RGB_RECEIVER_MIN_INTERVAL));
+    tokio::spawn(async move {
+        while is_receiving(&nostr_hex_sk).await {
+            match receive_rgb_transfers(&nostr_hex_sk, request.clone()).await {
+                Ok(resp) => {
+                    for transfer in resp.transfers {
+                        info!("Received {} ({:?})", transfer.consig_id, transfer.state);
+                    }
+                }
+                Err(err) => error!("Error in RGB receiver: {err}"),
+            }
+            sleep(interval).await;
+        }
+    });
+
+    Ok((StatusCode::OK, Json(true)))
+}
+
+async fn unwatch_transfers(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("DELETE /receiver/watch");
+
+    let stopped = stop_receiver(auth.token()).await;
+
+    Ok((StatusCode::OK, Json(stopped)))
+}
+
+async fn invoice_consignments(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("GET /invoice/consignments");
+
+    let nostr_hex_sk = auth.token();
+    let consignments_res = receive_invoice_consignments(nostr_hex_sk).await?;
+
+    Ok((StatusCode::OK, Json(consignments_res)))
+}
+
 async fn self_invoice(
     Json(self_invoice): Json<SelfInvoiceRequest>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -134,6 +282,10 @@ async fn self_invoice(
         amount: "1".to_string(),
         seal: invoice_seal.to_owned(),
         params: self_invoice.params,
+        transports: vec![],
+        expire_at: None,
+        min_amount: None,
+        max_amount: None,
     };
     let invoice_res = create_invoice(nostr_hex_sk, invoice).await?;
 
@@ -177,6 +329,58 @@ async fn pay(
     Ok((StatusCode::OK, Json(transfer_res)))
 }
 
+async fn inflate(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(inflate_req): Json<RgbTransferRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /inflate {inflate_req:?}");
+
+    let nostr_hex_sk = auth.token();
+
+    let inflate_res = inflate_asset(nostr_hex_sk, inflate_req).await?;
+
+    Ok((StatusCode::OK, Json(inflate_res)))
+}
+
+async fn replace(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(replace_req): Json<RgbTransferRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /replace {replace_req:?}");
+
+    let nostr_hex_sk = auth.token();
+
+    let replace_res = replace_asset(nostr_hex_sk, replace_req).await?;
+
+    Ok((StatusCode::OK, Json(replace_res)))
+}
+
+async fn burn(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(burn_req): Json<RgbBurnRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /burn {burn_req:?}");
+
+    let nostr_hex_sk = auth.token();
+
+    let burn_res = burn_asset(nostr_hex_sk, burn_req).await?;
+
+    Ok((StatusCode::OK, Json(burn_res)))
+}
+
+async fn airdrop(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(airdrop_req): Json<RgbAirdropRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /airdrop {airdrop_req:?}");
+
+    let nostr_hex_sk = auth.token();
+
+    let airdrop_res = airdrop_asset(nostr_hex_sk, airdrop_req).await?;
+
+    Ok((StatusCode::OK, Json(airdrop_res)))
+}
+
 #[axum_macros::debug_handler]
 async fn self_pay(
     Json(self_pay_req): Json<SelfFullRgbTransferRequest>,
@@ -203,6 +407,7 @@ async fn self_pay(
         fee,
         change_terminal: self_pay_req.terminal,
         bitcoin_changes: self_pay_req.bitcoin_changes,
+        amount: None,
     };
 
     let transfer_res = full_transfer_asset(nostr_hex_sk, request).await?;
@@ -210,6 +415,28 @@ async fn self_pay(
     Ok((StatusCode::OK, Json(transfer_res)))
 }
 
+async fn preview_accept(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(accept_req): Json<AcceptRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /accept/preview {accept_req:?}");
+
+    let nostr_hex_sk = auth.token();
+    let preview_res = preview_transfer(nostr_hex_sk, accept_req).await?;
+
+    Ok((StatusCode::OK, Json(preview_res)))
+}
+
+async fn inspect_accept(
+    Json(accept_req): Json<AcceptRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /transfers/inspect {accept_req:?}");
+
+    let inspect_res = inspect_transfer(accept_req).await?;
+
+    Ok((StatusCode::OK, Json(inspect_res)))
+}
+
 async fn accept(
     TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
     Json(accept_req): Json<AcceptRequest>,
@@ -249,6 +476,17 @@ async fn contracts(
     Ok((StatusCode::OK, Json(contracts_res)))
 }
 
+async fn verify_terms(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<VerifyTermsRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /contracts/terms {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let verify_res = verify_contract_terms(nostr_hex_sk, request).await?;
+    Ok((StatusCode::OK, Json(verify_res)))
+}
+
 async fn contract_detail(
     TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
     Path(id): Path<String>,
@@ -392,11 +630,36 @@ async fn list_transfers(
     info!("GET /transfers/{contract_id:?}");
 
     let nostr_hex_sk = auth.token();
-    let transfers_res = list_rgb_transfers(nostr_hex_sk, contract_id).await?;
+    let transfers_res = list_rgb_transfers(nostr_hex_sk, contract_id, None).await?;
+
+    Ok((StatusCode::OK, Json(transfers_res)))
+}
+
+async fn list_transfers_by_state(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Path((contract_id, state)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("GET /transfers/{contract_id:?}/{state:?}");
+
+    let nostr_hex_sk = auth.token();
+    let state = TransferState::from_str(&state).map_err(|op| anyhow::anyhow!(op))?;
+    let transfers_res = list_rgb_transfers(nostr_hex_sk, contract_id, Some(state)).await?;
 
     Ok((StatusCode::OK, Json(transfers_res)))
 }
 
+async fn update_transfer_state(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbTransferStateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("PUT /transfers/state {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let state_res = update_rgb_transfer_state(nostr_hex_sk, request).await?;
+
+    Ok((StatusCode::OK, Json(state_res)))
+}
+
 async fn save_transfer(
     TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
     Json(request): Json<RgbSaveTransferRequest>,
@@ -421,6 +684,82 @@ async fn remove_transfer(
     Ok((StatusCode::OK, Json(import_res)))
 }
 
+async fn cancel_transfer(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbCancelTransferRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /transfers/cancel {request:?}");
+
+    let nostr_hex_sk = auth.token();
+    let cancel_res = cancel_rgb_transfer(nostr_hex_sk, request).await?;
+
+    Ok((StatusCode::OK, Json(cancel_res)))
+}
+
+async fn watch_payments(
+    TypedHeader(_auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<PaymentWatcherRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /payments/watch");
+
+    // The first event carries the subscription id, required to stop the watcher
+    let subscription = start_payment_watcher(&request.descriptor).await;
+    let subscribed = Event::default()
+        .event("payment")
+        .json_data(PaymentEvent::Subscribed {
+            subscription: subscription.clone(),
+        });
+
+    // Shorter intervals are raised, so the explorer is never polled in a busy loop
+    let interval = Duration::from_secs(request.interval.max(PAYMENT_WATCHER_MIN_INTERVAL));
+    let events = stream::unfold((request, true), move |(request, first)| {
+        let subscription = subscription.clone();
+        async move {
+            if !first {
+                sleep(interval).await;
+            }
+
+            if !is_watching_payments(&subscription).await {
+                return None;
+            }
+
+            let events = match poll_payment_events(&subscription, &request).await {
+                Ok(events) => events
+                    .into_iter()
+                    .map(|event| Event::default().event("payment").json_data(event))
+                    .collect(),
+                Err(err) => vec![Ok(Event::default().event("error").data(err.to_string()))],
+            };
+
+            Some((stream::iter(events), (request, false)))
+        }
+    })
+    .flatten();
+
+    Ok(Sse::new(stream::iter(vec![subscribed]).chain(events)).keep_alive(KeepAlive::default()))
+}
+
+async fn unwatch_payments(
+    TypedHeader(_auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<PaymentUnwatchRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("DELETE /payments/watch");
+
+    let stopped = stop_payment_watcher(&request.subscription).await;
+
+    Ok((StatusCode::OK, Json(stopped)))
+}
+
+async fn verify_reserves(
+    Json(request): Json<VerifyReservesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /reserves/verify {request:?}");
+
+    let verify_res = verify_proof_of_reserves(&request.psbt, &request.message).await?;
+
+    Ok((StatusCode::OK, Json(verify_res)))
+}
+
 async fn co_store(
     Path((pk, name)): Path<(String, String)>,
     body: Bytes,
@@ -650,6 +989,34 @@ async fn rgb_proxy_consig_retrieve(Path(id): Path<String>) -> Result<impl IntoRe
     Ok((StatusCode::OK, Json(resp)))
 }
 
+async fn rgb_proxy_transport_consig_save(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbProxyTransportConsigReq>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /proxy/transport/consignment {}", request.endpoint);
+    let RgbProxyTransportConsigReq { endpoint, request } = request;
+    check_transport_endpoint(&endpoint).await?;
+
+    let request = RgbProxyConsigFileReq::from(request);
+    let resp = proxy_consig_store_at(&endpoint, auth.token(), request).await?;
+    Ok((StatusCode::OK, Json(resp)))
+}
+
+async fn rgb_proxy_transport_consig_retrieve(
+    TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
+    Json(request): Json<RgbProxyTransportRetrieveReq>,
+) -> Result<impl IntoResponse, AppError> {
+    info!("POST /proxy/transport/consignment/retrieve {request:?}");
+    let RgbProxyTransportRetrieveReq {
+        endpoint,
+        recipient_id,
+    } = request;
+    check_transport_endpoint(&endpoint).await?;
+
+    let resp = proxy_consig_retrieve_at(&endpoint, auth.token(), &recipient_id).await?;
+    Ok((StatusCode::OK, Json(resp)))
+}
+
 async fn rgb_proxy_media_retrieve(Path(id): Path<String>) -> Result<impl IntoResponse, AppError> {
     info!("GET /proxy/media/{id}");
     let resp = proxy_media_retrieve(&id).await?;
@@ -763,18 +1130,33 @@ async fn main() -> Result<()> {
 
     let mut app = Router::new()
         .route("/issue", post(issue))
+        .route("/issue/preview", post(preview_issue))
         .route("/reissue", post(reissue))
         .route("/selfissue", post(self_issue))
         .route("/invoice", post(invoice))
+        .route("/invoice", delete(cancel_invoice))
+        .route("/invoice/cleanup", post(cleanup_invoices))
+        .route("/invoice/consignments", get(invoice_consignments))
+        .route("/receiver", post(receive_transfers))
+        .route("/receiver/watch", post(watch_transfers))
+        .route("/receiver/watch", delete(unwatch_transfers))
+        .route("/invoices", post(list_invoices))
         .route("/selfinvoice", post(self_invoice))
         // .route("/psbt", post(psbt))
         // .route("/sign", post(sign_psbt))
         .route("/pay", post(pay))
         .route("/selfpay", post(self_pay))
+        .route("/inflate", post(inflate))
+        .route("/replace", post(replace))
+        .route("/burn", post(burn))
+        .route("/airdrop", post(airdrop))
         .route("/accept", post(accept))
+        .route("/accept/preview", post(preview_accept))
+        .route("/transfers/inspect", post(inspect_accept))
         .route("/selfaccept", post(self_accept))
         .route("/contracts", get(contracts))
         .route("/contracts/:id", get(contract_detail))
+        .route("/contracts/terms", post(verify_terms))
         .route("/interfaces", get(interfaces))
         .route("/schemas", get(schemas))
         .route("/import", post(import))
@@ -789,8 +1171,15 @@ async fn main() -> Result<()> {
         .route("/watcher/:name/:asset/utxo/:utxo", put(register_utxo))
         .route("/watcher/:name", delete(clear_watcher))
         .route("/transfers/:id", get(list_transfers))
+        .route("/transfers/:id/:state", get(list_transfers_by_state))
+        .route("/transfers/state", put(update_transfer_state))
         .route("/transfers/", post(save_transfer))
         .route("/transfers/", delete(remove_transfer))
+        .route("/transfers/cancel", post(cancel_transfer))
+        .route("/transfers/reorgs", post(reorgs))
+        .route("/payments/watch", post(watch_payments))
+        .route("/payments/watch", delete(unwatch_payments))
+        .route("/reserves/verify", post(verify_reserves))
         .route("/key/:pk", get(key))
         .route("/carbonado/status", get(status))
         .route("/carbonado/server/:name", get(co_server_retrieve))
@@ -801,6 +1190,14 @@ async fn main() -> Result<()> {
         .route("/carbonado/:pk/:name/metadata", get(co_metadata))
         .route("/proxy/consignment/:id", post(rgb_proxy_consig_save))
         .route("/proxy/consignment/:id", get(rgb_proxy_consig_retrieve))
+        .route(
+            "/proxy/transport/consignment",
+            post(rgb_proxy_transport_consig_save),
+        )
+        .route(
+            "/proxy/transport/consignment/retrieve",
+            post(rgb_proxy_transport_consig_retrieve),
+        )
         .route("/proxy/media-metadata", post(rgb_proxy_media_data_save))
         .route("/proxy/media-metadata/:id", get(rgb_proxy_media_retrieve))
         .route("/proxy/media/:id", get(rgb_proxy_metadata_retrieve))
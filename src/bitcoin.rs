@@ -16,17 +16,32 @@ use zeroize::Zeroize;
 
 mod assets;
 mod keys;
+mod mempool;
 mod payment;
 mod psbt;
+mod reserves;
+mod vault;
 mod wallet;
 
 pub use crate::bitcoin::{
     assets::dust_tx,
     keys::{new_mnemonic, save_mnemonic, BitcoinKeysError},
+    mempool::{
+        is_watching_payments, poll_payment_events, record_payment_events, start_payment_watcher,
+        stop_payment_watcher, BitcoinMempoolError,
+    },
     payment::{create_payjoin, create_transaction, BitcoinPaymentError},
     psbt::{
-        multi_sign_and_publish_psbt, multi_sign_psbt, publish_psbt, sign_and_publish_psbt,
-        sign_psbt, BitcoinPsbtError,
+        convert_psbt, decode_psbt, encode_psbt, multi_sign_and_publish_psbt, multi_sign_psbt,
+        publish_psbt, sign_and_publish_psbt, sign_psbt, BitcoinPsbtError,
+    },
+    reserves::{
+        check_proof_of_reserves, create_proof_of_reserves, verify_proof_of_reserves,
+        BitcoinReservesError,
+    },
+    vault::{
+        create_inheritance_vault, refresh_inheritance_vault, sweep_inheritance_vault,
+        vault_descriptor, vault_tapret_tweak, BitcoinVaultError,
     },
     wallet::{
         get_blockchain, get_wallet, sync_wallet, sync_wallets, BitcoinWalletError, MemoryWallet,
@@ -38,9 +53,9 @@ use crate::{
     constants::{DIBA_DESCRIPTOR, DIBA_DESCRIPTOR_VERSION, DIBA_MAGIC_NO, NETWORK},
     debug, info,
     structs::{
-        DecryptedWalletData, EncryptedWalletDataV04, FundVaultDetails, PublishPsbtRequest,
-        PublishedPsbtResponse, SatsInvoice, SecretString, SignPsbtRequest, SignedPsbtResponse,
-        WalletData, WalletTransaction,
+        ConvertPsbtRequest, ConvertedPsbtResponse, DecryptedWalletData, EncryptedWalletDataV04,
+        FundVaultDetails, PsbtFormat, PublishPsbtRequest, PublishedPsbtResponse, SatsInvoice,
+        SecretString, SignPsbtRequest, SignedPsbtResponse, WalletData, WalletTransaction,
     },
     trace,
 };
@@ -86,12 +101,21 @@ pub enum BitcoinError {
     /// BitMask Core Bitcoin Keys error
     #[error(transparent)]
     BitcoinKeysError(#[from] BitcoinKeysError),
+    /// BitMask Core Bitcoin Mempool error
+    #[error(transparent)]
+    BitcoinMempoolError(#[from] BitcoinMempoolError),
     /// BitMask Core Bitcoin Payment error
     #[error(transparent)]
     BitcoinPaymentError(#[from] BitcoinPaymentError),
     /// BitMask Core Bitcoin Psbt error
     #[error(transparent)]
     BitcoinPsbtError(#[from] BitcoinPsbtError),
+    /// BitMask Core Bitcoin Reserves error
+    #[error(transparent)]
+    BitcoinReservesError(#[from] BitcoinReservesError),
+    /// BitMask Core Bitcoin Vault error
+    #[error(transparent)]
+    BitcoinVaultError(#[from] BitcoinVaultError),
     /// BitMask Core Bitcoin Wallet error
     #[error(transparent)]
     BitcoinWalletError(#[from] BitcoinWalletError),
@@ -467,7 +491,7 @@ pub async fn get_assets_vault(
 pub async fn sign_psbt_file(request: SignPsbtRequest) -> Result<SignedPsbtResponse, BitcoinError> {
     let SignPsbtRequest { psbt, descriptors } = request;
 
-    let original_psbt = Psbt::from_str(&psbt)?;
+    let (original_psbt, psbt_version) = decode_psbt(&psbt)?;
     let final_psbt = PartiallySignedTransaction::from(original_psbt);
 
     let mut wallets = vec![];
@@ -478,8 +502,10 @@ pub async fn sign_psbt_file(request: SignPsbtRequest) -> Result<SignedPsbtRespon
 
     let psbt_signed = multi_sign_psbt(wallets, final_psbt).await?;
 
-    let psbt_bytes = encode::serialize(&psbt_signed);
-    let psbt_hex = psbt_bytes.to_hex();
+    let psbt_hex = match psbt_version {
+        PsbtFormat::V0 => encode::serialize(&psbt_signed).to_hex(),
+        PsbtFormat::V2 => encode_psbt(Psbt::from(psbt_signed), psbt_version),
+    };
     Ok(SignedPsbtResponse {
         sign: true,
         psbt: psbt_hex,
@@ -491,24 +517,35 @@ pub async fn publish_psbt_file(
 ) -> Result<SignedPsbtResponse, BitcoinError> {
     let PublishPsbtRequest { psbt } = request;
 
-    let original_psbt = Psbt::from_str(&psbt)?;
+    let (original_psbt, psbt_version) = decode_psbt(&psbt)?;
     let final_psbt = PartiallySignedTransaction::from(original_psbt);
 
     publish_psbt(final_psbt.clone()).await?;
-    let psbt_bytes = encode::serialize(&final_psbt);
-    let psbt_hex = psbt_bytes.to_hex();
+    let psbt_hex = match psbt_version {
+        PsbtFormat::V0 => encode::serialize(&final_psbt).to_hex(),
+        PsbtFormat::V2 => encode_psbt(Psbt::from(final_psbt), psbt_version),
+    };
     Ok(SignedPsbtResponse {
         sign: true,
         psbt: psbt_hex,
     })
 }
 
+pub fn convert_psbt_file(
+    request: ConvertPsbtRequest,
+) -> Result<ConvertedPsbtResponse, BitcoinError> {
+    let ConvertPsbtRequest { psbt, psbt_version } = request;
+
+    let psbt = convert_psbt(&psbt, psbt_version)?;
+    Ok(ConvertedPsbtResponse { psbt, psbt_version })
+}
+
 pub async fn sign_and_publish_psbt_file(
     request: SignPsbtRequest,
 ) -> Result<PublishedPsbtResponse, BitcoinError> {
     let SignPsbtRequest { psbt, descriptors } = request;
 
-    let original_psbt = Psbt::from_str(&psbt)?;
+    let (original_psbt, _) = decode_psbt(&psbt)?;
     let final_psbt = PartiallySignedTransaction::from(original_psbt);
 
     let mut wallets = vec![];
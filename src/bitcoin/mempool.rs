@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use bdk::{blockchain::GetHeight, TransactionDetails};
+use bitcoin::{OutPoint, Txid};
+use bitcoin_hashes::{sha256, Hash};
+use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::{
+    bitcoin::wallet::{get_blockchain, get_wallet, sync_wallet, BitcoinWalletError},
+    debug,
+    structs::{PaymentEvent, PaymentWatcherRequest, SecretString},
+};
+
+#[derive(Error, Debug)]
+pub enum BitcoinMempoolError {
+    /// BitMask Core Bitcoin Wallet error
+    #[error(transparent)]
+    BitcoinWalletError(#[from] BitcoinWalletError),
+    /// No watcher of the descriptor was started with this subscription
+    #[error("Payment watcher subscription {0} not found")]
+    NoSubscription(String),
+    /// BDK error
+    #[error(transparent)]
+    BdkError(#[from] bdk::Error),
+}
+
+#[derive(Clone, Debug, Default)]
+struct WatchedPayment {
+    inputs: BTreeSet<OutPoint>,
+    height: Option<u32>,
+    notified: BTreeSet<u32>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct WatchedPayments {
+    /// Hash of the watched descriptor
+    descriptor: String,
+    initialized: bool,
+    payments: BTreeMap<Txid, WatchedPayment>,
+}
+
+/// Watchers by subscription, so consumers of the same descriptor keep their own history
+static PAYMENT_WATCHERS: Lazy<Mutex<BTreeMap<String, WatchedPayments>>> =
+    Lazy::new(Default::default);
+
+fn watcher_key(descriptor: &SecretString) -> String {
+    sha256::Hash::hash(descriptor.0.as_bytes()).to_string()
+}
+
+/// Starts a watcher of the descriptor and returns its subscription id.
+///
+/// The id is random, so only the consumer who started the watcher can poll or stop it.
+pub async fn start_payment_watcher(descriptor: &SecretString) -> String {
+    let mut rng = StdRng::from_entropy();
+    let subscription = hex::encode(rng.gen::<[u8; 16]>());
+    PAYMENT_WATCHERS.lock().await.insert(
+        subscription.clone(),
+        WatchedPayments {
+            descriptor: watcher_key(descriptor),
+            ..Default::default()
+        },
+    );
+    subscription
+}
+
+pub async fn stop_payment_watcher(subscription: &str) -> bool {
+    PAYMENT_WATCHERS.lock().await.remove(subscription).is_some()
+}
+
+pub async fn is_watching_payments(subscription: &str) -> bool {
+    PAYMENT_WATCHERS.lock().await.contains_key(subscription)
+}
+
+/// Syncs the wallet and returns the incoming payment events since the last poll.
+///
+/// The first poll only records the wallet history, so confirmed payments are not
+/// reported again when a watcher is (re)started.
+pub async fn poll_payment_events(
+    subscription: &str,
+    request: &PaymentWatcherRequest,
+) -> Result<Vec<PaymentEvent>, BitcoinMempoolError> {
+    let PaymentWatcherRequest {
+        descriptor,
+        change_descriptor,
+        confirmations,
+        ..
+    } = request;
+
+    let wallet = get_wallet(descriptor, change_descriptor.as_ref()).await?;
+    sync_wallet(&wallet).await?;
+
+    let tip = get_blockchain().await.get_height().await?;
+    let transactions = wallet.lock().await.list_transactions(true)?;
+
+    let events =
+        record_payment_events(subscription, descriptor, transactions, tip, confirmations).await?;
+    debug!(format!("Payment events: {events:#?}"));
+    Ok(events)
+}
+
+/// Compares the wallet history with the last one recorded by the subscription and
+/// returns the incoming payment events
+pub async fn record_payment_events(
+    subscription: &str,
+    descriptor: &SecretString,
+    transactions: Vec<TransactionDetails>,
+    tip: u32,
+    confirmations: &[u32],
+) -> Result<Vec<PaymentEvent>, BitcoinMempoolError> {
+    let mut thresholds = confirmations.to_vec();
+    thresholds.sort();
+    thresholds.dedup();
+
+    let mut watchers = PAYMENT_WATCHERS.lock().await;
+    let watcher = match watchers.get_mut(subscription) {
+        Some(watcher) if watcher.descriptor == watcher_key(descriptor) => watcher,
+        _ => {
+            return Err(BitcoinMempoolError::NoSubscription(
+                subscription.to_string(),
+            ))
+        }
+    };
+    let initialized = watcher.initialized;
+
+    let mut events = vec![];
+    let mut current = BTreeMap::new();
+    for tx in transactions {
+        // Only incoming payments are reported
+        if tx.received <= tx.sent {
+            continue;
+        }
+
+        let txid = tx.txid;
+        let height = tx.confirmation_time.map(|time| time.height);
+        let inputs: BTreeSet<OutPoint> = tx
+            .transaction
+            .map(|tx| tx.input.iter().map(|input| input.previous_output).collect())
+            .unwrap_or_default();
+
+        let mut payment = match watcher.payments.remove(&txid) {
+            Some(payment) => payment,
+            None => {
+                // Payments already mined when first seen only report their confirmations
+                if initialized && height.is_none() {
+                    events.push(PaymentEvent::Seen {
+                        txid: txid.to_string(),
+                        amount: tx.received - tx.sent,
+                    });
+                }
+                WatchedPayment::default()
+            }
+        };
+
+        payment.inputs = inputs;
+        if payment.height != height {
+            // Reorg or new confirmation, thresholds are counted from the new height
+            payment.notified.clear();
+            payment.height = height;
+        }
+
+        if let Some(height) = height {
+            let depth = tip.saturating_sub(height) + 1;
+            for threshold in thresholds.iter().filter(|t| **t <= depth) {
+                if payment.notified.insert(*threshold) && initialized {
+                    events.push(PaymentEvent::Confirmed {
+                        txid: txid.to_string(),
+                        height,
+                        confirmations: *threshold,
+                    });
+                }
+            }
+        }
+
+        current.insert(txid, payment);
+    }
+
+    // Payments that left the wallet history were either replaced or dropped
+    for (txid, payment) in watcher.payments.iter() {
+        let replaced_by = current
+            .iter()
+            .find(|(_, other)| !other.inputs.is_disjoint(&payment.inputs))
+            .map(|(other, _)| other.to_string());
+
+        match replaced_by {
+            Some(replaced_by) => events.push(PaymentEvent::Replaced {
+                txid: txid.to_string(),
+                replaced_by,
+            }),
+            None => events.push(PaymentEvent::Dropped {
+                txid: txid.to_string(),
+            }),
+        }
+    }
+
+    watcher.payments = current;
+    watcher.initialized = true;
+
+    Ok(events)
+}
@@ -1,3 +1,6 @@
+use std::str::FromStr;
+
+use ::psbt::{serialize::Serialize, Psbt, PsbtVersion};
 use bdk::{blockchain::Blockchain, psbt::PsbtUtils, SignOptions, TransactionDetails};
 use bitcoin::{consensus::serialize, hashes::hex::ToHex, util::psbt::PartiallySignedTransaction};
 use thiserror::Error;
@@ -5,6 +8,7 @@ use thiserror::Error;
 use crate::{
     bitcoin::{get_blockchain, MemoryWallet},
     debug,
+    structs::PsbtFormat,
 };
 
 #[derive(Error, Debug)]
@@ -18,6 +22,28 @@ pub enum BitcoinPsbtError {
     /// BDK esplora error
     #[error(transparent)]
     BdkEsploraError(#[from] bdk::esplora_client::Error),
+    /// PSBT decode error
+    #[error(transparent)]
+    PsbtDecodeError(#[from] bitcoin::consensus::encode::Error),
+}
+
+/// Decodes a PSBT (BIP-174 or BIP-370) and returns the version it was encoded with
+pub fn decode_psbt(psbt: &str) -> Result<(Psbt, PsbtFormat), BitcoinPsbtError> {
+    let psbt = Psbt::from_str(psbt)?;
+    let version = PsbtFormat::from(psbt.psbt_version);
+    Ok((psbt, version))
+}
+
+/// Encodes a PSBT in hex using the given version
+pub fn encode_psbt(mut psbt: Psbt, version: PsbtFormat) -> String {
+    psbt.psbt_version = PsbtVersion::from(version);
+    Serialize::serialize(&psbt).to_hex()
+}
+
+/// Re-encodes a PSBT (v0 or v2) in the given version
+pub fn convert_psbt(psbt: &str, version: PsbtFormat) -> Result<String, BitcoinPsbtError> {
+    let (psbt, _) = decode_psbt(psbt)?;
+    Ok(encode_psbt(psbt, version))
 }
 
 // Only signs an original psbt.
@@ -0,0 +1,278 @@
+use std::collections::BTreeSet;
+
+use bdk::SignOptions;
+use bitcoin::{
+    blockdata::{opcodes, script::Builder},
+    hashes::{hex::ToHex, sha256d, Hash},
+    psbt::{Input, PartiallySignedTransaction},
+    secp256k1::{Message, Secp256k1, XOnlyPublicKey},
+    util::{
+        schnorr::SchnorrSig,
+        sighash::{Prevouts, SchnorrSighashType, SighashCache},
+    },
+    OutPoint, Script, Transaction, TxOut, Txid, Witness,
+};
+use thiserror::Error;
+
+use crate::{
+    bitcoin::{
+        psbt::{decode_psbt, BitcoinPsbtError},
+        wallet::{get_blockchain, get_wallet, sync_wallet, BitcoinWalletError},
+    },
+    debug, info,
+    structs::{ReservesProofResponse, SecretString, VerifiedReservesResponse},
+};
+
+#[derive(Error, Debug)]
+pub enum BitcoinReservesError {
+    /// Wallet has no funds to prove
+    #[error("Wallet has no UTXOs to include in the proof of reserves")]
+    NoUtxos,
+    /// Proof could not be finalized
+    #[error("Proof of reserves could not be finalized")]
+    Unfinalized,
+    /// Proof has no challenge input for the given message
+    #[error("Proof of reserves does not commit to the given message")]
+    WrongChallenge,
+    /// Proof has no reserve inputs
+    #[error("Proof of reserves has no reserve inputs")]
+    EmptyProof,
+    /// Proof output is spendable
+    #[error("Proof of reserves must have a single unspendable output")]
+    SpendableOutput,
+    /// Proof input cannot be found in the blockchain
+    #[error("Proof of reserves input {0} was not found")]
+    UnknownInput(OutPoint),
+    /// Proof input is already spent
+    #[error("Proof of reserves input {0} is already spent")]
+    SpentInput(OutPoint),
+    /// Proof input is not a taproot key-path spend
+    #[error("Proof of reserves input {0} is not a taproot key-path spend")]
+    UnsupportedInput(OutPoint),
+    /// Proof input signature is invalid
+    #[error("Proof of reserves input {0} has an invalid signature")]
+    WrongSignature(OutPoint),
+    /// Proof input is repeated, so its value would be counted more than once
+    #[error("Proof of reserves input {0} is included more than once")]
+    DuplicateInput(OutPoint),
+    /// Proof input signature does not commit to all the inputs and outputs
+    #[error("Proof of reserves input {0} must be signed with SIGHASH_DEFAULT or SIGHASH_ALL")]
+    WrongSighash(OutPoint),
+    /// BitMask Core Bitcoin Psbt error
+    #[error(transparent)]
+    BitcoinPsbtError(#[from] BitcoinPsbtError),
+    /// BitMask Core Bitcoin Wallet error
+    #[error(transparent)]
+    BitcoinWalletError(#[from] BitcoinWalletError),
+    /// BDK error
+    #[error(transparent)]
+    BdkError(#[from] bdk::Error),
+    /// BDK esplora error
+    #[error(transparent)]
+    BdkEsploraError(#[from] bdk::esplora_client::Error),
+}
+
+/// BIP-127 challenge input, spending a non-existent output committed to the message
+fn challenge_outpoint(message: &str) -> OutPoint {
+    let message = format!("Proof-of-Reserves: {message}");
+    let txid = Txid::from_hash(sha256d::Hash::hash(message.as_bytes()));
+    OutPoint::new(txid, 0)
+}
+
+fn challenge_txout() -> TxOut {
+    TxOut {
+        value: 0,
+        script_pubkey: Builder::new().push_opcode(opcodes::OP_TRUE).into_script(),
+    }
+}
+
+fn unspendable_script() -> Script {
+    Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .into_script()
+}
+
+/// Creates a signed BIP-127 proof of reserves for all the wallet UTXOs.
+///
+/// The PSBT can never be broadcast: its first input spends a non-existent
+/// output derived from the message and its only output is unspendable.
+pub async fn create_proof_of_reserves(
+    descriptor: &SecretString,
+    change_descriptor: Option<&SecretString>,
+    message: &str,
+) -> Result<ReservesProofResponse, BitcoinReservesError> {
+    let wallet = get_wallet(descriptor, change_descriptor).await?;
+    sync_wallet(&wallet).await?;
+
+    let mut psbt = {
+        let locked_wallet = wallet.lock().await;
+        if locked_wallet.list_unspent()?.is_empty() {
+            return Err(BitcoinReservesError::NoUtxos);
+        }
+
+        let challenge_input = Input {
+            witness_utxo: Some(challenge_txout()),
+            final_script_sig: Some(Script::new()),
+            final_script_witness: Some(Witness::new()),
+            ..Default::default()
+        };
+
+        let mut builder = locked_wallet.build_tx();
+        builder
+            .drain_wallet()
+            .add_foreign_utxo(challenge_outpoint(message), challenge_input, 42)?
+            .only_witness_utxo()
+            .fee_absolute(0)
+            .drain_to(unspendable_script());
+        let (psbt, _) = builder.finish()?;
+        psbt
+    };
+
+    let opts = SignOptions {
+        trust_witness_utxo: true,
+        ..Default::default()
+    };
+    let finalized = wallet.lock().await.sign(&mut psbt, opts)?;
+    if !finalized {
+        return Err(BitcoinReservesError::Unfinalized);
+    }
+
+    let amount = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .fold(0, |sum, output| output.value + sum);
+
+    info!(format!("Proof of reserves: {amount} sats"));
+    Ok(ReservesProofResponse {
+        psbt: bitcoin::consensus::serialize(&psbt).to_hex(),
+        message: message.to_string(),
+        amount,
+    })
+}
+
+/// Verifies a BIP-127 proof of reserves against the explorer and returns the proven amount
+pub async fn verify_proof_of_reserves(
+    psbt: &str,
+    message: &str,
+) -> Result<VerifiedReservesResponse, BitcoinReservesError> {
+    let (psbt, _) = decode_psbt(psbt)?;
+    let tx = PartiallySignedTransaction::from(psbt).extract_tx();
+    check_proof_shape(&tx, message)?;
+
+    // Reserve outputs are always taken from the explorer, never from the PSBT
+    let blockchain = get_blockchain().await;
+    let challenge = challenge_outpoint(message);
+    if blockchain.get_tx(&challenge.txid).await?.is_some() {
+        return Err(BitcoinReservesError::WrongChallenge);
+    }
+    let mut prevouts = vec![];
+    for input in tx.input.iter().skip(1) {
+        let outpoint = input.previous_output;
+        let prev_tx = blockchain
+            .get_tx(&outpoint.txid)
+            .await?
+            .ok_or(BitcoinReservesError::UnknownInput(outpoint))?;
+        let prevout = prev_tx
+            .output
+            .get(outpoint.vout as usize)
+            .ok_or(BitcoinReservesError::UnknownInput(outpoint))?;
+
+        let status = blockchain
+            .get_output_status(&outpoint.txid, outpoint.vout as u64)
+            .await?;
+        if status.map(|status| status.spent).unwrap_or_default() {
+            return Err(BitcoinReservesError::SpentInput(outpoint));
+        }
+
+        prevouts.push(prevout.clone());
+    }
+
+    check_proof_of_reserves(&tx, message, &prevouts)
+}
+
+fn check_proof_shape(tx: &Transaction, message: &str) -> Result<(), BitcoinReservesError> {
+    match tx.input.first() {
+        Some(input) if input.previous_output == challenge_outpoint(message) => {}
+        _ => return Err(BitcoinReservesError::WrongChallenge),
+    }
+    if tx.input.len() < 2 {
+        return Err(BitcoinReservesError::EmptyProof);
+    }
+    // The challenge is part of the set, so no reserve input can spend it
+    let mut outpoints = BTreeSet::new();
+    if let Some(input) = tx
+        .input
+        .iter()
+        .find(|input| !outpoints.insert(input.previous_output))
+    {
+        return Err(BitcoinReservesError::DuplicateInput(input.previous_output));
+    }
+    if tx.output.len() != 1 || !tx.output[0].script_pubkey.is_provably_unspendable() {
+        return Err(BitcoinReservesError::SpendableOutput);
+    }
+    Ok(())
+}
+
+/// Verifies the proof of reserves signatures against the reserve outputs it spends.
+///
+/// `reserves` are the outputs spent by the proof inputs, after the challenge input.
+pub fn check_proof_of_reserves(
+    tx: &Transaction,
+    message: &str,
+    reserves: &[TxOut],
+) -> Result<VerifiedReservesResponse, BitcoinReservesError> {
+    check_proof_shape(tx, message)?;
+    if reserves.len() != tx.input.len() - 1 {
+        return Err(BitcoinReservesError::EmptyProof);
+    }
+
+    let mut prevouts = vec![challenge_txout()];
+    prevouts.extend_from_slice(reserves);
+
+    let secp = Secp256k1::verification_only();
+    let mut sighash_cache = SighashCache::new(tx);
+    let mut amount = 0;
+    let mut utxos = vec![];
+    for (index, input) in tx.input.iter().enumerate().skip(1) {
+        let outpoint = input.previous_output;
+        let prevout = &prevouts[index];
+        if !prevout.script_pubkey.is_v1_p2tr() || input.witness.len() != 1 {
+            return Err(BitcoinReservesError::UnsupportedInput(outpoint));
+        }
+
+        let output_key = XOnlyPublicKey::from_slice(&prevout.script_pubkey.as_bytes()[2..34])
+            .map_err(|_| BitcoinReservesError::UnsupportedInput(outpoint))?;
+        let signature = input
+            .witness
+            .last()
+            .and_then(|sig| SchnorrSig::from_slice(sig).ok())
+            .ok_or(BitcoinReservesError::WrongSignature(outpoint))?;
+
+        // Other sighash types can be reused for a proof of different inputs or outputs
+        if !matches!(
+            signature.hash_ty,
+            SchnorrSighashType::Default | SchnorrSighashType::All
+        ) {
+            return Err(BitcoinReservesError::WrongSighash(outpoint));
+        }
+
+        let sighash = sighash_cache
+            .taproot_key_spend_signature_hash(index, &Prevouts::All(&prevouts), signature.hash_ty)
+            .map_err(|_| BitcoinReservesError::WrongSignature(outpoint))?;
+        let msg = Message::from_slice(&sighash.into_inner())
+            .map_err(|_| BitcoinReservesError::WrongSignature(outpoint))?;
+        secp.verify_schnorr(&signature.sig, &msg, &output_key)
+            .map_err(|_| BitcoinReservesError::WrongSignature(outpoint))?;
+
+        amount += prevout.value;
+        utxos.push(outpoint.to_string());
+    }
+
+    debug!(format!("Proof of reserves verified: {utxos:#?}"));
+    Ok(VerifiedReservesResponse {
+        message: message.to_string(),
+        amount,
+        utxos,
+    })
+}
@@ -0,0 +1,311 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use bdk::{
+    miniscript::{Descriptor, DescriptorPublicKey, ToPublicKey},
+    wallet::AddressIndex,
+    FeeRate, KeychainKind, TransactionDetails,
+};
+use bitcoin::{
+    hashes::hex::ToHex,
+    secp256k1::Secp256k1,
+    util::{address::Address, taproot::TaprootBuilder},
+    Script,
+};
+use thiserror::Error;
+
+use crate::{
+    bitcoin::{
+        payment::{create_transaction, BitcoinPaymentError},
+        psbt::{sign_and_publish_psbt, BitcoinPsbtError},
+        wallet::{get_wallet, sync_wallet, BitcoinWalletError, MemoryWallet},
+    },
+    debug, info,
+    structs::{InheritanceVaultDetails, SatsInvoice, SecretString, VaultTimelock},
+};
+
+#[derive(Error, Debug)]
+pub enum BitcoinVaultError {
+    /// Vault keys must be single-key taproot descriptors
+    #[error("Wrong Descriptor: Vault keys must be single-key taproot descriptors")]
+    WrongKeyDescriptor,
+    /// Vault descriptor cannot be parsed or derived
+    #[error("Wrong Vault Descriptor: {0}")]
+    WrongVaultDescriptor(String),
+    /// Vault spending policy is not available
+    #[error("Vault spending policy is not available")]
+    NoSpendingPolicy,
+    /// Vault has no funds
+    #[error("Vault has no funds to spend")]
+    EmptyVault,
+    /// Absolute timelock can only be moved forward
+    #[error("Refreshed timelock must be later than the current one")]
+    WrongRefreshTimelock,
+    /// Timelock is zero or out of the block height range
+    #[error("Wrong Timelock: {0} is not a valid block height timelock")]
+    WrongTimelock(u32),
+    /// Tapret commitment cannot be added to the vault script tree
+    #[error("Tapret commitment cannot be added to the vault script tree: {0}")]
+    WrongTapret(String),
+    /// BitMask Core Bitcoin Payment error
+    #[error(transparent)]
+    BitcoinPaymentError(#[from] BitcoinPaymentError),
+    /// BitMask Core Bitcoin Psbt error
+    #[error(transparent)]
+    BitcoinPsbtError(#[from] BitcoinPsbtError),
+    /// BitMask Core Bitcoin Wallet error
+    #[error(transparent)]
+    BitcoinWalletError(#[from] BitcoinWalletError),
+    /// BDK error
+    #[error(transparent)]
+    BdkError(#[from] bdk::Error),
+    /// Bitcoin address error
+    #[error(transparent)]
+    BitcoinAddressError(#[from] bitcoin::util::address::Error),
+}
+
+/// nLockTime values from this threshold are timestamps instead of block heights
+const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+
+/// nSequence relative locks only use the low 16 bits as a block count. Larger values
+/// overlap the type flag (1 << 22) and the disable flag (1 << 31).
+const RELATIVE_LOCKTIME_MAX: u32 = 0xFFFF;
+
+/// Vault spending paths, in the same order BDK lists them in the taproot policy
+enum VaultBranch {
+    /// Owner key-path spend
+    Owner = 0,
+    /// Heir script-path spend, after the timelock
+    Recovery = 1,
+}
+
+fn descriptor_key(descriptor: &SecretString) -> Result<String, BitcoinVaultError> {
+    let descriptor = descriptor.0.split('#').next().unwrap_or_default();
+    match descriptor
+        .strip_prefix("tr(")
+        .and_then(|desc| desc.strip_suffix(')'))
+    {
+        Some(key) if !key.contains(',') => Ok(key.to_string()),
+        _ => Err(BitcoinVaultError::WrongKeyDescriptor),
+    }
+}
+
+/// Taproot descriptor with the owner as internal key and a single recovery leaf,
+/// spendable by the heir once the timelock expires
+pub fn vault_descriptor(
+    owner_descriptor: &SecretString,
+    heir_descriptor: &SecretString,
+    timelock: VaultTimelock,
+) -> Result<SecretString, BitcoinVaultError> {
+    let owner = descriptor_key(owner_descriptor)?;
+    let heir = descriptor_key(heir_descriptor)?;
+    let recovery = match timelock {
+        VaultTimelock::Relative(blocks) if blocks == 0 || blocks > RELATIVE_LOCKTIME_MAX => {
+            return Err(BitcoinVaultError::WrongTimelock(blocks))
+        }
+        VaultTimelock::Relative(blocks) => format!("older({blocks})"),
+        // Values from the threshold are interpreted as timestamps by the consensus rules
+        VaultTimelock::Absolute(height) if height == 0 || height >= LOCKTIME_THRESHOLD => {
+            return Err(BitcoinVaultError::WrongTimelock(height))
+        }
+        VaultTimelock::Absolute(height) => format!("after({height})"),
+    };
+
+    Ok(SecretString(format!(
+        "tr({owner},and_v(v:pk({heir}),{recovery}))"
+    )))
+}
+
+/// Merkle root of a vault output that also carries an RGB tapret commitment.
+///
+/// The tapret leaf is placed at depth 1, next to the recovery leaf, so the
+/// heir can still sweep the output after the timelock. The result can be used
+/// as the `tapret` of a `PsbtInputRequest` when spending the output.
+pub fn vault_tapret_tweak(
+    vault_descriptor: &SecretString,
+    index: u32,
+    tapret_script: Script,
+) -> Result<String, BitcoinVaultError> {
+    let secp = Secp256k1::new();
+    let descriptor = Descriptor::<DescriptorPublicKey>::from_str(&vault_descriptor.0)
+        .map_err(|op| BitcoinVaultError::WrongVaultDescriptor(op.to_string()))?;
+    let descriptor = descriptor
+        .at_derivation_index(index)
+        .derived_descriptor(&secp)
+        .map_err(|op| BitcoinVaultError::WrongVaultDescriptor(op.to_string()))?;
+
+    let tr = match descriptor {
+        Descriptor::Tr(tr) => tr,
+        _ => return Err(BitcoinVaultError::WrongKeyDescriptor),
+    };
+
+    let (_, recovery) = tr
+        .iter_scripts()
+        .next()
+        .ok_or(BitcoinVaultError::NoSpendingPolicy)?;
+
+    let spend_info = TaprootBuilder::new()
+        .add_leaf(1, recovery.encode())
+        .and_then(|builder| builder.add_leaf(1, tapret_script))
+        .map_err(|op| BitcoinVaultError::WrongTapret(op.to_string()))?
+        .finalize(&secp, tr.internal_key().to_x_only_pubkey())
+        .map_err(|_| BitcoinVaultError::WrongTapret("incomplete tree".to_string()))?;
+
+    match spend_info.merkle_root() {
+        Some(merkle_root) => Ok(merkle_root.to_hex()),
+        None => Err(BitcoinVaultError::WrongTapret("empty tree".to_string())),
+    }
+}
+
+async fn vault_details(
+    vault: &MemoryWallet,
+    timelock: VaultTimelock,
+    address: Address,
+    txid: Option<String>,
+) -> Result<InheritanceVaultDetails, BitcoinVaultError> {
+    let descriptor = vault
+        .lock()
+        .await
+        .public_descriptor(KeychainKind::External)?
+        .map(|desc| desc.to_string())
+        .unwrap_or_default();
+
+    Ok(InheritanceVaultDetails {
+        descriptor,
+        address: address.to_string(),
+        timelock,
+        txid,
+    })
+}
+
+async fn drain_vault(
+    vault: &MemoryWallet,
+    branch: VaultBranch,
+    destination: Script,
+    fee_rate: Option<f32>,
+) -> Result<TransactionDetails, BitcoinVaultError> {
+    sync_wallet(vault).await?;
+
+    let (psbt, details) = {
+        let locked_wallet = vault.lock().await;
+        if locked_wallet.list_unspent()?.is_empty() {
+            return Err(BitcoinVaultError::EmptyVault);
+        }
+
+        let policy = locked_wallet
+            .policies(KeychainKind::External)?
+            .ok_or(BitcoinVaultError::NoSpendingPolicy)?;
+        let policy_path = BTreeMap::from([(policy.id, vec![branch as usize])]);
+
+        let mut builder = locked_wallet.build_tx();
+        builder
+            .drain_wallet()
+            .drain_to(destination)
+            .policy_path(policy_path, KeychainKind::External)
+            .enable_rbf()
+            .fee_rate(fee_rate.map(FeeRate::from_sat_per_vb).unwrap_or_default());
+        builder.finish()?
+    };
+
+    debug!(format!("Drain vault: {details:#?}"));
+    let details = sign_and_publish_psbt(vault, psbt).await?;
+    Ok(details)
+}
+
+/// Funds a new inheritance vault from the owner's bitcoin wallet
+#[allow(clippy::too_many_arguments)]
+pub async fn create_inheritance_vault(
+    btc_descriptor_xprv: &SecretString,
+    btc_change_descriptor_xprv: &SecretString,
+    owner_descriptor_xpub: &SecretString,
+    heir_descriptor_xpub: &SecretString,
+    timelock: VaultTimelock,
+    amount: u64,
+    fee_rate: Option<f32>,
+) -> Result<InheritanceVaultDetails, BitcoinVaultError> {
+    let descriptor = vault_descriptor(owner_descriptor_xpub, heir_descriptor_xpub, timelock)?;
+    let vault = get_wallet(&descriptor, None).await?;
+    let address = vault.lock().await.get_address(AddressIndex::New)?.address;
+
+    let wallet = get_wallet(btc_descriptor_xprv, Some(btc_change_descriptor_xprv)).await?;
+    let invoice = SatsInvoice {
+        address: address.clone(),
+        amount,
+    };
+    let fee_rate = fee_rate.map(FeeRate::from_sat_per_vb);
+    let details = create_transaction(vec![invoice], &wallet, fee_rate).await?;
+
+    info!(format!("vault funding txid: {}", details.txid));
+    vault_details(&vault, timelock, address, Some(details.txid.to_hex())).await
+}
+
+/// Moves the vault funds through the owner key path into a fresh vault address,
+/// restarting a relative timelock or moving an absolute one to `new_timelock`
+pub async fn refresh_inheritance_vault(
+    owner_descriptor_xprv: &SecretString,
+    heir_descriptor_xpub: &SecretString,
+    timelock: VaultTimelock,
+    new_timelock: Option<VaultTimelock>,
+    fee_rate: Option<f32>,
+) -> Result<InheritanceVaultDetails, BitcoinVaultError> {
+    let new_timelock = new_timelock.unwrap_or(timelock);
+    if let (VaultTimelock::Absolute(current), VaultTimelock::Absolute(next)) =
+        (timelock, new_timelock)
+    {
+        if next <= current {
+            return Err(BitcoinVaultError::WrongRefreshTimelock);
+        }
+    }
+
+    let descriptor = vault_descriptor(owner_descriptor_xprv, heir_descriptor_xpub, timelock)?;
+    let vault = get_wallet(&descriptor, None).await?;
+
+    let new_descriptor =
+        vault_descriptor(owner_descriptor_xprv, heir_descriptor_xpub, new_timelock)?;
+    let new_vault = get_wallet(&new_descriptor, None).await?;
+    let address = new_vault
+        .lock()
+        .await
+        .get_address(AddressIndex::New)?
+        .address;
+
+    let details = drain_vault(
+        &vault,
+        VaultBranch::Owner,
+        address.script_pubkey(),
+        fee_rate,
+    )
+    .await?;
+
+    info!(format!("vault refresh txid: {}", details.txid));
+    vault_details(
+        &new_vault,
+        new_timelock,
+        address,
+        Some(details.txid.to_hex()),
+    )
+    .await
+}
+
+/// Sweeps the vault funds through the heir recovery branch, once the timelock expires
+pub async fn sweep_inheritance_vault(
+    owner_descriptor_xpub: &SecretString,
+    heir_descriptor_xprv: &SecretString,
+    timelock: VaultTimelock,
+    destination: &str,
+    fee_rate: Option<f32>,
+) -> Result<TransactionDetails, BitcoinVaultError> {
+    let descriptor = vault_descriptor(owner_descriptor_xpub, heir_descriptor_xprv, timelock)?;
+    let vault = get_wallet(&descriptor, None).await?;
+
+    let address = Address::from_str(destination)?;
+    let details = drain_vault(
+        &vault,
+        VaultBranch::Recovery,
+        address.script_pubkey(),
+        fee_rate,
+    )
+    .await?;
+
+    info!(format!("vault sweep txid: {}", details.txid));
+    Ok(details)
+}
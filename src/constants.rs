@@ -26,6 +26,9 @@ pub const BMC_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub static ELECTRUM_TIMEOUT: u8 = 4;
 
+// Payment Watcher (polling interval in seconds, same floor as the RGB receiver)
+pub const PAYMENT_WATCHER_MIN_INTERVAL: u64 = 10;
+
 static BITCOIN_EXPLORER_API_MAINNET: Lazy<RwLock<String>> =
     Lazy::new(|| RwLock::new(dot_env("BITCOIN_EXPLORER_API_MAINNET")));
 static BITCOIN_EXPLORER_API_TESTNET: Lazy<RwLock<String>> =
@@ -154,6 +157,10 @@ pub static BITMASK_ENDPOINT: Lazy<RwLock<String>> =
 pub static RGB_PROXY_ENDPOINT: Lazy<RwLock<String>> =
     Lazy::new(|| RwLock::new(dot_env("RGB_PROXY_ENDPOINT")));
 
+// other rgb proxies reachable through the node (comma separated)
+pub static RGB_TRANSPORT_ENDPOINTS: Lazy<RwLock<String>> =
+    Lazy::new(|| RwLock::new(dot_env("RGB_TRANSPORT_ENDPOINTS")));
+
 // carbonado
 pub static CARBONADO_ENDPOINT: Lazy<RwLock<String>> =
     Lazy::new(|| RwLock::new(dot_env("CARBONADO_ENDPOINT")));
@@ -219,6 +226,10 @@ pub mod storage_keys {
     pub const ASSETS_TRANSFERS: &str = "bitmask_assets_transfers.c15";
     pub const ASSETS_OFFERS: &str = "bitmask-asset_offers.c15";
     pub const ASSETS_BIDS: &str = "bitmask-asset_bids.c15";
+    pub const ASSETS_INVOICES: &str = "bitmask-asset_invoices.c15";
+    pub const ASSETS_AIRDROPS: &str = "bitmask-asset_airdrops.c15";
+    pub const ASSETS_SEAL_SECRETS: &str = "bitmask-asset_seal_secrets.c15";
+    pub const ASSETS_CONSIGNMENTS: &str = "bitmask-asset_consignments";
     pub const MARKETPLACE_OFFERS: &str = "bitmask-marketplace_public_offers.c15";
     pub const MARKETPLACE_BIDS: &str = "bitmask-marketplace_public_bids.c15";
 }
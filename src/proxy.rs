@@ -9,12 +9,15 @@ pub enum ProxyServerError {
     Parse(String),
     /// All endpoints failed error
     AllEndpointsFailed,
+    /// Endpoint '{0}' is not a configured RGB transport
+    UnknownEndpoint(String),
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use server::{
-    handle_file, proxy_consig_retrieve, proxy_consig_store, proxy_media_data_store,
-    proxy_media_retrieve, proxy_metadata_retrieve,
+    check_transport_endpoint, handle_file, proxy_consig_retrieve, proxy_consig_retrieve_at,
+    proxy_consig_store, proxy_consig_store_at, proxy_media_data_store, proxy_media_retrieve,
+    proxy_metadata_retrieve,
 };
 #[cfg(not(target_arch = "wasm32"))]
 mod server {
@@ -26,7 +29,7 @@ mod server {
     use tokio::fs;
 
     use crate::{
-        constants::{NETWORK, RGB_PROXY_ENDPOINT},
+        constants::{NETWORK, RGB_PROXY_ENDPOINT, RGB_TRANSPORT_ENDPOINTS},
         info,
         rgb::structs::{
             MediaMetadata, RgbProxyConsigFileReq, RgbProxyConsigReq, RgbProxyConsigRes,
@@ -41,6 +44,18 @@ mod server {
 
     pub async fn proxy_consig_store(
         request: RgbProxyConsigFileReq,
+    ) -> Result<RgbProxyConsigUploadRes, ProxyServerError> {
+        let endpoint = RGB_PROXY_ENDPOINT.read().await.to_string();
+        proxy_consig_store_at(&endpoint, "", request).await
+    }
+
+    /// Stores the consignment in the RGB proxy served at `endpoint`
+    ///
+    /// The token only authenticates the relay of the browser client.
+    pub async fn proxy_consig_store_at(
+        endpoint: &str,
+        _token: &str,
+        request: RgbProxyConsigFileReq,
     ) -> Result<RgbProxyConsigUploadRes, ProxyServerError> {
         let RgbProxyConsigFileReq {
             file_name,
@@ -61,7 +76,7 @@ mod server {
             file_name: filepath.to_string_lossy().to_string(),
         };
 
-        let resp = fetch_consignment_post(request_data).await?;
+        let resp = fetch_consignment_post(endpoint, request_data).await?;
         fs::remove_file(filepath)
             .await
             .map_err(|op| ProxyServerError::Server(op.to_string()))?;
@@ -72,7 +87,39 @@ mod server {
     pub async fn proxy_consig_retrieve(
         request_id: &str,
     ) -> Result<Option<RgbProxyConsigRes>, ProxyServerError> {
-        fetch_consignment_get(request_id).await
+        let endpoint = RGB_PROXY_ENDPOINT.read().await.to_string();
+        fetch_consignment_get(&endpoint, request_id).await
+    }
+
+    /// Retrieves the consignment from the RGB proxy served at `endpoint`
+    ///
+    /// The token only authenticates the relay of the browser client.
+    pub async fn proxy_consig_retrieve_at(
+        endpoint: &str,
+        _token: &str,
+        request_id: &str,
+    ) -> Result<Option<RgbProxyConsigRes>, ProxyServerError> {
+        fetch_consignment_get(endpoint, request_id).await
+    }
+
+    /// Checks that `endpoint` is the RGB proxy of the node or one of the
+    /// `RGB_TRANSPORT_ENDPOINTS`, so requests are never relayed to other hosts
+    pub async fn check_transport_endpoint(endpoint: &str) -> Result<(), ProxyServerError> {
+        let proxy_endpoint = RGB_PROXY_ENDPOINT.read().await.to_string();
+        let transport_endpoints = RGB_TRANSPORT_ENDPOINTS.read().await.to_string();
+
+        let endpoint = endpoint.trim_end_matches('/');
+        let configured = transport_endpoints
+            .split(',')
+            .chain([proxy_endpoint.as_str()])
+            .map(|configured| configured.trim().trim_end_matches('/'))
+            .any(|configured| !configured.is_empty() && configured == endpoint);
+
+        if !configured {
+            return Err(ProxyServerError::UnknownEndpoint(endpoint.to_string()));
+        }
+
+        Ok(())
     }
 
     pub async fn proxy_media_retrieve(
@@ -203,10 +250,10 @@ mod server {
     }
 
     async fn fetch_consignment_post(
+        endpoint: &str,
         request: RgbProxyConsigUploadReq,
     ) -> Result<RgbProxyConsigUploadRes, ProxyServerError> {
-        let endpoints = RGB_PROXY_ENDPOINT.read().await.to_string();
-        let url = format!("{endpoints}/json-rpc");
+        let url = format!("{endpoint}/json-rpc");
 
         let file_info = fs::read(request.file_name.clone())
             .await
@@ -231,10 +278,10 @@ mod server {
     }
 
     async fn fetch_consignment_get(
+        endpoint: &str,
         recipient_id: &str,
     ) -> Result<Option<RgbProxyConsigRes>, ProxyServerError> {
-        let endpoints = RGB_PROXY_ENDPOINT.read().await.to_string();
-        let url = format!("{endpoints}/json-rpc");
+        let url = format!("{endpoint}/json-rpc");
 
         let body = serde_json::to_string(&RgbProxyConsigReq {
             recipient_id: recipient_id.to_string(),
@@ -336,8 +383,8 @@ mod server {
 
 #[cfg(target_arch = "wasm32")]
 pub use client::{
-    proxy_consig_retrieve, proxy_consig_store, proxy_media_data_store, proxy_media_retrieve,
-    proxy_metadata_retrieve,
+    proxy_consig_retrieve, proxy_consig_retrieve_at, proxy_consig_store, proxy_consig_store_at,
+    proxy_media_data_store, proxy_media_retrieve, proxy_metadata_retrieve,
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -346,10 +393,11 @@ mod client {
         constants::{BITMASK_ENDPOINT, NETWORK},
         rgb::structs::{
             MediaMetadata, RgbProxyConsigCarbonadoReq, RgbProxyConsigFileReq, RgbProxyConsigRes,
-            RgbProxyConsigUploadRes, RgbProxyMediaRes,
+            RgbProxyConsigUploadRes, RgbProxyMediaRes, RgbProxyTransportConsigReq,
+            RgbProxyTransportRetrieveReq,
         },
         structs::{MediaEncode, MediaExtractRequest, MediaItemRequest},
-        util::{get, post_json},
+        util::{get, post_json, post_json_auth},
     };
 
     use super::ProxyServerError;
@@ -391,6 +439,54 @@ mod client {
         Ok(resp)
     }
 
+    /// Browsers cannot reach arbitrary RGB proxies, so the request is relayed by bitmaskd
+    pub async fn proxy_consig_store_at(
+        endpoint: &str,
+        token: &str,
+        request: RgbProxyConsigFileReq,
+    ) -> Result<RgbProxyConsigUploadRes, ProxyServerError> {
+        let bitmask_endpoint = BITMASK_ENDPOINT.read().await.to_string();
+
+        let url = format!("{bitmask_endpoint}/proxy/transport/consignment");
+        let body = RgbProxyTransportConsigReq {
+            endpoint: endpoint.to_string(),
+            request: RgbProxyConsigCarbonadoReq::from(request),
+        };
+        let token = format!("Bearer {token}");
+        let reponse = post_json_auth(&url, &Some(body), Some(&token))
+            .await
+            .map_err(|op| ProxyServerError::Parse(op.to_string()))?;
+
+        let result = serde_json::from_str::<RgbProxyConsigUploadRes>(&reponse)
+            .map_err(|op| ProxyServerError::Parse(op.to_string()))?;
+        Ok(result)
+    }
+
+    pub async fn proxy_consig_retrieve_at(
+        endpoint: &str,
+        token: &str,
+        request_id: &str,
+    ) -> Result<Option<RgbProxyConsigRes>, ProxyServerError> {
+        let bitmask_endpoint = BITMASK_ENDPOINT.read().await.to_string();
+
+        let url = format!("{bitmask_endpoint}/proxy/transport/consignment/retrieve");
+        let body = RgbProxyTransportRetrieveReq {
+            endpoint: endpoint.to_string(),
+            recipient_id: request_id.to_string(),
+        };
+        let token = format!("Bearer {token}");
+        let reponse = post_json_auth(&url, &Some(body), Some(&token))
+            .await
+            .map_err(|op| ProxyServerError::Parse(op.to_string()))?;
+
+        let resp = match serde_json::from_str::<RgbProxyConsigRes>(&reponse) {
+            Ok(resp) => Some(resp),
+            Err(_) => None,
+        };
+
+        Ok(resp)
+    }
+
     pub async fn proxy_media_retrieve(
         attachment_id: &str,
     ) -> Result<Option<RgbProxyMediaRes>, ProxyServerError> {
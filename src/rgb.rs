@@ -1,7 +1,9 @@
-use ::psbt::{serialize::Serialize, Psbt};
+use ::psbt::{serialize::Serialize, Psbt, PsbtVersion};
+use ::wallet::onchain::ResolveTx;
 use amplify::{confinement::U32, hex::ToHex};
 use anyhow::Result;
 use autosurgeon::reconcile;
+use bdk::blockchain::GetHeight;
 use bitcoin::{psbt::PartiallySignedTransaction, Network, Txid};
 use bitcoin_30::bip32::ExtendedPubKey;
 use bitcoin_scripts::address::AddressNetwork;
@@ -9,15 +11,15 @@ use futures::TryFutureExt;
 use garde::Validate;
 
 use miniscript_crate::DescriptorPublicKey;
-use rgb::{RgbDescr, RgbWallet};
+use rgb::{RgbDescr, RgbWallet, TerminalPath};
 use rgbstd::{
-    containers::BindleContent,
+    containers::{BindleContent, Contract},
     contract::ContractId,
-    interface::TypedState,
+    interface::{IfaceId, TypedState},
     persistence::{Inventory, Stash, Stock},
     validation::Validity,
 };
-use rgbwallet::{psbt::DbcPsbtError, RgbInvoice};
+use rgbwallet::{psbt::DbcPsbtError, Beneficiary, RgbInvoice, RgbTransport};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     ops::Sub,
@@ -36,81 +38,112 @@ pub mod crdt;
 pub mod fs;
 pub mod import;
 pub mod issue;
+pub mod ledger;
 pub mod prebuild;
 pub mod prefetch;
 pub mod proxy;
 pub mod psbt;
+pub mod receiver;
+pub mod report;
 pub mod resolvers;
+pub mod schemata;
 pub mod structs;
 pub mod swap;
 pub mod transfer;
 pub mod wallet;
 
 use crate::{
+    bitcoin::get_blockchain,
     constants::{get_network, BITCOIN_EXPLORER_API, NETWORK},
     rgb::{
         issue::{issue_contract as create_contract, IssueContractError},
-        psbt::{create_psbt as create_rgb_psbt, extract_output_commit},
+        psbt::{create_psbt as create_rgb_psbt, extract_output_commit, psbt_fee},
         resolvers::ExplorerResolver,
         transfer::{
-            accept_transfer as accept_rgb_transfer, create_invoice as create_rgb_invoice,
-            pay_invoice,
+            accept_transfer as accept_rgb_transfer, check_invoice_amount, create_burn_invoice,
+            create_invoice as create_rgb_invoice, create_witness_invoice, fill_invoice_amount,
+            pay_invoice, remove_seal_secrets, reveal_seal_secrets, transfer_invoice_amount,
+            transfer_seal_amount, transfer_seal_utxos, transfer_secret_seals,
+            transfer_witness_vouts, unused_seal_secrets, witness_address_vouts, SealSecrets,
         },
         wallet::list_allocations,
     },
     structs::{
-        AcceptRequest, AcceptResponse, AssetType, BatchRgbTransferItem, BatchRgbTransferResponse,
+        AcceptRequest, AcceptResponse, AirdropStatus, AllocationDetail, AllocationValue, AssetType,
+        BatchRgbTransferItem, BatchRgbTransferResponse, CancelInvoiceRequest,
         ContractHiddenResponse, ContractResponse, ContractsResponse, FullRgbTransferRequest,
-        ImportRequest, InterfaceDetail, InterfacesResponse, InvoiceRequest, InvoiceResponse,
-        IssueMediaRequest, IssueRequest, IssueResponse, MediaEncode, MediaRequest, MediaResponse,
-        MediaView, NextAddressResponse, NextUtxoResponse, NextUtxosResponse, PsbtFeeRequest,
-        PsbtRequest, PsbtResponse, PublicRgbBidResponse, PublicRgbOfferResponse,
-        PublicRgbOffersResponse, ReIssueRequest, ReIssueResponse, RgbBidDetail, RgbBidRequest,
-        RgbBidResponse, RgbBidsResponse, RgbInternalSaveTransferRequest,
+        ImportRequest, InflationAllowanceRequest, InterfaceDetail, InterfacesResponse,
+        InvoiceBeneficiaryType, InvoiceCleanupResponse, InvoiceConsignment,
+        InvoiceConsignmentsResponse, InvoiceDetail, InvoicePaymentDetail, InvoiceRequest,
+        InvoiceResponse, InvoicesRequest, InvoicesResponse, IssueBeneficiaryRequest,
+        IssueMediaRequest, IssueRequest, IssueResponse, IssueTermsRequest, MediaEncode,
+        MediaRequest, MediaResponse, MediaView, NextAddressResponse, NextUtxoResponse,
+        NextUtxosResponse, PsbtFeeRequest, PsbtFormat, PsbtInputRequest, PsbtRequest, PsbtResponse,
+        PublicRgbBidResponse, PublicRgbOfferResponse, PublicRgbOffersResponse, ReIssueRequest,
+        ReIssueResponse, RgbAirdropRecipient, RgbAirdropRequest, RgbAirdropResponse, RgbBidDetail,
+        RgbBidRequest, RgbBidResponse, RgbBidsResponse, RgbBurnRequest, RgbCancelTransferRequest,
+        RgbCancelTransferResponse, RgbDoubleSpendRequest, RgbInternalSaveTransferRequest,
         RgbInternalTransferResponse, RgbInvoiceResponse, RgbOfferBidsResponse, RgbOfferDetail,
         RgbOfferRequest, RgbOfferResponse, RgbOfferUpdateRequest, RgbOfferUpdateResponse,
-        RgbOffersResponse, RgbRemoveTransferRequest, RgbReplaceResponse, RgbSaveTransferRequest,
-        RgbSwapRequest, RgbSwapResponse, RgbTransferDetail, RgbTransferRequest,
-        RgbTransferResponse, RgbTransferStatusResponse, RgbTransfersResponse, SchemaDetail,
-        SchemasResponse, SimpleContractResponse, TransferType, TxStatus, UtxoResponse,
+        RgbOffersResponse, RgbReceivedTransfer, RgbReceiverRequest, RgbReceiverResponse,
+        RgbRemoveTransferRequest, RgbReorgRequest, RgbReorgResponse, RgbReorgTransfer,
+        RgbReplaceResponse, RgbSaveTransferRequest, RgbSwapRequest, RgbSwapResponse,
+        RgbTransferDetail, RgbTransferPreviewAllocation, RgbTransferPreviewResponse,
+        RgbTransferRequest, RgbTransferResponse, RgbTransferStateRequest, RgbTransferStateResponse,
+        RgbTransferStatusResponse, RgbTransfersResponse, RgbValidationReport, SchemaDetail,
+        SchemasResponse, SimpleContractResponse, TermsEncode, TransferState, TransferType,
+        TxStatus, UtxoResponse, VerifyTermsRequest, VerifyTermsResponse, WatcherDetail,
         WatcherDetailResponse, WatcherRequest, WatcherResponse, WatcherUtxoResponse,
     },
-    validators::RGBContext,
+    validators::{parse_invoice_transport, verify_rgb_invoice, RGBContext},
 };
 
 use self::{
     consignment::NewTransferOptions,
-    constants::{RGB_DEFAULT_FETCH_LIMIT, RGB_DEFAULT_NAME},
-    contract::{export_boilerplate, export_contract, extract_metadata, ExportContractError},
+    constants::{
+        RGB_AIRDROP_MAX_RECIPIENTS, RGB_DEFAULT_FETCH_LIMIT, RGB_DEFAULT_NAME,
+        RGB_INVOICE_MAX_AMOUNT_PARAM, RGB_INVOICE_MIN_AMOUNT_PARAM,
+    },
+    contract::{
+        export_boilerplate, export_contract, extract_metadata, terms_digest, ExportContractError,
+    },
     crdt::{LocalRgbAccount, RawRgbAccount, RgbMerge},
     fs::{
-        retrieve_account, retrieve_bids, retrieve_local_account, retrieve_offers,
-        retrieve_public_offers, retrieve_stock as retrieve_rgb_stock, retrieve_stock_account,
+        retrieve_account, retrieve_airdrops, retrieve_bids, retrieve_consignment,
+        retrieve_invoices, retrieve_local_account, retrieve_offers, retrieve_public_offers,
+        retrieve_seal_secrets, retrieve_stock as retrieve_rgb_stock, retrieve_stock_account,
         retrieve_stock_account_transfers, retrieve_stock_transfers, retrieve_transfers,
-        store_account, store_bids, store_local_account, store_offers,
-        store_stock as store_rgb_stock, store_stock_account, store_stock_account_transfers,
-        store_stock_transfers, store_transfers, RgbPersistenceError,
+        store_account, store_airdrops, store_bids, store_consignment, store_invoices,
+        store_local_account, store_offers, store_seal_secrets, store_stock as store_rgb_stock,
+        store_stock_account, store_stock_account_transfers, store_stock_transfers, store_transfers,
+        RgbPersistenceError,
     },
     import::{import_contract, ImportContractError},
+    ledger::{record_invoice_payments, RgbInvoiceEntry, RgbInvoices},
     prebuild::{
         prebuild_buyer_swap, prebuild_extract_transfer, prebuild_seller_swap,
-        prebuild_transfer_asset,
+        prebuild_transfer_asset, witness_output_change,
     },
     prefetch::{
         prefetch_resolver_allocations, prefetch_resolver_import_rgb, prefetch_resolver_psbt,
-        prefetch_resolver_rgb, prefetch_resolver_txs_status, prefetch_resolver_user_utxo_status,
-        prefetch_resolver_utxos, prefetch_resolver_waddress, prefetch_resolver_wutxo,
+        prefetch_resolver_rgb, prefetch_resolver_txs, prefetch_resolver_txs_status,
+        prefetch_resolver_user_utxo_status, prefetch_resolver_utxos, prefetch_resolver_waddress,
+        prefetch_resolver_wutxo,
     },
     proxy::{
         get_consignment as get_rgb_consignment, get_media_metadata as get_rgb_media_metadata,
-        post_consignments, post_media_metadata, post_media_metadata_list, ProxyError,
+        get_transport_consignment, post_consignments, post_contract_consignments,
+        post_media_metadata, post_media_metadata_list, post_transport_consignments, ProxyError,
     },
     psbt::{
-        save_tap_commit_str, set_tapret_output, CreatePsbtError, EstimateFeeError, NewPsbtOptions,
+        remove_tap_commit_str, save_tap_commit_str, set_tapret_output, CreatePsbtError,
+        EstimateFeeError, NewPsbtOptions,
     },
+    report::{rejection_report, validation_report},
+    schemata::check_schema_rules,
     structs::{
-        ContractAmount, ContractBoilerplate, MediaMetadata, RgbAccountV1, RgbExtractTransfer,
-        RgbTransferV1, RgbTransfersV1,
+        ContractAmount, ContractAmountError, ContractBoilerplate, MediaMetadata, RgbAccountV1,
+        RgbExtractTransfer, RgbTransferAnchor, RgbTransferLifecycle, RgbTransferV1, RgbTransfersV2,
     },
     swap::{
         get_public_offer, get_swap_bid, get_swap_bid_by_buyer, get_swap_bids_by_seller,
@@ -152,16 +185,9 @@ pub async fn issue_contract(sk: &str, request: IssueRequest) -> Result<IssueResp
         return Err(IssueError::Validation(errors));
     }
 
-    let IssueRequest {
-        ticker,
-        name,
-        description,
-        supply,
-        precision,
-        iface,
-        seal,
-        meta,
-    } = request;
+    let seal = request.seal.clone();
+    let precision = request.precision;
+    let beneficiaries = request.beneficiaries.clone();
 
     let mut resolver = ExplorerResolver {
         explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
@@ -169,12 +195,11 @@ pub async fn issue_contract(sk: &str, request: IssueRequest) -> Result<IssueResp
     };
 
     let (mut stock, mut rgb_account) = retrieve_stock_account(sk).await.map_err(IssueError::IO)?;
-    let network = get_network().await;
     let wallet = rgb_account.wallets.get(RGB_DEFAULT_NAME);
     let mut wallet = match wallet {
         Some(wallet) => {
             let mut fetch_wallet = wallet.to_owned();
-            for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+            for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
                 let contract_index = contract_type as u32;
                 prefetch_resolver_utxos(
                     contract_index,
@@ -197,21 +222,7 @@ pub async fn issue_contract(sk: &str, request: IssueRequest) -> Result<IssueResp
         _ => None,
     };
 
-    let contract_amount = ContractAmount::with(supply, precision);
-    let contract = create_contract(
-        &ticker,
-        &name,
-        &description,
-        precision,
-        contract_amount.to_value(),
-        &iface,
-        &seal,
-        &network,
-        meta,
-        &mut resolver,
-        &mut stock,
-    )
-    .map_err(IssueError::Issue)?;
+    let contract = internal_issue_contract(request, &mut resolver, &mut stock).await?;
 
     let ContractResponse {
         contract_id,
@@ -244,6 +255,20 @@ pub async fn issue_contract(sk: &str, request: IssueRequest) -> Result<IssueResp
         None
     };
 
+    // The genesis has no history, so the contract is the whole consignment of
+    // every beneficiary: all of them receive the same contract, where the blinded
+    // seals of the others stay concealed
+    let consignments: BTreeMap<String, String> = beneficiaries
+        .into_iter()
+        .map(|beneficiary| (beneficiary.seal, contract.strict.clone()))
+        .collect();
+
+    if !consignments.is_empty() {
+        post_contract_consignments(consignments.clone())
+            .await
+            .map_err(IssueError::Proxy)?;
+    }
+
     if let Some(wallet) = wallet {
         rgb_account
             .wallets
@@ -269,9 +294,106 @@ pub async fn issue_contract(sk: &str, request: IssueRequest) -> Result<IssueResp
         issue_method: "tapret1st".to_string(),
         issue_utxo: seal.replace("tapret1st:", ""),
         meta,
+        consignments,
     })
 }
 
+/// Builds and validates the contract genesis without storing it.
+///
+/// The stock is retrieved only to resolve the interfaces, and it is discarded
+/// after the contract is exported, so the issuer can review it before issuing.
+/// Issuing the same request with the `created` date of the preview reproduces
+/// the previewed contract id.
+pub async fn preview_issue_contract(
+    sk: &str,
+    request: IssueRequest,
+) -> Result<ContractResponse, IssueError> {
+    if let Err(err) = request.validate(&RGBContext::default()) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(IssueError::Validation(errors));
+    }
+
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let mut stock = retrieve_rgb_stock(sk).await.map_err(IssueError::IO)?;
+    let contract = internal_issue_contract(request, &mut resolver, &mut stock).await?;
+
+    let resp = export_contract(contract.contract_id(), &mut stock, &mut resolver, &mut None)
+        .map_err(IssueError::Export)?;
+
+    Ok(resp)
+}
+
+async fn internal_issue_contract(
+    request: IssueRequest,
+    resolver: &mut ExplorerResolver,
+    stock: &mut Stock,
+) -> Result<Contract, IssueError> {
+    let IssueRequest {
+        ticker,
+        name,
+        description,
+        supply,
+        precision,
+        iface,
+        seal,
+        meta,
+        inflation,
+        collection,
+        beneficiaries,
+        terms,
+        fractional,
+        created,
+    } = request;
+
+    let contract_amount = ContractAmount::with(supply, precision);
+    let beneficiaries: Vec<IssueBeneficiaryRequest> = beneficiaries
+        .into_iter()
+        .map(|beneficiary| IssueBeneficiaryRequest {
+            amount: ContractAmount::with(beneficiary.amount, precision).to_value(),
+            ..beneficiary
+        })
+        .collect();
+
+    let inflation = inflation
+        .into_iter()
+        .map(|allowance| InflationAllowanceRequest {
+            amount: ContractAmount::with(allowance.amount, precision).to_value(),
+            ..allowance
+        })
+        .collect();
+
+    let network = get_network().await;
+    let contract = create_contract(
+        &ticker,
+        &name,
+        &description,
+        precision,
+        contract_amount.to_value(),
+        &iface,
+        &seal,
+        &network,
+        meta,
+        inflation,
+        collection,
+        beneficiaries,
+        terms,
+        fractional,
+        created,
+        resolver,
+        stock,
+    )
+    .map_err(IssueError::Issue)?;
+
+    Ok(contract)
+}
+
 pub async fn reissue_contract(
     sk: &str,
     request: ReIssueRequest,
@@ -302,6 +424,9 @@ pub async fn reissue_contract(
             precision,
             allocations,
             meta: contract_meta,
+            allowance,
+            tokens,
+            terms,
             ..
         } = contract;
 
@@ -311,6 +436,26 @@ pub async fn reissue_contract(
             .collect();
         let seal = seals.first().unwrap().to_owned();
 
+        // Remaining inflation rights are reissued to the owner seal
+        let inflation = allowance
+            .filter(|amount| *amount > 0)
+            .map(|amount| InflationAllowanceRequest {
+                seal: seal.clone(),
+                amount,
+            })
+            .into_iter()
+            .collect();
+
+        // Collections are reissued with all their tokens, one unit each
+        let (supply, collection) = match &contract_meta {
+            None if !tokens.is_empty() => {
+                (1, tokens.into_iter().map(IssueMediaRequest::from).collect())
+            }
+            _ => (supply, vec![]),
+        };
+        // Only fractional tokens are issued as RGB21 with many units
+        let fractional = iface == "RGB21" && collection.is_empty() && supply > 1;
+
         // TODO: Move to rgb/issue sub-module
         let meta = contract_meta.map(IssueMediaRequest::from);
         let network = get_network().await;
@@ -318,7 +463,7 @@ pub async fn reissue_contract(
         let mut wallet = match wallet {
             Some(wallet) => {
                 let mut fetch_wallet = wallet.to_owned();
-                for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+                for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
                     prefetch_resolver_utxos(
                         contract_type as u32,
                         &mut fetch_wallet,
@@ -343,6 +488,12 @@ pub async fn reissue_contract(
             &seal,
             &network,
             meta,
+            inflation,
+            collection,
+            vec![],
+            terms.map(IssueTermsRequest::from),
+            fractional,
+            None,
             &mut resolver,
             &mut stock,
         )
@@ -400,6 +551,7 @@ pub async fn reissue_contract(
             issue_method: "tapret1st".to_string(),
             issue_utxo: seal.replace("tapret1st:", ""),
             meta,
+            ..Default::default()
         });
     }
 
@@ -425,6 +577,16 @@ pub enum InvoiceError {
     IO(RgbPersistenceError),
     /// Occurs an error in invoice step. {0}
     Invoice(NewInvoiceError),
+    /// Invoice expiration date ({0}) must be in the future.
+    WrongExpiry(i64),
+    /// Invoice not found in the account.
+    NoInvoice,
+    /// Invoice amount range requires an open amount and a minimum below the maximum.
+    WrongAmountRange,
+    /// Invoice amount cannot be decoded. {0}
+    WrongAmount(ContractAmountError),
+    /// Occurs an error in explorer connection. {0}
+    Explorer(String),
 }
 
 pub async fn create_invoice(
@@ -434,12 +596,38 @@ pub async fn create_invoice(
     let (mut stock, mut rgb_account) =
         retrieve_stock_account(sk).await.map_err(InvoiceError::IO)?;
 
-    let invoice = internal_create_invoice(request, &mut stock).await?;
+    let mut rgb_invoices = retrieve_invoices(sk).await.map_err(InvoiceError::IO)?;
+    let mut seal_secrets = retrieve_seal_secrets(sk).await.map_err(InvoiceError::IO)?;
+
+    // Blinded seals are written as "method:txid:vout"
+    let outpoint = request
+        .seal
+        .split_once(':')
+        .map(|(_, outpoint)| outpoint.to_string());
+    let iface = request.iface.clone();
+    let amount = match request.amount.is_empty() {
+        true => 0,
+        false => ContractAmount::from_raw(request.amount.to_string())
+            .map_err(InvoiceError::WrongAmount)?
+            .to_value(),
+    };
+
+    let invoice = internal_create_invoice(request, &mut stock, &mut seal_secrets).await?;
     rgb_account.invoices.push(invoice.to_string());
+    rgb_invoices.invoices.insert(
+        invoice.to_string(),
+        RgbInvoiceEntry::with(&invoice, &iface, amount, outpoint),
+    );
 
     store_stock_account(sk, stock, rgb_account)
         .await
         .map_err(InvoiceError::IO)?;
+    store_invoices(sk, rgb_invoices)
+        .await
+        .map_err(InvoiceError::IO)?;
+    store_seal_secrets(sk, seal_secrets)
+        .await
+        .map_err(InvoiceError::IO)?;
 
     Ok(InvoiceResponse {
         invoice: invoice.to_string(),
@@ -449,6 +637,7 @@ pub async fn create_invoice(
 async fn internal_create_invoice(
     request: InvoiceRequest,
     stock: &mut Stock,
+    seal_secrets: &mut SealSecrets,
 ) -> Result<RgbInvoice, InvoiceError> {
     if let Err(err) = request.validate(&RGBContext::default()) {
         let errors = err
@@ -463,36 +652,304 @@ async fn internal_create_invoice(
         iface,
         seal,
         amount,
+        min_amount,
+        max_amount,
         params,
+        transports,
+        expire_at,
     } = request;
 
+    if let Some(expire_at) = expire_at {
+        let utc = chrono::Local::now().naive_utc().timestamp();
+        if expire_at.sub(utc) <= 0 {
+            return Err(InvoiceError::WrongExpiry(expire_at));
+        }
+    }
+
     let network = NETWORK.read().await.to_string();
 
     let contr_id = ContractId::from_str(&contract_id).map_err(|_| InvoiceError::NoContract)?;
     let boilerplate = export_boilerplate(contr_id, stock).map_err(|_| InvoiceError::NoContract)?;
-    let invoice_amount = ContractAmount::from_raw(amount.to_string());
-    if invoice_amount.precision != boilerplate.precision {
-        return Err(InvoiceError::WrongPrecision(
-            boilerplate.precision,
-            invoice_amount.precision,
-        ));
+    let contract_amount = |value: String| {
+        let amount = ContractAmount::from_raw(value).map_err(InvoiceError::WrongAmount)?;
+        if amount.precision != boilerplate.precision {
+            return Err(InvoiceError::WrongPrecision(
+                boilerplate.precision,
+                amount.precision,
+            ));
+        }
+        Ok(amount.to_value())
+    };
+
+    // Open amount invoices let the payer pick the amount, optionally within a range
+    let open_amount = amount.is_empty();
+    if !open_amount && (min_amount.is_some() || max_amount.is_some()) {
+        return Err(InvoiceError::WrongAmountRange);
     }
 
-    let invoice_amount = invoice_amount.to_value();
-    let invoice = create_rgb_invoice(
-        &contract_id,
-        &iface,
-        invoice_amount,
-        &seal,
-        &network,
-        params,
-        stock,
-    )
+    let invoice_amount = if open_amount {
+        0
+    } else {
+        contract_amount(amount)?
+    };
+    let min_amount = min_amount.map(contract_amount).transpose()?;
+    let max_amount = max_amount.map(contract_amount).transpose()?;
+    if let (Some(min), Some(max)) = (min_amount, max_amount) {
+        if min > max {
+            return Err(InvoiceError::WrongAmountRange);
+        }
+    }
+
+    let mut invoice = if bitcoin_30::Address::from_str(&seal).is_err() {
+        create_rgb_invoice(
+            &contract_id,
+            &iface,
+            invoice_amount,
+            &seal,
+            &network,
+            params,
+            stock,
+            seal_secrets,
+        )
+    } else {
+        create_witness_invoice(
+            &contract_id,
+            &iface,
+            invoice_amount,
+            &seal,
+            &network,
+            params,
+            stock,
+        )
+    }
     .map_err(InvoiceError::Invoice)?;
 
+    let transports = transports
+        .iter()
+        .map(|transport| parse_invoice_transport(transport))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| {
+            InvoiceError::Validation(BTreeMap::from([(
+                "transports".to_string(),
+                err.to_string(),
+            )]))
+        })?;
+    if !transports.is_empty() {
+        invoice.transports = transports;
+    }
+    invoice.expiry = expire_at;
+
+    if open_amount {
+        invoice.owned_state = TypedState::Void;
+        if let Some(min_amount) = min_amount {
+            invoice.unknown_query.insert(
+                RGB_INVOICE_MIN_AMOUNT_PARAM.to_string(),
+                min_amount.to_string(),
+            );
+        }
+        if let Some(max_amount) = max_amount {
+            invoice.unknown_query.insert(
+                RGB_INVOICE_MAX_AMOUNT_PARAM.to_string(),
+                max_amount.to_string(),
+            );
+        }
+    }
+
     Ok(invoice)
 }
 
+pub async fn cancel_invoice(
+    sk: &str,
+    request: CancelInvoiceRequest,
+) -> Result<InvoiceCleanupResponse, InvoiceError> {
+    let network = NETWORK.read().await.to_string();
+    let context = RGBContext::with(&network);
+
+    if let Err(err) = request.validate(&context) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(InvoiceError::Validation(errors));
+    }
+
+    let (mut stock, mut rgb_account) =
+        retrieve_stock_account(sk).await.map_err(InvoiceError::IO)?;
+    let mut seal_secrets = retrieve_seal_secrets(sk).await.map_err(InvoiceError::IO)?;
+
+    let CancelInvoiceRequest { invoice } = request;
+    let pos = rgb_account
+        .invoices
+        .iter()
+        .position(|x| x == &invoice)
+        .ok_or(InvoiceError::NoInvoice)?;
+    let invoice = rgb_account.invoices.remove(pos);
+
+    let mut rgb_invoices = retrieve_invoices(sk).await.map_err(InvoiceError::IO)?;
+    let resp = remove_invoices(
+        vec![invoice],
+        &mut rgb_invoices,
+        &mut seal_secrets,
+        &mut stock,
+    )?;
+
+    store_account(sk, rgb_account)
+        .await
+        .map_err(InvoiceError::IO)?;
+    store_invoices(sk, rgb_invoices)
+        .await
+        .map_err(InvoiceError::IO)?;
+    store_seal_secrets(sk, seal_secrets)
+        .await
+        .map_err(InvoiceError::IO)?;
+
+    Ok(resp)
+}
+
+/// Drops the expired invoices from the account and their seal secrets
+pub async fn cleanup_invoices(sk: &str) -> Result<InvoiceCleanupResponse, InvoiceError> {
+    let (mut stock, mut rgb_account) =
+        retrieve_stock_account(sk).await.map_err(InvoiceError::IO)?;
+    let mut seal_secrets = retrieve_seal_secrets(sk).await.map_err(InvoiceError::IO)?;
+
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let (expired, current): (Vec<String>, Vec<String>) = rgb_account
+        .invoices
+        .into_iter()
+        .partition(|invoice| match RgbInvoice::from_str(invoice) {
+            Ok(RgbInvoice {
+                expiry: Some(expiry),
+                ..
+            }) => expiry <= utc,
+            _ => false,
+        });
+    rgb_account.invoices = current;
+
+    let mut rgb_invoices = retrieve_invoices(sk).await.map_err(InvoiceError::IO)?;
+    let resp = remove_invoices(expired, &mut rgb_invoices, &mut seal_secrets, &mut stock)?;
+
+    store_account(sk, rgb_account)
+        .await
+        .map_err(InvoiceError::IO)?;
+    store_invoices(sk, rgb_invoices)
+        .await
+        .map_err(InvoiceError::IO)?;
+    store_seal_secrets(sk, seal_secrets)
+        .await
+        .map_err(InvoiceError::IO)?;
+
+    Ok(resp)
+}
+
+/// Lists the invoices ledger with the payments received and their confirmations
+pub async fn list_invoices(
+    sk: &str,
+    request: InvoicesRequest,
+) -> Result<InvoicesResponse, InvoiceError> {
+    let rgb_invoices = retrieve_invoices(sk).await.map_err(InvoiceError::IO)?;
+
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let txids: HashSet<Txid> = rgb_invoices
+        .invoices
+        .values()
+        .flat_map(|entry| entry.payments.iter())
+        .filter_map(|payment| Txid::from_str(&payment.txid).ok())
+        .collect();
+
+    let mut tip = 0;
+    if !txids.is_empty() {
+        prefetch_resolver_txs_status(txids.into_iter().collect(), &mut resolver).await;
+        tip = get_blockchain()
+            .await
+            .get_height()
+            .await
+            .map_err(|op| InvoiceError::Explorer(op.to_string()))?;
+    }
+
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let InvoicesRequest {
+        contract_id,
+        status,
+    } = request;
+
+    let mut invoices = vec![];
+    for (invoice, entry) in rgb_invoices.invoices {
+        if contract_id.is_some() && contract_id != Some(entry.contract_id.clone()) {
+            continue;
+        }
+
+        let invoice_status = entry.status(utc);
+        if status.is_some() && status != Some(invoice_status.clone()) {
+            continue;
+        }
+
+        let payments: Vec<InvoicePaymentDetail> = entry
+            .payments
+            .iter()
+            .map(|payment| {
+                let confirmations = match Txid::from_str(&payment.txid)
+                    .ok()
+                    .and_then(|txid| resolver.txs_status.get(&txid))
+                {
+                    Some(TxStatus::Block(height)) if tip >= *height => tip - height + 1,
+                    _ => 0,
+                };
+                InvoicePaymentDetail {
+                    consig_id: payment.consig_id.clone(),
+                    txid: payment.txid.clone(),
+                    amount: payment.amount,
+                    confirmations,
+                }
+            })
+            .collect();
+
+        invoices.push(InvoiceDetail {
+            invoice,
+            paid: entry.paid(),
+            status: invoice_status,
+            confirmations: payments.iter().map(|x| x.confirmations).min(),
+            contract_id: entry.contract_id,
+            iface: entry.iface,
+            amount: entry.amount,
+            expiry: entry.expiry,
+            payments,
+        });
+    }
+
+    Ok(InvoicesResponse { invoices })
+}
+
+fn remove_invoices(
+    invoices: Vec<String>,
+    invoice_ledger: &mut RgbInvoices,
+    seal_secrets: &mut SealSecrets,
+    stock: &mut Stock,
+) -> Result<InvoiceCleanupResponse, InvoiceError> {
+    // Ledger entries with payments are kept as the account history
+    invoice_ledger
+        .invoices
+        .retain(|invoice, entry| !invoices.contains(invoice) || !entry.payments.is_empty());
+
+    let rgb_invoices: Vec<RgbInvoice> = invoices
+        .iter()
+        .filter_map(|invoice| RgbInvoice::from_str(invoice).ok())
+        .collect();
+
+    // Secrets of paid invoices are kept by the stock, they are required to spend the received state
+    let unused =
+        unused_seal_secrets(&rgb_invoices, seal_secrets, stock).map_err(InvoiceError::Invoice)?;
+    remove_seal_secrets(&rgb_invoices, seal_secrets).map_err(InvoiceError::Invoice)?;
+
+    Ok(InvoiceCleanupResponse {
+        invoices,
+        seal_secrets: unused.len(),
+    })
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Display, From, Error)]
 #[display(doc_comments)]
 pub enum PsbtError {
@@ -531,6 +988,21 @@ pub async fn create_psbt(sk: &str, request: PsbtRequest) -> Result<PsbtResponse,
     };
 
     let mut rgb_account = retrieve_account(sk).await.map_err(PsbtError::IO)?;
+    let rgb_transfers = retrieve_transfers(sk).await.map_err(PsbtError::IO)?;
+
+    // The state of transfers whose anchor left the chain cannot be spent
+    if let Some(input) = request
+        .asset_inputs
+        .iter()
+        .find(|input| is_reorged_utxo(&input.utxo, &rgb_transfers))
+    {
+        let mut errors = BTreeMap::new();
+        errors.insert(
+            "asset_inputs".to_string(),
+            format!("the allocation {} was reorged out", input.utxo),
+        );
+        return Err(PsbtError::Validation(errors));
+    }
 
     let options = NewPsbtOptions::with(request.rbf);
     let psbt =
@@ -562,6 +1034,7 @@ async fn internal_create_psbt(
         bitcoin_inputs,
         bitcoin_changes,
         fee,
+        psbt_version,
         ..
     } = request;
 
@@ -595,6 +1068,8 @@ async fn internal_create_psbt(
         psbt_file = set_tapret_output(psbt_file, pos).map_err(PsbtError::Create)?;
     }
 
+    psbt_file.psbt_version = PsbtVersion::from(psbt_version);
+
     let psbt = PsbtResponse {
         psbt: Serialize::serialize(&psbt_file).to_hex(),
         terminal: change_terminal,
@@ -654,11 +1129,27 @@ pub enum TransferError {
     Save(SaveTransferError),
     /// Occurs an error in retrieve proxy step. {0}
     Proxy(ProxyError),
+    /// Occurs an error in invoice step. {0}
+    Invoice(NewInvoiceError),
+    /// Witness output cannot be registered in the watcher. {0}
+    WrongWitness(String),
+    /// Invoice {0} has expired.
+    InvoiceExpired(String),
+    /// The amount received is not allowed by the invoice {0}.
+    InvoiceAmount(String),
+    /// Amount contains wrong contract precision. expect: {0} / current: {1}.
+    WrongPrecision(u8, u8),
+    /// Amount cannot be decoded. {0}
+    WrongAmount(ContractAmountError),
+    /// Transfer {0} not found.
+    NoTransfer(String),
+    /// Transfer {0} is not a draft or signed transfer and cannot be cancelled.
+    WrongCancel(String),
 }
 
 pub async fn full_transfer_asset(
     sk: &str,
-    request: FullRgbTransferRequest,
+    mut request: FullRgbTransferRequest,
 ) -> Result<RgbTransferResponse, TransferError> {
     if let Err(err) = request.validate(&RGBContext::default()) {
         let errors = err
@@ -672,6 +1163,9 @@ pub async fn full_transfer_asset(
         .await
         .map_err(TransferError::IO)?;
 
+    request.rgb_invoice =
+        internal_fill_invoice(&request.rgb_invoice, request.amount.clone(), &mut stock)?;
+
     let local_rgb_account = retrieve_local_account(sk)
         .await
         .map_err(TransferError::IO)?;
@@ -689,20 +1183,78 @@ pub async fn full_transfer_asset(
         ..default!()
     };
 
-    let mut rgb_wallet = match rgb_account.wallets.get(RGB_DEFAULT_NAME) {
-        Some(rgb_wallet) => rgb_wallet.to_owned(),
-        _ => return Err(TransferError::NoWatcher),
-    };
-
-    let (asset_inputs, bitcoin_inputs, bitcoin_changes, fee_value) =
-        prebuild_transfer_asset(request.clone(), &mut stock, &mut rgb_wallet, &mut resolver)
-            .await?;
-
-    let FullRgbTransferRequest {
-        rgb_invoice,
-        change_terminal,
+    let rgb_invoice = request.rgb_invoice.clone();
+    let RgbInternalTransferResponse {
+        consig_id,
+        consig,
+        psbt,
+        commit,
+        txid,
         ..
-    } = request;
+    } = internal_full_transfer_asset(
+        sk,
+        request,
+        rgb_invoice,
+        NewTransferOptions::default(),
+        &mut stock,
+        &mut rgb_account,
+        &mut rgb_transfers,
+        &mut resolver,
+    )
+    .await?;
+
+    let resp = RgbTransferResponse {
+        consig_id,
+        consig,
+        psbt,
+        commit,
+        txid,
+    };
+
+    rgb_account.clone().update(&mut rgb_account_changes);
+    reconcile(&mut fork_wallet, rgb_account_changes.clone())
+        .map_err(|op| TransferError::WrongAutoMerge(op.to_string()))?;
+
+    store_local_account(sk, fork_wallet.save())
+        .await
+        .map_err(TransferError::IO)?;
+
+    store_stock_transfers(sk, stock, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(resp)
+}
+
+/// Selects the asset and bitcoin inputs of the request, creates the PSBT and
+/// the transfer paying the invoice, and records the tapret commitment of the
+/// change output in the wallet.
+///
+/// The request invoice is used to select the inputs, so it can cover more
+/// invoices than the one paid (see `NewTransferOptions::other_invoices`).
+async fn internal_full_transfer_asset(
+    sk: &str,
+    request: FullRgbTransferRequest,
+    rgb_invoice: String,
+    options: NewTransferOptions,
+    stock: &mut Stock,
+    rgb_account: &mut RgbAccountV1,
+    rgb_transfers: &mut RgbTransfersV2,
+    resolver: &mut ExplorerResolver,
+) -> Result<RgbInternalTransferResponse, TransferError> {
+    let mut rgb_wallet = match rgb_account.wallets.get(RGB_DEFAULT_NAME) {
+        Some(rgb_wallet) => rgb_wallet.to_owned(),
+        _ => return Err(TransferError::NoWatcher),
+    };
+
+    let (asset_inputs, bitcoin_inputs, bitcoin_changes, fee_value) = prebuild_transfer_asset(
+        request.clone(),
+        stock,
+        &mut rgb_wallet,
+        rgb_transfers,
+        resolver,
+    )
+    .await?;
 
     let psbt_req = PsbtRequest {
         fee: PsbtFeeRequest::Value(fee_value),
@@ -710,11 +1262,12 @@ pub async fn full_transfer_asset(
         bitcoin_inputs,
         bitcoin_changes,
         asset_descriptor_change: None,
-        asset_terminal_change: Some(change_terminal),
+        asset_terminal_change: Some(request.change_terminal),
         rbf: true,
+        psbt_version: PsbtFormat::default(),
     };
 
-    let psbt_response = internal_create_psbt(psbt_req, &mut rgb_account, &mut resolver, None)
+    let psbt_response = internal_create_psbt(psbt_req, rgb_account, resolver, None)
         .await
         .map_err(TransferError::Create)?;
 
@@ -722,8 +1275,314 @@ pub async fn full_transfer_asset(
         rgb_invoice,
         psbt: psbt_response.psbt,
         terminal: psbt_response.terminal.clone(),
+        amount: None,
+    };
+
+    let resp =
+        internal_transfer_asset(sk, transfer_req, options, stock, rgb_account, rgb_transfers)
+            .await?;
+
+    let mut rgb_wallet = match rgb_account.wallets.get(RGB_DEFAULT_NAME) {
+        Some(rgb_wallet) => rgb_wallet.to_owned(),
+        _ => return Err(TransferError::NoWatcher),
+    };
+
+    save_tap_commit_str(
+        &resp.outpoint,
+        resp.amount,
+        &resp.commit,
+        &psbt_response.terminal,
+        &mut rgb_wallet,
+    );
+    rgb_account
+        .wallets
+        .insert(RGB_DEFAULT_NAME.to_owned(), rgb_wallet);
+
+    Ok(resp)
+}
+
+/// Sends the asset to many recipients, packing their invoices in the same transfer.
+///
+/// Up to `RGB_AIRDROP_MAX_RECIPIENTS` invoices are sent in each request, as the
+/// next batch can only spend the asset change once the PSBT of the current
+/// batch is published. The remaining invoices are stored as pending and
+/// returned in the response, and the caller resumes the airdrop with another
+/// request with `resume` (the new invoices of that request are added after the
+/// pending ones) until no invoice is pending. A request without `resume` is
+/// refused while the contract has pending invoices.
+pub async fn airdrop_asset(
+    sk: &str,
+    request: RgbAirdropRequest,
+) -> Result<RgbAirdropResponse, TransferError> {
+    if let Err(err) = request.validate(&RGBContext::default()) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(TransferError::Validation(errors));
+    }
+
+    let RgbAirdropRequest {
+        contract_id,
+        iface,
+        invoices: request_invoices,
+        csv,
+        resume,
+        descriptor,
+        change_terminal,
+        fee,
+        mut bitcoin_changes,
+    } = request;
+
+    let (mut stock, mut rgb_transfers) = retrieve_stock_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+    let mut rgb_airdrops = retrieve_airdrops(sk).await.map_err(TransferError::IO)?;
+
+    let pending = rgb_airdrops
+        .take_pending(&contract_id, resume)
+        .map_err(|pending| {
+            TransferError::Validation(BTreeMap::from([(
+                "resume".to_string(),
+                format!("{pending} invoices of a previous airdrop are pending, resume it first"),
+            )]))
+        })?;
+
+    let mut recipients: Vec<(String, Option<String>)> = vec![];
+    recipients.extend(pending.into_iter().map(|invoice| (invoice, None)));
+    recipients.extend(request_invoices.into_iter().map(|invoice| (invoice, None)));
+    recipients.extend(parse_airdrop_csv(&csv));
+
+    // Validate all invoices before building the transfer
+    let network = NETWORK.read().await.to_string();
+    let context = RGBContext::with(&network);
+    let now = chrono::Utc::now().timestamp();
+    let mut errors = BTreeMap::new();
+    let mut beneficiaries = HashSet::new();
+    let mut invoices = vec![];
+    let mut rgb_invoices = vec![];
+    for (pos, (invoice, amount)) in recipients.into_iter().enumerate() {
+        let field = format!("invoices[{pos}]");
+        if let Err(err) = verify_rgb_invoice(&invoice, &context) {
+            errors.insert(field, err.to_string());
+            continue;
+        }
+
+        let invoice = match amount {
+            Some(amount) => match internal_fill_invoice(&invoice, Some(amount), &mut stock) {
+                Ok(invoice) => invoice,
+                Err(err) => {
+                    errors.insert(field, err.to_string());
+                    continue;
+                }
+            },
+            None => invoice,
+        };
+
+        let rgb_invoice = RgbInvoice::from_str(&invoice).expect("invoice already verified");
+        if rgb_invoice.contract.map(|id| id.to_string()) != Some(contract_id.clone()) {
+            errors.insert(field, "contract mismatch".to_string());
+        } else if !matches!(rgb_invoice.owned_state, TypedState::Amount(_)) {
+            errors.insert(field, "only amounts are supported".to_string());
+        } else if matches!(rgb_invoice.expiry, Some(expiry) if expiry <= now) {
+            errors.insert(field, "invoice expired".to_string());
+        } else if !beneficiaries.insert(rgb_invoice.beneficiary.to_string()) {
+            errors.insert(field, "duplicated beneficiary".to_string());
+        }
+        invoices.push(invoice);
+        rgb_invoices.push(rgb_invoice);
+    }
+
+    if invoices.is_empty() {
+        errors.insert("invoices".to_string(), "no invoices".to_string());
+    }
+
+    let batch_len = invoices.len().min(RGB_AIRDROP_MAX_RECIPIENTS);
+
+    // The inputs must cover the amount of the whole batch
+    let total = rgb_invoices[..batch_len]
+        .iter()
+        .try_fold(0u64, |total, invoice| match invoice.owned_state {
+            TypedState::Amount(amount) => total.checked_add(amount),
+            _ => Some(total),
+        });
+    if total.is_none() {
+        errors.insert("invoices".to_string(), "the amounts overflow".to_string());
+    }
+
+    if !errors.is_empty() {
+        return Err(TransferError::Validation(errors));
+    }
+
+    let total = total.unwrap_or_default();
+    let pending = invoices.split_off(batch_len);
+    let pending_invoices = rgb_invoices.split_off(batch_len);
+
+    let local_rgb_account = retrieve_local_account(sk)
+        .await
+        .map_err(TransferError::IO)?;
+
+    let LocalRgbAccount {
+        doc,
+        mut rgb_account,
+    } = local_rgb_account;
+    let mut fork_wallet = automerge::AutoCommit::load(&doc)
+        .map_err(|op| TransferError::WrongAutoMerge(op.to_string()))?;
+    let mut rgb_account_changes = RawRgbAccount::from(rgb_account.clone());
+
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let mut batch_invoice = rgb_invoices[0].clone();
+    batch_invoice.owned_state = TypedState::Amount(total);
+
+    // The witness output of the first invoice is added by the prebuild
+    bitcoin_changes.extend(rgb_invoices[1..].iter().filter_map(witness_output_change));
+
+    let prebuild_req = FullRgbTransferRequest {
+        contract_id: contract_id.clone(),
+        iface,
+        rgb_invoice: batch_invoice.to_string(),
+        descriptor,
+        change_terminal,
+        fee,
+        bitcoin_changes,
+        amount: None,
+    };
+
+    let options = NewTransferOptions::with(false, rgb_invoices[1..].to_vec());
+    let RgbInternalTransferResponse {
+        consig_id,
+        consig,
+        psbt,
+        commit,
+        consigs,
+        txid,
+        ..
+    } = internal_full_transfer_asset(
+        sk,
+        prebuild_req,
+        invoices[0].clone(),
+        options,
+        &mut stock,
+        &mut rgb_account,
+        &mut rgb_transfers,
+        &mut resolver,
+    )
+    .await?;
+
+    // Every recipient can fetch its consignment as soon as the PSBT is published
+    let main_beneficiary = rgb_invoices[0].beneficiary.to_string();
+    let mut consignments = consigs.clone();
+    consignments.insert(main_beneficiary.clone(), consig.clone());
+    post_consignments(consignments)
+        .await
+        .map_err(TransferError::Proxy)?;
+
+    let mut recipients = vec![];
+    for (invoice, rgb_invoice) in invoices.into_iter().zip(rgb_invoices) {
+        let beneficiary = rgb_invoice.beneficiary.to_string();
+        let consig_id = if beneficiary == main_beneficiary {
+            Some(consig_id.clone())
+        } else {
+            consigs
+                .get(&beneficiary)
+                .and_then(|consig| extract_transfer(consig.to_owned()).ok())
+                .map(|(_, transfer)| transfer.bindle_id().to_string())
+        };
+
+        recipients.push(RgbAirdropRecipient {
+            invoice,
+            beneficiary,
+            amount: match rgb_invoice.owned_state {
+                TypedState::Amount(amount) => amount,
+                _ => 0,
+            },
+            consig_id,
+            status: AirdropStatus::Sent,
+        });
+    }
+
+    for (invoice, rgb_invoice) in pending.iter().zip(pending_invoices) {
+        recipients.push(RgbAirdropRecipient {
+            invoice: invoice.to_owned(),
+            beneficiary: rgb_invoice.beneficiary.to_string(),
+            amount: match rgb_invoice.owned_state {
+                TypedState::Amount(amount) => amount,
+                _ => 0,
+            },
+            consig_id: None,
+            status: AirdropStatus::Pending,
+        });
+    }
+
+    rgb_airdrops.keep_pending(&contract_id, pending.clone());
+
+    let resp = RgbAirdropResponse {
+        transfer: RgbTransferResponse {
+            consig_id,
+            consig,
+            psbt,
+            commit,
+            txid,
+        },
+        recipients,
+        pending,
     };
 
+    rgb_account.clone().update(&mut rgb_account_changes);
+    reconcile(&mut fork_wallet, rgb_account_changes.clone())
+        .map_err(|op| TransferError::WrongAutoMerge(op.to_string()))?;
+
+    store_local_account(sk, fork_wallet.save())
+        .await
+        .map_err(TransferError::IO)?;
+
+    store_stock_transfers(sk, stock, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+
+    store_airdrops(sk, rgb_airdrops)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(resp)
+}
+
+/// Reads the airdrop recipients in CSV format (format: {invoice},{amount}).
+///
+/// Rows without an invoice (e.g. the header) are skipped, and the amount is
+/// optional (it's only required by open amount invoices).
+pub fn parse_airdrop_csv(csv: &str) -> Vec<(String, Option<String>)> {
+    csv.lines()
+        .filter_map(|line| {
+            let mut columns = line
+                .split(',')
+                .map(|column| column.trim().trim_matches('"'));
+            let invoice = columns.next()?;
+            if !invoice.starts_with("rgb:") {
+                return None;
+            }
+
+            let amount = columns
+                .next()
+                .filter(|amount| !amount.is_empty())
+                .map(|amount| amount.to_string());
+            Some((invoice.to_string(), amount))
+        })
+        .collect()
+}
+
+pub async fn transfer_asset(
+    sk: &str,
+    request: RgbTransferRequest,
+) -> Result<RgbTransferResponse, TransferError> {
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+
     let options = NewTransferOptions::default();
     let RgbInternalTransferResponse {
         consig_id,
@@ -735,7 +1594,8 @@ pub async fn full_transfer_asset(
         txid,
         ..
     } = internal_transfer_asset(
-        transfer_req,
+        sk,
+        request.clone(),
         options,
         &mut stock,
         &mut rgb_account,
@@ -752,7 +1612,7 @@ pub async fn full_transfer_asset(
         &outpoint,
         amount,
         &commit,
-        &psbt_response.terminal,
+        &request.terminal,
         &mut rgb_wallet,
     );
     rgb_account
@@ -767,30 +1627,167 @@ pub async fn full_transfer_asset(
         txid,
     };
 
-    rgb_account.clone().update(&mut rgb_account_changes);
-    reconcile(&mut fork_wallet, rgb_account_changes.clone())
-        .map_err(|op| TransferError::WrongAutoMerge(op.to_string()))?;
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
 
-    store_local_account(sk, fork_wallet.save())
+    Ok(resp)
+}
+
+/// Issues new supply of an inflatable RGB20 contract to the invoice beneficiary.
+///
+/// The PSBT must spend the UTXOs holding the inflation allowance. The consumed
+/// allowance is assigned to an unspendable seal and the remaining allowance to
+/// the asset change output.
+pub async fn inflate_asset(
+    sk: &str,
+    request: RgbTransferRequest,
+) -> Result<RgbTransferResponse, TransferError> {
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+
+    let options = NewTransferOptions {
+        inflation: true,
+        ..default!()
+    };
+    let resp = internal_operation_transfer(
+        sk,
+        request,
+        options,
+        &mut stock,
+        &mut rgb_account,
+        &mut rgb_transfers,
+    )
+    .await?;
+
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(resp)
+}
+
+/// Replaces lost allocations of an inflatable RGB20 contract, issuing their amount
+/// to the invoice beneficiary.
+///
+/// Works as `inflate_asset`, but the new supply is also recorded as burned, so the
+/// circulating supply does not change.
+pub async fn replace_asset(
+    sk: &str,
+    request: RgbTransferRequest,
+) -> Result<RgbTransferResponse, TransferError> {
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
         .await
         .map_err(TransferError::IO)?;
 
-    store_stock_transfers(sk, stock, rgb_transfers)
+    let options = NewTransferOptions {
+        replace: true,
+        ..default!()
+    };
+    let resp = internal_operation_transfer(
+        sk,
+        request,
+        options,
+        &mut stock,
+        &mut rgb_account,
+        &mut rgb_transfers,
+    )
+    .await?;
+
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(resp)
+}
+
+/// Destroys the amount of the contract, assigning it to an unspendable seal.
+///
+/// Inflatable contracts also record the amount in the `burnedSupply` state, the
+/// other RGB20 contracts burn it through a plain transfer.
+pub async fn burn_asset(
+    sk: &str,
+    request: RgbBurnRequest,
+) -> Result<RgbTransferResponse, TransferError> {
+    if let Err(err) = request.validate(&RGBContext::default()) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(TransferError::Validation(errors));
+    }
+
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+
+    let RgbBurnRequest {
+        contract_id,
+        iface,
+        amount,
+        psbt,
+        terminal,
+    } = request;
+
+    let network = NETWORK.read().await.to_string();
+    let contr_id = ContractId::from_str(&contract_id).map_err(|_| TransferError::NoContract)?;
+    let boilerplate =
+        export_boilerplate(contr_id, &mut stock).map_err(|_| TransferError::NoContract)?;
+    let burn_amount = ContractAmount::from_raw(amount).map_err(TransferError::WrongAmount)?;
+    if burn_amount.precision != boilerplate.precision {
+        return Err(TransferError::WrongPrecision(
+            boilerplate.precision,
+            burn_amount.precision,
+        ));
+    }
+
+    let invoice = create_burn_invoice(
+        &contract_id,
+        &iface,
+        burn_amount.to_value(),
+        &network,
+        &mut stock,
+    )
+    .map_err(TransferError::Invoice)?;
+
+    let request = RgbTransferRequest {
+        rgb_invoice: invoice.to_string(),
+        psbt,
+        terminal,
+        amount: None,
+    };
+    let options = NewTransferOptions {
+        burn: true,
+        ..default!()
+    };
+    let resp = internal_operation_transfer(
+        sk,
+        request,
+        options,
+        &mut stock,
+        &mut rgb_account,
+        &mut rgb_transfers,
+    )
+    .await?;
+
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
         .await
         .map_err(TransferError::IO)?;
 
     Ok(resp)
 }
 
-pub async fn transfer_asset(
+/// Transfer paid by the contract owner itself (inflation or burn),
+/// keeping the change commitment in the default watcher
+async fn internal_operation_transfer(
     sk: &str,
     request: RgbTransferRequest,
+    options: NewTransferOptions,
+    stock: &mut Stock,
+    rgb_account: &mut RgbAccountV1,
+    rgb_transfers: &mut RgbTransfersV2,
 ) -> Result<RgbTransferResponse, TransferError> {
-    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
-        .await
-        .map_err(TransferError::IO)?;
-
-    let options = NewTransferOptions::default();
     let RgbInternalTransferResponse {
         consig_id,
         consig,
@@ -801,11 +1798,12 @@ pub async fn transfer_asset(
         txid,
         ..
     } = internal_transfer_asset(
+        sk,
         request.clone(),
         options,
-        &mut stock,
-        &mut rgb_account,
-        &mut rgb_transfers,
+        stock,
+        rgb_account,
+        rgb_transfers,
     )
     .await?;
 
@@ -825,19 +1823,13 @@ pub async fn transfer_asset(
         .wallets
         .insert(RGB_DEFAULT_NAME.to_owned(), rgb_wallet);
 
-    let resp = RgbTransferResponse {
+    Ok(RgbTransferResponse {
         consig_id,
         consig,
         psbt,
         commit,
         txid,
-    };
-
-    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
-        .await
-        .map_err(TransferError::IO)?;
-
-    Ok(resp)
+    })
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Display, From, Error)]
@@ -922,6 +1914,7 @@ pub async fn create_seller_offer(
 
     let (mut stock, mut rgb_account) =
         retrieve_stock_account(sk).await.map_err(RgbSwapError::IO)?;
+    let rgb_transfers = retrieve_transfers(sk).await.map_err(RgbSwapError::IO)?;
 
     let mut rgb_wallet = match rgb_account.wallets.get(RGB_DEFAULT_NAME) {
         Some(rgb_wallet) => rgb_wallet.to_owned(),
@@ -936,6 +1929,7 @@ pub async fn create_seller_offer(
         expire_at,
         presig,
         change_terminal,
+        psbt_version,
         ..
     } = request.clone();
 
@@ -948,7 +1942,14 @@ pub async fn create_seller_offer(
         export_boilerplate(contr_id, &mut stock).map_err(|_| RgbSwapError::NoContract)?;
 
     let (allocations, asset_inputs, bitcoin_inputs, mut bitcoin_changes, change_value) =
-        prebuild_seller_swap(request, &mut stock, &mut rgb_wallet, &mut resolver).await?;
+        prebuild_seller_swap(
+            request,
+            &mut stock,
+            &mut rgb_wallet,
+            &rgb_transfers,
+            &mut resolver,
+        )
+        .await?;
 
     rgb_account
         .wallets
@@ -964,6 +1965,7 @@ pub async fn create_seller_offer(
         asset_descriptor_change: None,
         asset_terminal_change: Some(change_terminal.clone()),
         rbf: true,
+        psbt_version,
     };
 
     let options = NewPsbtOptions::set_inflaction(change_value);
@@ -986,7 +1988,9 @@ pub async fn create_seller_offer(
         expire_at,
     );
 
-    let contract_amount = ContractAmount::from_raw(contract_amount).to_string();
+    let contract_amount = ContractAmount::from_raw(contract_amount)
+        .map_err(|op| RgbSwapError::WrongValue(op.to_string()))?
+        .to_string();
     let contract_amount =
         f64::from_str(&contract_amount).map_err(|_| RgbSwapError::WrongValue(contract_amount))?;
 
@@ -1140,6 +2144,7 @@ pub async fn create_buyer_bid(
         asset_descriptor_change: None,
         asset_terminal_change: Some(change_terminal.clone()),
         rbf: true,
+        psbt_version: PsbtFormat::default(),
     };
 
     let options = NewPsbtOptions {
@@ -1167,6 +2172,7 @@ pub async fn create_buyer_bid(
 
     let seller_psbt =
         Psbt::from_str(&seller_psbt).map_err(|op| RgbSwapError::WrongPsbtSeller(op.to_string()))?;
+    let swap_version = seller_psbt.psbt_version;
 
     let buyer_psbt = Psbt::from_str(&buyer_psbt.psbt)
         .map_err(|op| RgbSwapError::WrongPsbtBuyer(op.to_string()))?;
@@ -1178,7 +2184,8 @@ pub async fn create_buyer_bid(
         .join(buyer_psbt)
         .map_err(|op| RgbSwapError::WrongPsbtSwap(op.to_string()))?;
 
-    let swap_psbt = Psbt::from(swap_psbt);
+    let mut swap_psbt = Psbt::from(swap_psbt);
+    swap_psbt.psbt_version = swap_version;
     let swap_psbt = Serialize::serialize(&swap_psbt).to_hex();
 
     let RgbBid {
@@ -1204,8 +2211,13 @@ pub async fn create_buyer_bid(
         amount: invoice_amount.to_string(),
         seal: format!("tapret1st:{buyer_outpoint}"),
         params: HashMap::new(),
+        transports: vec![],
+        expire_at: None,
+        min_amount: None,
+        max_amount: None,
     };
-    let invoice = internal_create_invoice(invoice_req, &mut stock)
+    let mut seal_secrets = retrieve_seal_secrets(sk).await.map_err(RgbSwapError::IO)?;
+    let invoice = internal_create_invoice(invoice_req, &mut stock, &mut seal_secrets)
         .await
         .map_err(RgbSwapError::Invoice)?;
 
@@ -1225,6 +2237,9 @@ pub async fn create_buyer_bid(
     store_stock_account(sk, stock, rgb_account)
         .await
         .map_err(RgbSwapError::IO)?;
+    store_seal_secrets(sk, seal_secrets)
+        .await
+        .map_err(RgbSwapError::IO)?;
 
     let public_bid = RgbBidSwap::from(new_bid);
     publish_swap_bid(sk, &offer_pub, public_bid.clone(), expire_at)
@@ -1290,6 +2305,7 @@ pub async fn create_swap_transfer(
     let change_terminal = match iface.to_uppercase().as_str() {
         "RGB20" => "/20/1",
         "RGB21" => "/21/1",
+        "RGB25" => "/25/1",
         _ => "/10/1",
     };
 
@@ -1297,6 +2313,7 @@ pub async fn create_swap_transfer(
         psbt: swap_psbt.clone(),
         rgb_invoice: buyer_invoice.to_string(),
         terminal: change_terminal.to_string(),
+        amount: None,
     };
 
     let params = NewTransferOptions {
@@ -1314,6 +2331,7 @@ pub async fn create_swap_transfer(
         amount,
         ..
     } = internal_transfer_asset(
+        sk,
         transfer_req,
         params,
         &mut stock,
@@ -1399,12 +2417,37 @@ pub async fn direct_swap_transfer(
     .await
 }
 
+/// Sets the amount picked by the payer (in decimal units) in open amount invoices
+fn internal_fill_invoice(
+    invoice: &str,
+    amount: Option<String>,
+    stock: &mut Stock,
+) -> Result<String, TransferError> {
+    let amount = match amount {
+        Some(amount) => {
+            let contract_id = RgbInvoice::from_str(invoice)
+                .map_err(|err| TransferError::WrongInvoice(err.to_string()))?
+                .contract
+                .ok_or(TransferError::WrongInvoice(invoice.to_string()))?;
+            let boilerplate = export_boilerplate(contract_id, stock)
+                .map_err(|err| TransferError::WrongInvoice(err.to_string()))?;
+            let amount = ContractAmount::from(amount, boilerplate.precision)
+                .map_err(TransferError::WrongAmount)?;
+            Some(amount.to_value())
+        }
+        None => None,
+    };
+
+    fill_invoice_amount(invoice, amount).map_err(TransferError::Pay)
+}
+
 async fn internal_transfer_asset(
+    sk: &str,
     request: RgbTransferRequest,
     options: NewTransferOptions,
     stock: &mut Stock,
     rgb_account: &mut RgbAccountV1,
-    rgb_transfers: &mut RgbTransfersV1,
+    rgb_transfers: &mut RgbTransfersV2,
 ) -> Result<RgbInternalTransferResponse, TransferError> {
     let network = NETWORK.read().await.to_string();
     let context = RGBContext::with(&network);
@@ -1424,9 +2467,11 @@ async fn internal_transfer_asset(
     let RgbTransferRequest {
         rgb_invoice: invoice,
         psbt,
+        amount,
         ..
     } = request;
 
+    let invoice = internal_fill_invoice(&invoice, amount, stock)?;
     let (psbt, mut transfers) =
         pay_invoice(invoice.clone(), psbt, options.clone(), stock).map_err(TransferError::Pay)?;
     let (outpoint, amount, commit) =
@@ -1447,6 +2492,10 @@ async fn internal_transfer_asset(
 
     let iface = rgb_invoice.clone().iface.unwrap().to_string();
     let mut consigs = BTreeMap::default();
+    let mut transport_consigs = vec![(
+        rgb_invoice.transports.clone(),
+        BTreeMap::from([(rgb_invoice.beneficiary.to_string(), consig.clone())]),
+    )];
     for (pos, invoice) in options.other_invoices.into_iter().enumerate() {
         let current_transfer = &transfers[pos];
         let current_transfer = current_transfer
@@ -1454,7 +2503,11 @@ async fn internal_transfer_asset(
             .map_err(|err| TransferError::WrongConsig(err.to_string()))?;
 
         let current_transfer = current_transfer.to_hex();
-        consigs.insert(invoice.beneficiary.to_string(), current_transfer);
+        consigs.insert(invoice.beneficiary.to_string(), current_transfer.clone());
+        transport_consigs.push((
+            invoice.transports,
+            BTreeMap::from([(invoice.beneficiary.to_string(), current_transfer)]),
+        ));
     }
 
     let internal_request = RgbInternalSaveTransferRequest::with(
@@ -1471,6 +2524,13 @@ async fn internal_transfer_asset(
         .await
         .map_err(TransferError::WrongSave)?;
 
+    // Consignments are delivered to the endpoints announced by the invoices
+    for (transports, consignments) in transport_consigs {
+        post_transport_consignments(sk, &transports, consignments)
+            .await
+            .map_err(TransferError::Proxy)?;
+    }
+
     let resp = RgbInternalTransferResponse {
         consig_id,
         consig,
@@ -1505,6 +2565,7 @@ pub async fn internal_replace_transfer(
         txid,
         ..
     } = internal_transfer_asset(
+        sk,
         request.clone(),
         options,
         &mut stock,
@@ -1529,6 +2590,31 @@ pub async fn internal_replace_transfer(
         .wallets
         .insert(RGB_DEFAULT_NAME.to_owned(), rgb_wallet);
 
+    // The previous transfers spending the same utxos are replaced by fee
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let utxos: Vec<String> = rgb_transfers
+        .transfers
+        .values()
+        .flatten()
+        .filter(|x| x.consig_id == consig_id)
+        .flat_map(|x| x.utxos.clone())
+        .collect();
+    let replaced: Vec<String> = rgb_transfers
+        .transfers
+        .values()
+        .flatten()
+        .filter(|x| x.sender && x.consig_id != consig_id)
+        .filter(|x| x.utxos.iter().any(|utxo| utxos.contains(utxo)))
+        .map(|x| x.consig_id.clone())
+        .collect();
+    for replaced_id in replaced {
+        rgb_transfers
+            .lifecycle
+            .entry(replaced_id)
+            .or_insert_with(|| RgbTransferLifecycle::new(utc))
+            .move_to(TransferState::Replaced, utc);
+    }
+
     let resp = RgbReplaceResponse {
         consig_id,
         consig,
@@ -1545,6 +2631,169 @@ pub async fn internal_replace_transfer(
     Ok(resp)
 }
 
+/// Reports what a consignment would give us, without accepting it.
+///
+/// The contract specs of a new contract are read from a copy of the stock, so
+/// nothing is stored.
+pub async fn preview_transfer(
+    sk: &str,
+    request: AcceptRequest,
+) -> Result<RgbTransferPreviewResponse, TransferError> {
+    if let Err(err) = request.validate(&RGBContext::default()) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(TransferError::Validation(errors));
+    }
+    let (mut stock, rgb_account) = retrieve_stock_account(sk)
+        .await
+        .map_err(TransferError::IO)?;
+    let invoice_secrets = retrieve_seal_secrets(sk).await.map_err(TransferError::IO)?;
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let AcceptRequest { consignment, .. } = request;
+    let (anchor_txid, transfer) =
+        extract_transfer(consignment.clone()).map_err(TransferError::Accept)?;
+
+    let consig_id = transfer.id().to_string();
+    let transfer = transfer.unbindle();
+    let contract_id = transfer.contract_id();
+
+    prefetch_resolver_rgb(&consignment, &mut resolver, None).await;
+    let txid = Txid::from_str(&anchor_txid.to_hex()).expect("invalid tx id");
+    prefetch_resolver_txs_status(vec![txid], &mut resolver).await;
+    let tx_status = resolver
+        .txs_status
+        .get(&txid)
+        .unwrap_or(&TxStatus::NotFound)
+        .to_owned();
+
+    let consig = match transfer.clone().validate(&mut resolver) {
+        Ok(consig) => consig,
+        Err(consig) => consig,
+    };
+    let status = consig.clone().into_validation_status().unwrap_or_default();
+    let report = match check_schema_rules(&transfer) {
+        Ok(_) => validation_report(&status),
+        Err(err) => rejection_report(err),
+    };
+    let valid = report.valid;
+
+    let new_contract = !stock
+        .contract_ids()
+        .map(|ids| ids.contains(&contract_id))
+        .unwrap_or_default();
+
+    let mut preview_stock = stock.clone();
+    if new_contract && valid {
+        let _ = preview_stock.accept_transfer(consig, &mut resolver, false);
+    }
+
+    let boilerplate = export_boilerplate(contract_id, &mut preview_stock).ok();
+    let iface = boilerplate
+        .as_ref()
+        .and_then(|boilerplate| IfaceId::from_str(&boilerplate.iface_id).ok())
+        .and_then(|iface_id| preview_stock.iface_by_id(iface_id).ok())
+        .map(|iface| iface.name.to_string());
+    let precision = boilerplate
+        .as_ref()
+        .map(|boilerplate| boilerplate.precision);
+
+    // Only the seals created by our invoices are reported
+    let seal_secrets: Vec<_> = stock
+        .seal_secrets()
+        .unwrap_or_default()
+        .into_iter()
+        .chain(invoice_secrets.secrets())
+        .map(|secret| secret.to_concealed_seal())
+        .collect();
+
+    let allocations = transfer_secret_seals(&transfer)
+        .into_iter()
+        .filter(|seal| seal_secrets.contains(seal))
+        .map(|seal| {
+            let amount = transfer_seal_amount(&transfer, &seal);
+            let invoice = rgb_account
+                .invoices
+                .iter()
+                .find(|invoice| {
+                    matches!(
+                        RgbInvoice::from_str(invoice),
+                        Ok(RgbInvoice {
+                            beneficiary: Beneficiary::BlindedSeal(invoice_seal),
+                            ..
+                        }) if invoice_seal == seal
+                    )
+                })
+                .cloned();
+            RgbTransferPreviewAllocation {
+                seal: seal.to_string(),
+                amount,
+                decimal_amount: precision
+                    .map(|precision| ContractAmount::with(amount, precision).to_string()),
+                invoice,
+            }
+        })
+        .collect();
+
+    let (ticker, name) = match boilerplate {
+        Some(ContractBoilerplate { ticker, name, .. }) => (Some(ticker), Some(name)),
+        None => (None, None),
+    };
+
+    Ok(RgbTransferPreviewResponse {
+        consig_id,
+        contract_id: contract_id.to_string(),
+        iface,
+        ticker,
+        name,
+        precision,
+        new_contract,
+        txid: anchor_txid.to_string(),
+        tx_status,
+        valid,
+        report,
+        allocations,
+    })
+}
+
+/// Reports why a consignment is valid or not, without accepting it.
+pub async fn inspect_transfer(
+    request: AcceptRequest,
+) -> Result<RgbValidationReport, TransferError> {
+    if let Err(err) = request.validate(&RGBContext::default()) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(TransferError::Validation(errors));
+    }
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let AcceptRequest { consignment, .. } = request;
+    let (_, transfer) = extract_transfer(consignment.clone()).map_err(TransferError::Accept)?;
+    let transfer = transfer.unbindle();
+
+    prefetch_resolver_rgb(&consignment, &mut resolver, None).await;
+    let consig = match transfer.clone().validate(&mut resolver) {
+        Ok(consig) => consig,
+        Err(consig) => consig,
+    };
+    let status = consig.into_validation_status().unwrap_or_default();
+
+    match check_schema_rules(&transfer) {
+        Ok(_) => Ok(validation_report(&status)),
+        Err(err) => Ok(rejection_report(err)),
+    }
+}
+
 pub async fn accept_transfer(
     sk: &str,
     request: AcceptRequest,
@@ -1556,29 +2805,430 @@ pub async fn accept_transfer(
             .collect();
         return Err(TransferError::Validation(errors));
     }
-    let mut stock = retrieve_rgb_stock(sk).await.map_err(TransferError::IO)?;
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+    let mut rgb_invoices = retrieve_invoices(sk).await.map_err(TransferError::IO)?;
+    let seal_secrets = retrieve_seal_secrets(sk).await.map_err(TransferError::IO)?;
     let mut resolver = ExplorerResolver {
         explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
         ..default!()
     };
 
     let AcceptRequest { consignment, .. } = request;
-    prefetch_resolver_rgb(&consignment, &mut resolver, None).await;
+    let resp = internal_accept_transfer(
+        &consignment,
+        &mut stock,
+        &seal_secrets,
+        &mut rgb_account,
+        &mut rgb_invoices,
+        &mut resolver,
+    )
+    .await?;
+
+    // Transfers accepted by hand are watched for reorgs like the received ones
+    let (anchor_txid, transfer) =
+        extract_transfer(consignment.clone()).map_err(TransferError::Accept)?;
+    let consig_id = transfer.id().to_string();
+    let txid = Txid::from_str(&anchor_txid.to_hex()).expect("invalid tx id");
+    prefetch_resolver_txs_status(vec![txid], &mut resolver).await;
+    let status = resolver
+        .txs_status
+        .get(&txid)
+        .unwrap_or(&TxStatus::NotFound)
+        .to_owned();
+    if let Some(pending) = rgb_transfers
+        .transfers
+        .get_mut(&transfer.contract_id().to_string())
+    {
+        pending.retain(|x| x.consig_id != consig_id);
+    }
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    rgb_transfers
+        .lifecycle
+        .entry(consig_id.clone())
+        .or_insert_with(|| RgbTransferLifecycle::new(utc))
+        .move_to(TransferState::Accepted, utc);
+    watch_transfer_anchor(
+        sk,
+        &consig_id,
+        &consignment,
+        &status,
+        &stock,
+        &mut rgb_transfers,
+    )
+    .await?;
+
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+    store_invoices(sk, rgb_invoices)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(resp)
+}
+
+/// Accepts a consignment paying our invoices and registers the witness outputs.
+///
+/// The stock and the account can be changed even when an error is returned, so the
+/// caller must not store them in that case.
+async fn internal_accept_transfer(
+    consignment: &str,
+    stock: &mut Stock,
+    seal_secrets: &SealSecrets,
+    rgb_account: &mut RgbAccountV1,
+    rgb_invoices: &mut RgbInvoices,
+    resolver: &mut ExplorerResolver,
+) -> Result<AcceptResponse, TransferError> {
+    prefetch_resolver_rgb(consignment, resolver, None).await;
 
-    let transfer = accept_rgb_transfer(consignment, false, &mut resolver, &mut stock)
+    reveal_seal_secrets(consignment, seal_secrets, stock).map_err(TransferError::Accept)?;
+    let transfer = accept_rgb_transfer(consignment.to_owned(), false, resolver, stock)
         .map_err(TransferError::Accept)?;
 
-    let resp = AcceptResponse {
-        contract_id: transfer.contract_id().to_string(),
+    // Witness invoices are paid to the outputs of the anchor transaction
+    let witness_scripts = transfer_witness_scripts(consignment, resolver).await?;
+
+    // Payments to expired invoices are rejected before the stock is stored
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let secret_seals = transfer_secret_seals(&transfer);
+    if let Some(invoice) =
+        rgb_account
+            .invoices
+            .iter()
+            .find(|invoice| match RgbInvoice::from_str(invoice) {
+                Ok(RgbInvoice {
+                    beneficiary: Beneficiary::BlindedSeal(seal),
+                    expiry: Some(expiry),
+                    ..
+                }) => secret_seals.contains(&seal) && expiry <= utc,
+                Ok(RgbInvoice {
+                    beneficiary: Beneficiary::WitnessUtxo(address),
+                    expiry: Some(expiry),
+                    ..
+                }) => {
+                    let script = address.script_pubkey();
+                    witness_scripts
+                        .values()
+                        .any(|witness| witness.as_slice() == script.as_bytes())
+                        && expiry <= utc
+                }
+                _ => false,
+            })
+    {
+        return Err(TransferError::InvoiceExpired(invoice.to_string()));
+    }
+
+    // Open amount invoices only accept the amounts allowed by their range
+    if let Some(invoice) =
+        rgb_account
+            .invoices
+            .iter()
+            .find(|invoice| match RgbInvoice::from_str(invoice) {
+                Ok(rgb_invoice) if matches!(rgb_invoice.owned_state, TypedState::Void) => {
+                    let witness_vouts = match &rgb_invoice.beneficiary {
+                        Beneficiary::WitnessUtxo(address) => {
+                            witness_address_vouts(address, &witness_scripts)
+                        }
+                        _ => vec![],
+                    };
+                    transfer_invoice_amount(&transfer, &rgb_invoice, &witness_vouts)
+                        .is_some_and(|amount| !check_invoice_amount(&rgb_invoice, amount))
+                }
+                _ => false,
+            })
+    {
+        return Err(TransferError::InvoiceAmount(invoice.to_string()));
+    }
+
+    // Witness outputs are created by the sender, so the watcher learns them on accept
+    let contract_id = transfer.contract_id();
+    let witness_outputs: Vec<(String, AssetType)> = rgb_account
+        .invoices
+        .iter()
+        .filter_map(|invoice| RgbInvoice::from_str(invoice).ok())
+        .filter(|invoice| invoice.contract == Some(contract_id))
+        .filter_map(|invoice| match invoice.beneficiary {
+            Beneficiary::WitnessUtxo(address) => {
+                let contract_index = match invoice.iface.map(|iface| iface.to_string()).as_deref() {
+                    Some("RGB20") => AssetType::RGB20,
+                    Some("RGB25") => AssetType::RGB25,
+                    _ => AssetType::RGB21,
+                };
+                Some((address.to_string(), contract_index))
+            }
+            _ => None,
+        })
+        .collect();
+
+    if let Some(wallet) = rgb_account.wallets.get_mut(RGB_DEFAULT_NAME) {
+        for (address, contract_index) in witness_outputs {
+            prefetch_resolver_waddress(&address, wallet, resolver, Some(RGB_DEFAULT_FETCH_LIMIT))
+                .await;
+            register_address(
+                &address,
+                vec![contract_index as u32],
+                wallet,
+                resolver,
+                Some(RGB_DEFAULT_FETCH_LIMIT),
+            )
+            .map_err(|op| TransferError::WrongWitness(op.to_string()))?;
+        }
+    }
+
+    record_invoice_payments(consignment, rgb_invoices, &witness_scripts);
+
+    let status = transfer.validation_status().cloned().unwrap_or_default();
+    Ok(AcceptResponse {
+        contract_id: contract_id.to_string(),
         transfer_id: transfer.transfer_id().to_string(),
         valid: true,
+        report: validation_report(&status),
+    })
+}
+
+/// Scripts of the anchor transaction outputs receiving state as witness seals, by index
+async fn transfer_witness_scripts(
+    consignment: &str,
+    resolver: &mut ExplorerResolver,
+) -> Result<BTreeMap<u32, Vec<u8>>, TransferError> {
+    let (anchor_txid, transfer) =
+        extract_transfer(consignment.to_owned()).map_err(TransferError::Accept)?;
+    let witness_vouts = transfer_witness_vouts(&transfer);
+    if witness_vouts.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    let txid = Txid::from_str(&anchor_txid.to_hex()).expect("invalid tx id");
+    prefetch_resolver_txs(vec![txid], resolver).await;
+    let tx = resolver
+        .resolve_tx(txid)
+        .map_err(|op| TransferError::WrongWitness(op.to_string()))?;
+
+    Ok(tx
+        .output
+        .into_iter()
+        .enumerate()
+        .map(|(vout, output)| (vout as u32, output.script_pubkey.to_bytes()))
+        .filter(|(vout, _)| witness_vouts.contains(vout))
+        .collect())
+}
+
+/// Polls the transport endpoints of the stored invoices for consignments paying them
+pub async fn receive_invoice_consignments(
+    sk: &str,
+) -> Result<InvoiceConsignmentsResponse, TransferError> {
+    let rgb_account = retrieve_account(sk).await.map_err(TransferError::IO)?;
+
+    let mut consignments = vec![];
+    for invoice in rgb_account.invoices {
+        let rgb_invoice = match RgbInvoice::from_str(&invoice) {
+            Ok(rgb_invoice) => rgb_invoice,
+            _ => continue,
+        };
+
+        let recipient_id = rgb_invoice.beneficiary.to_string();
+        if let Some((endpoint, consignment)) =
+            get_transport_consignment(sk, &rgb_invoice.transports, &recipient_id)
+                .await
+                .map_err(TransferError::Proxy)?
+        {
+            consignments.push(InvoiceConsignment {
+                invoice,
+                endpoint,
+                consignment,
+            });
+        }
+    }
+
+    Ok(InvoiceConsignmentsResponse { consignments })
+}
+
+/// Accepts the consignments posted to the transport endpoints of our invoices.
+///
+/// Consignments whose anchor transaction has less than `min_confirmations` are kept as
+/// pending transfers, so the next poll (or `verify_transfers`) can accept them.
+pub async fn receive_transfers(
+    sk: &str,
+    request: RgbReceiverRequest,
+) -> Result<RgbReceiverResponse, TransferError> {
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+    let mut rgb_invoices = retrieve_invoices(sk).await.map_err(TransferError::IO)?;
+    let seal_secrets = retrieve_seal_secrets(sk).await.map_err(TransferError::IO)?;
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
     };
 
-    store_rgb_stock(sk, stock)
+    let RgbReceiverRequest {
+        min_confirmations, ..
+    } = request;
+
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let mut tip = None;
+    let mut transfers = vec![];
+    for invoice in rgb_account.invoices.clone() {
+        let rgb_invoice = match RgbInvoice::from_str(&invoice) {
+            Ok(rgb_invoice) => rgb_invoice,
+            _ => continue,
+        };
+        if matches!(rgb_invoice.expiry, Some(expiry) if expiry <= utc) {
+            continue;
+        }
+
+        // Unreachable endpoints are retried in the next poll
+        let recipient_id = rgb_invoice.beneficiary.to_string();
+        let consignment =
+            match get_transport_consignment(sk, &rgb_invoice.transports, &recipient_id).await {
+                Ok(Some((_, consignment))) => consignment,
+                _ => continue,
+            };
+
+        let (anchor_txid, transfer) = match extract_transfer(consignment.clone()) {
+            Ok(extracted) => extracted,
+            _ => continue,
+        };
+        let consig_id = transfer.id().to_string();
+        let contract_id = transfer.contract_id().to_string();
+
+        let paid = rgb_invoices
+            .invoices
+            .values()
+            .any(|entry| entry.payments.iter().any(|x| x.consig_id == consig_id));
+        let closed = rgb_transfers
+            .lifecycle
+            .get(&consig_id)
+            .map(|x| {
+                matches!(
+                    x.state,
+                    TransferState::Accepted
+                        | TransferState::Cancelled
+                        | TransferState::Expired
+                        | TransferState::Reorged
+                )
+            })
+            .unwrap_or_default();
+        if paid || closed {
+            continue;
+        }
+
+        let txid = Txid::from_str(&anchor_txid.to_hex()).expect("invalid tx id");
+        prefetch_resolver_txs_status(vec![txid], &mut resolver).await;
+        let status = resolver
+            .txs_status
+            .get(&txid)
+            .unwrap_or(&TxStatus::NotFound)
+            .to_owned();
+
+        let (mut state, confirmations) = match status {
+            TxStatus::Block(height) => {
+                if tip.is_none() {
+                    tip = get_blockchain().await.get_height().await.ok();
+                }
+                let confirmations = match tip {
+                    Some(tip) if tip >= height => tip - height + 1,
+                    _ => 1,
+                };
+                (TransferState::Confirmed(confirmations), Some(confirmations))
+            }
+            TxStatus::Mempool => (TransferState::Mempool, Some(0)),
+            _ => (TransferState::Draft, None),
+        };
+
+        let mut error = None;
+        if confirmations
+            .map(|confirmations| confirmations >= min_confirmations)
+            .unwrap_or_default()
+        {
+            // The consignment is accepted on copies, so a failure leaves nothing behind
+            let mut accept_stock = stock.clone();
+            let mut accept_account = rgb_account.clone();
+            let mut accept_invoices = rgb_invoices.clone();
+            match internal_accept_transfer(
+                &consignment,
+                &mut accept_stock,
+                &seal_secrets,
+                &mut accept_account,
+                &mut accept_invoices,
+                &mut resolver,
+            )
+            .await
+            {
+                Ok(_) => {
+                    stock = accept_stock;
+                    rgb_account = accept_account;
+                    rgb_invoices = accept_invoices;
+                    if let Some(pending) = rgb_transfers.transfers.get_mut(&contract_id) {
+                        pending.retain(|x| x.consig_id != consig_id);
+                    }
+                    watch_transfer_anchor(
+                        sk,
+                        &consig_id,
+                        &consignment,
+                        &status,
+                        &stock,
+                        &mut rgb_transfers,
+                    )
+                    .await?;
+                    state = TransferState::Accepted;
+                }
+                Err(err) => error = Some(err.to_string()),
+            }
+        }
+
+        let pending = rgb_transfers
+            .transfers
+            .get(&contract_id)
+            .map(|pending| pending.iter().any(|x| x.consig_id == consig_id))
+            .unwrap_or_default();
+        if state != TransferState::Accepted && !pending {
+            let iface = rgb_invoice
+                .iface
+                .map(|iface| iface.to_string())
+                .unwrap_or_default();
+            let request = RgbInternalSaveTransferRequest::with(
+                consig_id.clone(),
+                consignment.clone(),
+                recipient_id.clone(),
+                iface,
+                false,
+                Some(BTreeMap::from([(recipient_id, consignment)])),
+                None,
+            );
+            internal_save_transfer(request, &mut rgb_transfers)
+                .await
+                .map_err(TransferError::Save)?;
+        }
+
+        let transfer_lifecycle = rgb_transfers
+            .lifecycle
+            .entry(consig_id.clone())
+            .or_insert_with(|| RgbTransferLifecycle::new(utc));
+        match error {
+            Some(error) => transfer_lifecycle.fail(error, utc),
+            None => transfer_lifecycle.move_to(state, utc),
+        }
+
+        transfers.push(RgbReceivedTransfer {
+            invoice,
+            consig_id,
+            contract_id,
+            state: transfer_lifecycle.state.clone(),
+            error: transfer_lifecycle.error.clone(),
+        });
+    }
+
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+    store_invoices(sk, rgb_invoices)
         .await
         .map_err(TransferError::IO)?;
 
-    Ok(resp)
+    Ok(RgbReceiverResponse { transfers })
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Display, From, Error)]
@@ -1602,6 +3252,10 @@ pub enum SaveTransferError {
     WrongSwap(RgbOfferErrors),
     /// Write I/O or connectivity error. {1} in {0}
     Write(String, String),
+    /// Transfer {0} not found.
+    NoTransfer(String),
+    /// Transfer {0} cannot be moved to the requested state.
+    WrongState(String),
 }
 
 pub async fn save_transfer(
@@ -1655,7 +3309,7 @@ pub async fn save_transfer(
 
 pub async fn internal_save_transfer(
     request: RgbInternalSaveTransferRequest,
-    rgb_transfers: &mut RgbTransfersV1,
+    rgb_transfers: &mut RgbTransfersV2,
 ) -> Result<rgb::Txid, SaveTransferError> {
     let RgbInternalSaveTransferRequest {
         iface,
@@ -1663,6 +3317,7 @@ pub async fn internal_save_transfer(
         sender,
         utxos,
         beneficiaries,
+        psbt,
         ..
     } = request;
 
@@ -1694,6 +3349,15 @@ pub async fn internal_save_transfer(
         rbf: true,
     };
 
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let transfer_lifecycle = rgb_transfers
+        .lifecycle
+        .entry(consig_id.clone())
+        .or_insert_with(|| RgbTransferLifecycle::new(utc));
+    if let Some(psbt) = psbt {
+        transfer_lifecycle.fee = Some(psbt_fee(&psbt));
+    }
+
     if let Some(transfers) = rgb_transfers.transfers.get(&contract_id.clone()) {
         let mut current_transfers = transfers.clone();
 
@@ -1755,6 +3419,7 @@ pub async fn remove_transfer(
             .transfers
             .insert(contract_id.clone(), current_transfers);
     }
+    rgb_transfers.prune_lifecycle();
 
     store_transfers(sk, rgb_transfers)
         .await
@@ -1767,10 +3432,289 @@ pub async fn remove_transfer(
     })
 }
 
+/// Marks the witness transaction of a transfer as signed or broadcasted by the wallet
+pub async fn update_transfer_state(
+    sk: &str,
+    request: RgbTransferStateRequest,
+) -> Result<RgbTransferStateResponse, SaveTransferError> {
+    if let Err(err) = request.validate(&RGBContext::default()) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(SaveTransferError::Validation(errors));
+    }
+
+    let RgbTransferStateRequest { consig_id, state } = request;
+    if !matches!(state, TransferState::Signed | TransferState::Broadcast) {
+        return Err(SaveTransferError::WrongState(consig_id));
+    }
+
+    let mut rgb_transfers = retrieve_transfers(sk)
+        .await
+        .map_err(SaveTransferError::IO)?;
+
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let transfer_lifecycle = rgb_transfers
+        .lifecycle
+        .get_mut(&consig_id)
+        .ok_or(SaveTransferError::NoTransfer(consig_id.clone()))?;
+
+    // The verify routines own the states after the publication
+    if !matches!(
+        transfer_lifecycle.state,
+        TransferState::Draft | TransferState::Signed
+    ) {
+        return Err(SaveTransferError::WrongState(consig_id));
+    }
+    transfer_lifecycle.move_to(state, utc);
+
+    let resp = RgbTransferStateResponse {
+        consig_id,
+        state: transfer_lifecycle.state.clone(),
+        updated_at: transfer_lifecycle.updated_at,
+    };
+
+    store_transfers(sk, rgb_transfers)
+        .await
+        .map_err(SaveTransferError::IO)?;
+
+    Ok(resp)
+}
+
+/// Cancels a draft or signed transfer, whose witness transaction was not broadcast.
+///
+/// Broadcast transfers, and transfers without a known state, may already be in
+/// the mempool, so they cannot be cancelled.
+///
+/// The consignment is discarded (the stock never accepts it) and the inputs are
+/// released. When the PSBT may have leaked, the inputs are spent again to an
+/// invoice of the wallet itself, and the new transfer must be signed and published.
+pub async fn cancel_transfer(
+    sk: &str,
+    request: RgbCancelTransferRequest,
+) -> Result<RgbCancelTransferResponse, TransferError> {
+    let network = NETWORK.read().await.to_string();
+    let context = RGBContext::with(&network);
+    if let Err(err) = request.validate(&context) {
+        let errors = err
+            .iter()
+            .map(|(f, e)| (f.to_string(), e.to_string()))
+            .collect();
+        return Err(TransferError::Validation(errors));
+    }
+
+    let (mut stock, mut rgb_account, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+
+    let RgbCancelTransferRequest {
+        contract_id,
+        consig_id,
+        double_spend,
+    } = request;
+
+    let transfer = rgb_transfers
+        .transfers
+        .get(&contract_id)
+        .and_then(|transfers| {
+            transfers
+                .iter()
+                .find(|x| x.sender && x.consig_id == consig_id)
+        })
+        .cloned()
+        .ok_or(TransferError::NoTransfer(consig_id.clone()))?;
+
+    let transfer_lifecycle = match rgb_transfers.lifecycle.get(&consig_id) {
+        Some(transfer_lifecycle) if transfer_lifecycle.is_cancellable() => transfer_lifecycle,
+        _ => return Err(TransferError::WrongCancel(consig_id)),
+    };
+
+    // The double spend must outbid the fee of the cancelled transfer
+    let original_fee = transfer_lifecycle.fee;
+    if double_spend.is_some() && original_fee.is_none() {
+        let mut errors = BTreeMap::new();
+        errors.insert(
+            "double_spend".to_string(),
+            "the fee of the transfer is unknown, so the double spend cannot replace it".to_string(),
+        );
+        return Err(TransferError::Validation(errors));
+    }
+    let original_fee = original_fee.unwrap_or_default();
+
+    // The inputs are only spent again to an unpaid invoice of this wallet
+    if let Some(RgbDoubleSpendRequest { rgb_invoice, .. }) = &double_spend {
+        let rgb_invoices = retrieve_invoices(sk).await.map_err(TransferError::IO)?;
+        let own_invoice = RgbInvoice::from_str(rgb_invoice)
+            .ok()
+            .and_then(|invoice| rgb_invoices.invoices.get(&invoice.to_string()))
+            .map(|entry| entry.contract_id == contract_id && entry.payments.is_empty())
+            .unwrap_or_default();
+        if !own_invoice {
+            let mut errors = BTreeMap::new();
+            errors.insert(
+                "double_spend.rgb_invoice".to_string(),
+                "the invoice is not an unpaid invoice of the wallet for the contract".to_string(),
+            );
+            return Err(TransferError::Validation(errors));
+        }
+    }
+
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let txid = Txid::from_str(&transfer.tx_id.to_hex()).expect("invalid tx id");
+    prefetch_resolver_txs_status(vec![txid], &mut resolver).await;
+    if matches!(
+        resolver.txs_status.get(&txid),
+        Some(TxStatus::Mempool | TxStatus::Block(_))
+    ) {
+        return Err(TransferError::WrongCancel(consig_id));
+    }
+
+    let mut rgb_wallet = match rgb_account.wallets.get(RGB_DEFAULT_NAME) {
+        Some(rgb_wallet) => rgb_wallet.to_owned(),
+        _ => return Err(TransferError::NoWatcher),
+    };
+
+    // The outputs (and tapret commitments) of the witness transaction are dropped
+    remove_tap_commit_str(&transfer.tx_id.to_hex(), &mut rgb_wallet);
+    rgb_account
+        .wallets
+        .insert(RGB_DEFAULT_NAME.to_owned(), rgb_wallet.clone());
+
+    if let Some(transfers) = rgb_transfers.transfers.get_mut(&contract_id) {
+        transfers.retain(|x| x.consig_id != consig_id);
+    }
+    rgb_transfers.prune_lifecycle();
+
+    let double_spend = match double_spend {
+        Some(RgbDoubleSpendRequest {
+            rgb_invoice,
+            descriptor,
+            change_terminal,
+            fee,
+        }) => {
+            // Spending any asset input is enough to invalidate the cancelled witness
+            let asset_inputs: Vec<PsbtInputRequest> = rgb_wallet
+                .utxos
+                .iter()
+                .filter(|utxo| transfer.utxos.contains(&utxo.outpoint.to_string()))
+                .map(|utxo| {
+                    let TerminalPath { app, index } = utxo.derivation.terminal;
+                    PsbtInputRequest {
+                        descriptor: descriptor.clone(),
+                        utxo: utxo.outpoint.to_string(),
+                        utxo_terminal: format!("/{app}/{index}"),
+                        tapret: None,
+                        sigh_hash: None,
+                    }
+                })
+                .collect();
+
+            if asset_inputs.is_empty() {
+                let mut errors = BTreeMap::new();
+                errors.insert(
+                    "double_spend".to_string(),
+                    "the transfer inputs are not tracked by the watcher".to_string(),
+                );
+                return Err(TransferError::Validation(errors));
+            }
+
+            let psbt_req = PsbtRequest {
+                fee,
+                asset_inputs,
+                bitcoin_inputs: vec![],
+                bitcoin_changes: vec![],
+                asset_descriptor_change: None,
+                asset_terminal_change: Some(change_terminal),
+                rbf: true,
+                psbt_version: PsbtFormat::default(),
+            };
+
+            let psbt_response =
+                internal_create_psbt(psbt_req, &mut rgb_account, &mut resolver, None)
+                    .await
+                    .map_err(TransferError::Create)?;
+
+            // The double spend must pay more than the cancelled transfer to replace it
+            let fee = psbt_fee(&Psbt::from_str(&psbt_response.psbt).expect("invalid psbt"));
+            if fee <= original_fee {
+                let mut errors = BTreeMap::new();
+                errors.insert(
+                    "double_spend.fee".to_string(),
+                    format!("the fee {fee} must exceed the fee {original_fee} of the transfer"),
+                );
+                return Err(TransferError::Validation(errors));
+            }
+
+            let transfer_req = RgbTransferRequest {
+                rgb_invoice,
+                psbt: psbt_response.psbt,
+                terminal: psbt_response.terminal.clone(),
+                amount: None,
+            };
+
+            let RgbInternalTransferResponse {
+                consig_id,
+                consig,
+                psbt,
+                commit,
+                outpoint,
+                amount,
+                txid,
+                ..
+            } = internal_transfer_asset(
+                sk,
+                transfer_req,
+                NewTransferOptions::default(),
+                &mut stock,
+                &mut rgb_account,
+                &mut rgb_transfers,
+            )
+            .await?;
+
+            save_tap_commit_str(
+                &outpoint,
+                amount,
+                &commit,
+                &psbt_response.terminal,
+                &mut rgb_wallet,
+            );
+            rgb_account
+                .wallets
+                .insert(RGB_DEFAULT_NAME.to_owned(), rgb_wallet);
+
+            Some(RgbTransferResponse {
+                consig_id,
+                consig,
+                psbt,
+                commit,
+                txid,
+            })
+        }
+        None => None,
+    };
+
+    store_stock_account_transfers(sk, stock, rgb_account, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(RgbCancelTransferResponse {
+        contract_id,
+        consig_id,
+        utxos: transfer.utxos,
+        double_spend,
+    })
+}
+
 pub async fn verify_transfers(sk: &str) -> Result<BatchRgbTransferResponse, TransferError> {
     let (mut stock, mut rgb_accounts, mut rgb_transfers) = retrieve_stock_account_transfers(sk)
         .await
         .map_err(TransferError::IO)?;
+    let seal_secrets = retrieve_seal_secrets(sk).await.map_err(TransferError::IO)?;
 
     let mut rgb_wallet = rgb_accounts.wallets.get(RGB_DEFAULT_NAME).unwrap().clone();
     internal_update_transfers(rgb_accounts.clone(), &mut rgb_transfers).await?;
@@ -1778,7 +3722,72 @@ pub async fn verify_transfers(sk: &str) -> Result<BatchRgbTransferResponse, Tran
         .await
         .map_err(TransferError::Save)?;
 
-    let (rgb_pending, transfers) = internal_verify_transfers(&mut stock, rgb_transfers).await?;
+    let received_consigs: Vec<(String, String)> = rgb_transfers
+        .transfers
+        .values()
+        .flatten()
+        .filter(|activity| !activity.sender)
+        .map(|activity| (activity.tx_id.to_hex(), activity.consig.clone()))
+        .collect();
+
+    let (mut rgb_pending, transfers) =
+        internal_verify_transfers(sk, &mut stock, &seal_secrets, rgb_transfers).await?;
+
+    // Received transfers of expired invoices which were never published
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let expired_seals: Vec<String> = rgb_accounts
+        .invoices
+        .iter()
+        .filter_map(|invoice| RgbInvoice::from_str(invoice).ok())
+        .filter(|invoice| {
+            invoice
+                .expiry
+                .map(|expiry| expiry <= utc)
+                .unwrap_or_default()
+        })
+        .map(|invoice| invoice.beneficiary.to_string())
+        .collect();
+    for activity in rgb_pending.transfers.values().flatten() {
+        if activity.sender
+            || !activity
+                .beneficiaries
+                .iter()
+                .any(|seal| expired_seals.contains(seal))
+        {
+            continue;
+        }
+        if let Some(transfer_lifecycle) = rgb_pending.lifecycle.get_mut(&activity.consig_id) {
+            if matches!(
+                transfer_lifecycle.state,
+                TransferState::Draft | TransferState::Signed | TransferState::Broadcast
+            ) {
+                transfer_lifecycle.move_to(TransferState::Expired, utc);
+            }
+        }
+    }
+
+    // Accepted receiver transfers settle the invoices they pay
+    let accepted_txids: Vec<_> = transfers
+        .iter()
+        .filter(|x| x.is_accept && !x.is_mine)
+        .map(|x| x.txid.clone())
+        .collect();
+    if !accepted_txids.is_empty() {
+        let mut rgb_invoices = retrieve_invoices(sk).await.map_err(TransferError::IO)?;
+        let mut resolver = ExplorerResolver {
+            explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+            ..default!()
+        };
+        for (txid, consig) in received_consigs {
+            if accepted_txids.contains(&txid) {
+                let witness_scripts = transfer_witness_scripts(&consig, &mut resolver).await?;
+                record_invoice_payments(&consig, &mut rgb_invoices, &witness_scripts);
+            }
+        }
+        store_invoices(sk, rgb_invoices)
+            .await
+            .map_err(TransferError::IO)?;
+    }
 
     let mut my_public_offers = vec![];
     let check_offers: Vec<_> = transfers
@@ -1840,7 +3849,7 @@ pub async fn verify_transfers(sk: &str) -> Result<BatchRgbTransferResponse, Tran
 pub async fn internal_swap_transfers(
     sk: &str,
     rgb_wallet: &mut RgbWallet,
-    rgb_transfers: &mut RgbTransfersV1,
+    rgb_transfers: &mut RgbTransfersV2,
 ) -> Result<(), SaveTransferError> {
     let mut my_swaps = vec![];
     let my_offers = retrieve_offers(sk).await.map_err(SaveTransferError::IO)?;
@@ -1929,7 +3938,7 @@ pub async fn internal_swap_transfers(
 
 pub async fn internal_update_transfers(
     rgb_account: RgbAccountV1,
-    rgb_transfers: &mut RgbTransfersV1,
+    rgb_transfers: &mut RgbTransfersV2,
 ) -> Result<Vec<RgbTransferV1>, TransferError> {
     let mut all_transfers = vec![];
     rgb_transfers
@@ -1982,6 +3991,12 @@ pub async fn internal_update_transfers(
                 RgbTransferV1::new(consig_id.clone(), consig, iface, tx_id, vec![seal]);
             new_transfers.push(rgb_transfer.clone());
 
+            let utc = chrono::Local::now().naive_utc().timestamp();
+            rgb_transfers
+                .lifecycle
+                .entry(consig_id.clone())
+                .or_insert_with(|| RgbTransferLifecycle::new(utc));
+
             if let Some(transfers) = rgb_transfers.transfers.get(&contract_id.clone()) {
                 let mut current_transfers = transfers.clone();
 
@@ -2009,18 +4024,274 @@ pub async fn internal_update_transfers(
     Ok(new_transfers)
 }
 
+/// Keeps the anchor of an accepted transfer, so the reorg monitor can recheck it.
+///
+/// The consignment is stored apart from the transfers, by consignment id, and
+/// fetched again only when the transfer has to be re-validated.
+async fn watch_transfer_anchor(
+    sk: &str,
+    consig_id: &str,
+    consig: &str,
+    status: &TxStatus,
+    stock: &Stock,
+    rgb_transfers: &mut RgbTransfersV2,
+) -> Result<(), TransferError> {
+    let (anchor_txid, transfer) = match extract_transfer(consig.to_owned()) {
+        Ok(extracted) => extracted,
+        _ => return Ok(()),
+    };
+    store_consignment(sk, consig_id, consig)
+        .await
+        .map_err(TransferError::IO)?;
+    let transfer = transfer.unbindle();
+
+    // Transfers accepted from the mempool are rechecked until they are mined
+    let height = match status {
+        TxStatus::Block(height) => *height,
+        _ => 0,
+    };
+    rgb_transfers.anchors.insert(
+        consig_id.to_owned(),
+        RgbTransferAnchor {
+            contract_id: transfer.contract_id().to_string(),
+            tx_id: anchor_txid,
+            height,
+            utxos: transfer_seal_utxos(&transfer, anchor_txid, stock),
+        },
+    );
+
+    Ok(())
+}
+
+/// Rechecks the anchors of the accepted transfers against the current chain tip.
+///
+/// Transfers whose anchor left the chain (reorged out or double-spent) are marked
+/// as reorged and their allocations are excluded from the balances. They are
+/// re-validated once the anchor is mined again.
+pub async fn check_reorgs(
+    sk: &str,
+    request: RgbReorgRequest,
+) -> Result<RgbReorgResponse, TransferError> {
+    let (mut stock, mut rgb_transfers) = retrieve_stock_transfers(sk)
+        .await
+        .map_err(TransferError::IO)?;
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let RgbReorgRequest { depth } = request;
+
+    let txids: Vec<Txid> = rgb_transfers
+        .anchors
+        .values()
+        .map(|anchor| Txid::from_str(&anchor.tx_id.to_hex()).expect("invalid tx id"))
+        .collect();
+    prefetch_resolver_txs_status(txids, &mut resolver).await;
+    let tip = get_blockchain().await.get_height().await.ok();
+
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let mut transfers = vec![];
+    let mut buried = vec![];
+    for (consig_id, anchor) in rgb_transfers.anchors.iter_mut() {
+        let txid = Txid::from_str(&anchor.tx_id.to_hex()).expect("invalid tx id");
+        let status = resolver
+            .txs_status
+            .get(&txid)
+            .unwrap_or(&TxStatus::NotFound)
+            .to_owned();
+
+        let transfer_lifecycle = rgb_transfers
+            .lifecycle
+            .entry(consig_id.clone())
+            .or_insert_with(|| RgbTransferLifecycle::new(utc));
+        let reorged = transfer_lifecycle.state == TransferState::Reorged;
+
+        let height = match status {
+            TxStatus::Block(height) => {
+                if reorged || height != anchor.height {
+                    // The witness ordering is computed on accept, so the transfer is
+                    // accepted again with the new anchor height
+                    match retrieve_consignment(sk, consig_id).await {
+                        Ok(Some(consig)) => {
+                            prefetch_resolver_rgb(&consig, &mut resolver, None).await;
+                            match accept_rgb_transfer(consig, false, &mut resolver, &mut stock) {
+                                Ok(_) => transfer_lifecycle.move_to(TransferState::Accepted, utc),
+                                Err(err) => transfer_lifecycle.reorg(err.to_string(), utc),
+                            }
+                        }
+                        Ok(None) => transfer_lifecycle
+                            .reorg("consignment of the transfer not found".to_string(), utc),
+                        Err(err) => transfer_lifecycle.reorg(err.to_string(), utc),
+                    }
+                    anchor.height = height;
+                } else {
+                    let confirmations = match tip {
+                        Some(tip) if tip >= height => tip - height + 1,
+                        _ => 1,
+                    };
+                    if confirmations >= depth {
+                        buried.push(consig_id.clone());
+                    }
+                    continue;
+                }
+                Some(height)
+            }
+            // Accepted from the mempool, the anchor is not mined yet
+            TxStatus::Mempool if anchor.height == 0 => continue,
+            TxStatus::Mempool => {
+                transfer_lifecycle.reorg("anchor tx is back in the mempool".to_string(), utc);
+                None
+            }
+            TxStatus::NotFound => {
+                transfer_lifecycle.reorg(
+                    "anchor tx not found, reorged out or double-spent".to_string(),
+                    utc,
+                );
+                None
+            }
+            // The explorer is unavailable, the anchor is rechecked in the next call
+            TxStatus::Error(_) => continue,
+        };
+
+        transfers.push(RgbReorgTransfer {
+            consig_id: consig_id.clone(),
+            contract_id: anchor.contract_id.clone(),
+            txid: anchor.tx_id.to_string(),
+            height,
+            state: transfer_lifecycle.state.clone(),
+            error: transfer_lifecycle.error.clone(),
+            utxos: anchor.utxos.clone(),
+        });
+    }
+
+    for consig_id in buried {
+        rgb_transfers.anchors.remove(&consig_id);
+    }
+    rgb_transfers.prune_lifecycle();
+    let watching = rgb_transfers.anchors.len();
+
+    store_stock_transfers(sk, stock, rgb_transfers)
+        .await
+        .map_err(TransferError::IO)?;
+
+    Ok(RgbReorgResponse {
+        tip,
+        transfers,
+        watching,
+    })
+}
+
+/// Anchors of the watched transfers which left the chain
+fn reorged_anchors(rgb_transfers: &RgbTransfersV2) -> Vec<&RgbTransferAnchor> {
+    rgb_transfers
+        .anchors
+        .iter()
+        .filter(|(consig_id, _)| {
+            rgb_transfers
+                .lifecycle
+                .get(*consig_id)
+                .map(|x| x.state == TransferState::Reorged)
+                .unwrap_or_default()
+        })
+        .map(|(_, anchor)| anchor)
+        .collect()
+}
+
+/// Checks if the utxo holds state assigned by a transfer whose anchor left the chain
+pub fn is_reorged_utxo(utxo: &str, rgb_transfers: &RgbTransfersV2) -> bool {
+    reorged_anchors(rgb_transfers).iter().any(|anchor| {
+        anchor.utxos.iter().any(|x| x == utxo) || utxo.starts_with(&format!("{}:", anchor.tx_id))
+    })
+}
+
+/// Removes the allocations of the contract assigned by transfers whose anchor left
+/// the chain, returns if any allocation was removed
+fn retain_unreorged_allocations(
+    contract_id: &str,
+    allocations: &mut Vec<AllocationDetail>,
+    rgb_transfers: &RgbTransfersV2,
+) -> bool {
+    let reorged: Vec<&RgbTransferAnchor> = reorged_anchors(rgb_transfers)
+        .into_iter()
+        .filter(|anchor| anchor.contract_id == contract_id)
+        .collect();
+    if reorged.is_empty() {
+        return false;
+    }
+
+    let len = allocations.len();
+    allocations.retain(|allocation| {
+        !reorged.iter().any(|anchor| {
+            anchor.utxos.contains(&allocation.utxo)
+                || allocation.utxo.starts_with(&format!("{}:", anchor.tx_id))
+        })
+    });
+    allocations.len() != len
+}
+
+/// Allocations of transfers whose anchor left the chain are neither part of the
+/// balance nor selected for spending
+pub fn exclude_reorged_allocations(
+    contract: &mut ContractResponse,
+    rgb_transfers: &RgbTransfersV2,
+) {
+    if !retain_unreorged_allocations(
+        &contract.contract_id,
+        &mut contract.allocations,
+        rgb_transfers,
+    ) {
+        return;
+    }
+
+    contract.balance = contract
+        .allocations
+        .iter()
+        .filter(|a| a.is_mine && !a.is_spent)
+        .map(|a| match &a.value {
+            AllocationValue::Value(value) => value.to_owned(),
+            AllocationValue::UDA(position) => position.fraction,
+        })
+        .sum();
+    contract.balance_normalised =
+        f64::from_str(&ContractAmount::with(contract.balance, contract.precision).to_string())
+            .unwrap_or_default();
+}
+
+/// Watcher allocations of transfers whose anchor left the chain are not listed,
+/// so they cannot be picked for a transfer or a swap either
+pub fn exclude_reorged_details(details: &mut [WatcherDetail], rgb_transfers: &RgbTransfersV2) {
+    for detail in details {
+        retain_unreorged_allocations(&detail.contract_id, &mut detail.allocations, rgb_transfers);
+    }
+}
+
 pub async fn internal_verify_transfers(
+    sk: &str,
     stock: &mut Stock,
-    rgb_transfers: RgbTransfersV1,
-) -> Result<(RgbTransfersV1, Vec<BatchRgbTransferItem>), TransferError> {
+    seal_secrets: &SealSecrets,
+    rgb_transfers: RgbTransfersV2,
+) -> Result<(RgbTransfersV2, Vec<BatchRgbTransferItem>), TransferError> {
     let mut resolver = ExplorerResolver {
         explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
         ..default!()
     };
 
+    let RgbTransfersV2 {
+        transfers: all_transfers,
+        mut lifecycle,
+        anchors,
+    } = rgb_transfers;
+
+    let utc = chrono::Local::now().naive_utc().timestamp();
+    let mut tip = None;
+
     let mut transfers = vec![];
-    let mut rgb_pending = RgbTransfersV1::default();
-    for (contract_id, transfer_activities) in rgb_transfers.transfers {
+    let mut rgb_pending = RgbTransfersV2 {
+        anchors,
+        ..default!()
+    };
+    for (contract_id, transfer_activities) in all_transfers {
         let mut pending_transfers = vec![];
         let txids: Vec<bitcoin::Txid> = transfer_activities
             .clone()
@@ -2038,13 +4309,31 @@ pub async fn internal_verify_transfers(
                 .unwrap_or(&TxStatus::NotFound)
                 .to_owned();
 
+            let transfer_lifecycle = lifecycle
+                .entry(activity.consig_id.clone())
+                .or_insert_with(|| RgbTransferLifecycle::new(utc));
+
             let accept_status = match status.clone() {
-                TxStatus::Block(_) => {
+                TxStatus::Block(height) => {
+                    if tip.is_none() {
+                        tip = get_blockchain().await.get_height().await.ok();
+                    }
+                    let confirmations = match tip {
+                        Some(tip) if tip >= height => tip - height + 1,
+                        _ => 1,
+                    };
+                    transfer_lifecycle.move_to(TransferState::Confirmed(confirmations), utc);
+
                     prefetch_resolver_rgb(&activity.consig, &mut resolver, None).await;
+                    reveal_seal_secrets(&activity.consig, seal_secrets, stock)
+                        .map_err(TransferError::Accept)?;
                     accept_rgb_transfer(activity.consig.clone(), false, &mut resolver, stock)
                         .map_err(TransferError::Accept)?
                 }
                 _ => {
+                    if status == TxStatus::Mempool {
+                        transfer_lifecycle.move_to(TransferState::Mempool, utc);
+                    }
                     pending_transfers.push(activity.to_owned());
                     transfers.push(BatchRgbTransferItem {
                         iface,
@@ -2062,6 +4351,16 @@ pub async fn internal_verify_transfers(
             let accept_status = accept_status.unbindle();
             if let Some(rgb_status) = accept_status.into_validation_status() {
                 if rgb_status.validity() == Validity::Valid {
+                    transfer_lifecycle.move_to(TransferState::Accepted, utc);
+                    watch_transfer_anchor(
+                        sk,
+                        &activity.consig_id,
+                        &activity.consig,
+                        &status,
+                        stock,
+                        &mut rgb_pending,
+                    )
+                    .await?;
                     transfers.push(BatchRgbTransferItem {
                         iface,
                         status,
@@ -2072,6 +4371,9 @@ pub async fn internal_verify_transfers(
                         txid: txid.to_hex(),
                     });
                 } else {
+                    let failures: Vec<String> =
+                        rgb_status.failures.iter().map(|x| x.to_string()).collect();
+                    transfer_lifecycle.fail(failures.join(", "), utc);
                     transfers.push(BatchRgbTransferItem {
                         iface,
                         status,
@@ -2090,6 +4392,8 @@ pub async fn internal_verify_transfers(
             .transfers
             .insert(contract_id.to_string(), pending_transfers);
     }
+    rgb_pending.lifecycle = lifecycle;
+    rgb_pending.prune_lifecycle();
 
     Ok((rgb_pending, transfers))
 }
@@ -2107,7 +4411,7 @@ pub async fn get_contract(sk: &str, contract_id: &str) -> Result<ContractRespons
     let mut wallet = match wallet {
         Some(wallet) => {
             let mut fetch_wallet = wallet.to_owned();
-            for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+            for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
                 let contract_index = contract_type.clone() as u32;
                 let iface_name = contract_type.to_string().to_uppercase().clone();
 
@@ -2134,6 +4438,8 @@ pub async fn get_contract(sk: &str, contract_id: &str) -> Result<ContractRespons
     };
 
     let mut contract = export_contract(contract_id, &mut stock, &mut resolver, &mut wallet)?;
+    let rgb_transfers = retrieve_transfers(sk).await?;
+    exclude_reorged_allocations(&mut contract, &rgb_transfers);
     contract.meta = if let Some(meta) = contract.meta {
         Some(
             extract_metadata(meta)
@@ -2163,6 +4469,7 @@ pub async fn get_simple_contract(sk: &str, contract_id: &str) -> Result<SimpleCo
         contract_id,
         iface_id,
         precision,
+        ..
     } = contract;
 
     Ok(SimpleContractResponse {
@@ -2172,6 +4479,34 @@ pub async fn get_simple_contract(sk: &str, contract_id: &str) -> Result<SimpleCo
     })
 }
 
+pub async fn verify_contract_terms(
+    sk: &str,
+    request: VerifyTermsRequest,
+) -> Result<VerifyTermsResponse> {
+    let mut resolver = ExplorerResolver {
+        explorer_url: BITCOIN_EXPLORER_API.read().await.to_string(),
+        ..default!()
+    };
+
+    let mut stock = retrieve_rgb_stock(sk).await?;
+    let contract_id = ContractId::from_str(&request.contract_id)?;
+    let contract = export_contract(contract_id, &mut stock, &mut resolver, &mut None)?;
+
+    let document = match request.encode {
+        TermsEncode::Text => request.document.into_bytes(),
+        TermsEncode::Base64 => base64::decode(&request.document)?,
+    };
+
+    let digest = terms_digest(&document);
+    let expected = contract.terms.map(|terms| terms.digest).unwrap_or_default();
+
+    Ok(VerifyTermsResponse {
+        valid: digest == expected,
+        digest,
+        expected,
+    })
+}
+
 pub async fn hidden_contract(sk: &str, contract_id: &str) -> Result<ContractHiddenResponse> {
     let mut rgb_account = retrieve_account(sk).await?;
     if !rgb_account
@@ -2194,13 +4529,13 @@ pub async fn list_contracts(sk: &str, hidden_contracts: bool) -> Result<Contract
         ..default!()
     };
 
-    let (mut stock, mut rgb_account) = retrieve_stock_account(sk).await?;
+    let (mut stock, mut rgb_account, rgb_transfers) = retrieve_stock_account_transfers(sk).await?;
 
     let wallet = rgb_account.wallets.get(RGB_DEFAULT_NAME);
     let mut wallet = match wallet {
         Some(wallet) => {
             let mut fetch_wallet = wallet.to_owned();
-            for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+            for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
                 let contract_index = contract_type as u32;
                 sync_wallet(contract_index, &mut fetch_wallet, &mut resolver);
                 prefetch_resolver_utxos(
@@ -2217,7 +4552,7 @@ pub async fn list_contracts(sk: &str, hidden_contracts: bool) -> Result<Contract
     };
 
     let mut contracts = vec![];
-    for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+    for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
         let iface_name = contract_type.to_string().to_uppercase().clone();
         let iface_name = tn!(iface_name);
         let iface = stock
@@ -2245,6 +4580,7 @@ pub async fn list_contracts(sk: &str, hidden_contracts: bool) -> Result<Contract
 
             prefetch_resolver_allocations(contract_iface, &mut resolver, true).await;
             let mut resp = export_contract(contract_id, &mut stock, &mut resolver, &mut wallet)?;
+            exclude_reorged_allocations(&mut resp, &rgb_transfers);
             resp.meta = if let Some(meta) = resp.meta {
                 Some(
                     extract_metadata(meta)
@@ -2309,7 +4645,11 @@ pub async fn list_schemas(sk: &str) -> Result<SchemasResponse> {
     Ok(SchemasResponse { schemas })
 }
 
-pub async fn list_transfers(sk: &str, contract_id: String) -> Result<RgbTransfersResponse> {
+pub async fn list_transfers(
+    sk: &str,
+    contract_id: String,
+    state: Option<TransferState>,
+) -> Result<RgbTransfersResponse> {
     let rgb_transfers = retrieve_transfers(sk).await?;
 
     let mut resolver = ExplorerResolver {
@@ -2341,10 +4681,31 @@ pub async fn list_transfers(sk: &str, contract_id: String) -> Result<RgbTransfer
                 .unwrap_or(&TxStatus::NotFound)
                 .to_owned();
 
+            let RgbTransferLifecycle {
+                state: transfer_state,
+                created_at,
+                updated_at,
+                error,
+                ..
+            } = rgb_transfers
+                .lifecycle
+                .get(&activity.consig_id)
+                .cloned()
+                .unwrap_or_default();
+            if let Some(state) = &state {
+                if !transfer_state.is(state) {
+                    continue;
+                }
+            }
+
             let detail = RgbTransferDetail {
                 consig_id: activity.consig_id,
                 status,
                 ty,
+                state: transfer_state,
+                created_at,
+                updated_at,
+                error,
             };
             transfers.push(detail);
         }
@@ -2583,7 +4944,7 @@ pub async fn watcher_details(sk: &str, name: &str) -> Result<WatcherDetailRespon
     };
 
     let mut allocations = vec![];
-    for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+    for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
         let iface_index = contract_type as u32;
         prefetch_resolver_utxos(
             iface_index,
@@ -2598,6 +4959,9 @@ pub async fn watcher_details(sk: &str, name: &str) -> Result<WatcherDetailRespon
         allocations.append(&mut result);
     }
 
+    let rgb_transfers = retrieve_transfers(sk).await.map_err(WatcherError::IO)?;
+    exclude_reorged_details(&mut allocations, &rgb_transfers);
+
     let resp = WatcherDetailResponse {
         contracts: allocations,
     };
@@ -2708,6 +5072,7 @@ pub async fn watcher_next_address(
     let iface_index = match iface {
         "RGB20" => 20,
         "RGB21" => 21,
+        "RGB25" => 25,
         _ => 10,
     };
 
@@ -2730,6 +5095,7 @@ pub async fn watcher_next_utxo(
     let iface_index = match iface {
         "RGB20" => 20,
         "RGB21" => 21,
+        "RGB25" => 25,
         _ => 10,
     };
 
@@ -2789,6 +5155,7 @@ pub async fn watcher_unspent_utxos(
     let iface_index = match iface {
         "RGB20" => 20,
         "RGB21" => 21,
+        "RGB25" => 25,
         _ => 10,
     };
 
@@ -2870,7 +5237,17 @@ pub async fn import_uda_data(request: MediaRequest) -> Result<MediaResponse> {
 }
 
 pub async fn decode_invoice(invoice: String) -> Result<RgbInvoiceResponse> {
-    let rgb_invoice = RgbInvoice::from_str(&invoice)?;
+    internal_decode_invoice(&invoice, &mut Stock::default())
+}
+
+/// Decodes the invoice with the ticker and precision of the contract in our stock
+pub async fn decode_full_invoice(sk: &str, invoice: String) -> Result<RgbInvoiceResponse> {
+    let mut stock = retrieve_rgb_stock(sk).await?;
+    internal_decode_invoice(&invoice, &mut stock)
+}
+
+pub fn internal_decode_invoice(invoice: &str, stock: &mut Stock) -> Result<RgbInvoiceResponse> {
+    let rgb_invoice = RgbInvoice::from_str(invoice)?;
 
     let contract_id = rgb_invoice
         .contract
@@ -2883,9 +5260,52 @@ pub async fn decode_invoice(invoice: String) -> Result<RgbInvoiceResponse> {
         _ => 0,
     };
 
+    let (beneficiary, beneficiary_type) = match rgb_invoice.beneficiary {
+        Beneficiary::BlindedSeal(seal) => (seal.to_string(), InvoiceBeneficiaryType::Blinded),
+        Beneficiary::WitnessUtxo(address) => (address.to_string(), InvoiceBeneficiaryType::Witness),
+    };
+
+    let transports = rgb_invoice
+        .transports
+        .iter()
+        .filter(|transport| !matches!(transport, RgbTransport::UnspecifiedMeans))
+        .map(|transport| transport.to_string())
+        .collect();
+
+    // Ticker and precision are only known when the contract is in the stock
+    let boilerplate = rgb_invoice
+        .contract
+        .and_then(|contract_id| export_boilerplate(contract_id, stock).ok());
+
+    let (decimal_amount, ticker, precision) = match &boilerplate {
+        Some(ContractBoilerplate {
+            ticker, precision, ..
+        }) => (
+            Some(ContractAmount::with(amount, *precision).to_string()),
+            Some(ticker.to_owned()),
+            Some(*precision),
+        ),
+        None => (None, None, None),
+    };
+
     Ok(RgbInvoiceResponse {
         contract_id,
         amount,
+        decimal_amount,
+        iface: rgb_invoice.iface.map(|iface| iface.to_string()),
+        operation: rgb_invoice.operation.map(|operation| operation.to_string()),
+        assignment: rgb_invoice
+            .assignment
+            .map(|assignment| assignment.to_string()),
+        beneficiary,
+        beneficiary_type,
+        chain: rgb_invoice.chain.map(|chain| chain.to_string()),
+        transports,
+        expiry: rgb_invoice.expiry,
+        unknown_query: rgb_invoice.unknown_query.into_iter().collect(),
+        known_contract: boilerplate.is_some(),
+        ticker,
+        precision,
     })
 }
 
@@ -2904,7 +5324,7 @@ pub async fn inspect_contract(
     let mut wallet = match wallet {
         Some(wallet) => {
             let mut fetch_wallet = wallet.to_owned();
-            for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+            for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
                 let contract_index = contract_type.clone() as u32;
                 let iface_name = contract_type.to_string().to_uppercase().clone();
 
@@ -2947,7 +5367,7 @@ pub async fn read_contract(sk: &str, contract_id: &str) -> Result<ContractRespon
     let mut wallet = match wallet {
         Some(wallet) => {
             let mut fetch_wallet = wallet.to_owned();
-            for contract_type in [AssetType::RGB20, AssetType::RGB21] {
+            for contract_type in [AssetType::RGB20, AssetType::RGB21, AssetType::RGB25] {
                 let contract_index = contract_type.clone() as u32;
                 let iface_name = contract_type.to_string().to_uppercase().clone();
 
@@ -2973,6 +5393,8 @@ pub async fn read_contract(sk: &str, contract_id: &str) -> Result<ContractRespon
         _ => None,
     };
 
-    let contract = export_contract(contract_id, &mut stock, &mut resolver, &mut wallet)?;
+    let mut contract = export_contract(contract_id, &mut stock, &mut resolver, &mut wallet)?;
+    let rgb_transfers = retrieve_transfers(sk).await?;
+    exclude_reorged_allocations(&mut contract, &rgb_transfers);
     Ok(contract)
 }
@@ -1,7 +1,9 @@
 use crate::rgb::structs::{RgbAccountV0, RgbAccountV1};
 use postcard::from_bytes;
 
-use super::structs::{RgbTransferV0, RgbTransferV1, RgbTransfersV0, RgbTransfersV1};
+use super::structs::{
+    RgbTransferV0, RgbTransferV1, RgbTransfersV0, RgbTransfersV1, RgbTransfersV2,
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Display, From, Error)]
 #[display(doc_comments)]
@@ -90,6 +92,7 @@ pub enum RgbtransferVersions {
     Unknown,
     V0(RgbTransfersV0),
     V1(RgbTransfersV1),
+    V2(RgbTransfersV2),
 }
 
 impl From<String> for RgbtransferVersions {
@@ -97,6 +100,7 @@ impl From<String> for RgbtransferVersions {
         match value.to_lowercase().as_str() {
             "v0" | "0" | "rgbst161" | "" => RgbtransferVersions::V0(RgbTransfersV0::default()),
             "v1" | "1" => RgbtransferVersions::V1(RgbTransfersV1::default()),
+            "v2" | "2" => RgbtransferVersions::V2(RgbTransfersV2::default()),
             _ => RgbtransferVersions::Unknown,
         }
     }
@@ -110,8 +114,8 @@ impl From<[u8; 8]> for RgbtransferVersions {
     }
 }
 
-impl ModelVersion<RgbTransfersV1> for RgbtransferVersions {
-    fn from_bytes(bytes: Vec<u8>, version: [u8; 8]) -> Result<RgbTransfersV1, ModelVersionError> {
+impl ModelVersion<RgbTransfersV2> for RgbtransferVersions {
+    fn from_bytes(bytes: Vec<u8>, version: [u8; 8]) -> Result<RgbTransfersV2, ModelVersionError> {
         let target_version = RgbtransferVersions::from(version);
         let latest_version = match target_version {
             RgbtransferVersions::Unknown => {
@@ -121,9 +125,13 @@ impl ModelVersion<RgbTransfersV1> for RgbtransferVersions {
             }
             RgbtransferVersions::V0(mut previous_version) => {
                 previous_version = from_bytes(&bytes).map_err(ModelVersionError::WrongDecode)?;
-                RgbTransfersV1::from(previous_version)
+                RgbTransfersV2::from(RgbTransfersV1::from(previous_version))
             }
-            RgbtransferVersions::V1(mut current_version) => {
+            RgbtransferVersions::V1(mut previous_version) => {
+                previous_version = from_bytes(&bytes).map_err(ModelVersionError::WrongDecode)?;
+                RgbTransfersV2::from(previous_version)
+            }
+            RgbtransferVersions::V2(mut current_version) => {
                 current_version = from_bytes(&bytes).map_err(ModelVersionError::WrongDecode)?;
                 current_version
             }
@@ -133,6 +141,15 @@ impl ModelVersion<RgbTransfersV1> for RgbtransferVersions {
     }
 }
 
+impl From<RgbTransfersV1> for RgbTransfersV2 {
+    fn from(value: RgbTransfersV1) -> Self {
+        Self {
+            transfers: value.transfers,
+            ..Default::default()
+        }
+    }
+}
+
 impl From<RgbTransfersV0> for RgbTransfersV1 {
     fn from(value: RgbTransfersV0) -> Self {
         let mut transfers = RgbTransfersV1::default();
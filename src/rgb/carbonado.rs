@@ -8,6 +8,7 @@ use strict_encoding::{StrictDeserialize, StrictSerialize};
 use crate::carbonado::server_store;
 use crate::rgb::crdt::{LocalRgbAccount, LocalRgbOffers, RawRgbAccount};
 
+use crate::rgb::ledger::RgbInvoices;
 use crate::rgb::swap::{RgbBids, RgbOffers};
 use crate::{
     carbonado::{retrieve, server_retrieve, store},
@@ -20,11 +21,12 @@ use crate::{
 };
 
 use super::cambria::RgbtransferVersions;
-use super::structs::RgbTransfersV1;
+use super::structs::{RgbAirdrops, RgbTransfersV2};
 use super::swap::{PublicRgbOffers, RgbBidSwap};
+use super::transfer::SealSecrets;
 
 const RGB_ACCOUNT_VERSION: [u8; 2] = *b"v1";
-const RGB_TRANSFER_VERSION: [u8; 2] = *b"v1";
+const RGB_TRANSFER_VERSION: [u8; 2] = *b"v2";
 
 #[derive(Debug, Clone, Eq, PartialEq, Display, From, Error)]
 #[display(doc_comments)]
@@ -106,7 +108,7 @@ pub async fn store_wallets(
 pub async fn store_transfers(
     sk: &str,
     name: &str,
-    rgb_transfers: &RgbTransfersV1,
+    rgb_transfers: &RgbTransfersV2,
 ) -> Result<(), StorageError> {
     let data = to_allocvec(rgb_transfers)
         .map_err(|op| StorageError::StrictWrite(name.to_string(), op.to_string()))?;
@@ -189,6 +191,51 @@ pub async fn retrieve_stock(sk: &str, name: &str) -> Result<Stock, StorageError>
     }
 }
 
+pub async fn store_seal_secrets(
+    sk: &str,
+    name: &str,
+    seal_secrets: &SealSecrets,
+) -> Result<(), StorageError> {
+    let data = seal_secrets
+        .to_strict_serialized::<U32>()
+        .map_err(|op| StorageError::StrictWrite(name.to_string(), op.to_string()))?;
+
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    store(
+        sk,
+        &format!("{hashed_name}.c15"),
+        &data,
+        false,
+        Some(RGB_STRICT_TYPE_VERSION.to_vec()),
+    )
+    .await
+    .map_err(|op| StorageError::CarbonadoWrite(name.to_string(), op.to_string()))
+}
+
+pub async fn retrieve_seal_secrets(sk: &str, name: &str) -> Result<SealSecrets, StorageError> {
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    let (data, _) = retrieve(sk, &format!("{hashed_name}.c15"), vec![])
+        .await
+        .map_err(|op| StorageError::CarbonadoRetrieve(name.to_string(), op.to_string()))?;
+
+    if data.is_empty() {
+        Ok(SealSecrets::default())
+    } else {
+        let confined = Confined::try_from_iter(data)
+            .map_err(|op| StorageError::StrictRetrieve(name.to_string(), op.to_string()))?;
+        let seal_secrets = SealSecrets::from_strict_serialized::<U32>(confined)
+            .map_err(|op| StorageError::StrictRetrieve(name.to_string(), op.to_string()))?;
+
+        Ok(seal_secrets)
+    }
+}
+
 pub async fn retrieve_wallets(sk: &str, name: &str) -> Result<RgbAccountV1, StorageError> {
     let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
         .to_hex()
@@ -212,7 +259,7 @@ pub async fn retrieve_wallets(sk: &str, name: &str) -> Result<RgbAccountV1, Stor
     }
 }
 
-pub async fn retrieve_transfers(sk: &str, name: &str) -> Result<RgbTransfersV1, StorageError> {
+pub async fn retrieve_transfers(sk: &str, name: &str) -> Result<RgbTransfersV2, StorageError> {
     let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
         .to_hex()
         .to_lowercase();
@@ -222,7 +269,7 @@ pub async fn retrieve_transfers(sk: &str, name: &str) -> Result<RgbTransfersV1,
         .map_err(|op| StorageError::CarbonadoRetrieve(name.to_string(), op.to_string()))?;
 
     if data.is_empty() {
-        Ok(RgbTransfersV1::default())
+        Ok(RgbTransfersV2::default())
     } else {
         let mut version: [u8; 8] = default!();
         if let Some(metadata) = metadata {
@@ -235,6 +282,68 @@ pub async fn retrieve_transfers(sk: &str, name: &str) -> Result<RgbTransfersV1,
     }
 }
 
+pub async fn store_invoices(
+    sk: &str,
+    name: &str,
+    rgb_invoices: &RgbInvoices,
+) -> Result<(), StorageError> {
+    let data = to_allocvec(rgb_invoices)
+        .map_err(|op| StorageError::StrictWrite(name.to_string(), op.to_string()))?;
+
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    store(
+        sk,
+        &format!("{hashed_name}.c15"),
+        &data,
+        true,
+        Some(RGB_STRICT_TYPE_VERSION.to_vec()),
+    )
+    .await
+    .map_err(|op| StorageError::CarbonadoWrite(name.to_string(), op.to_string()))
+}
+
+pub async fn store_airdrops(
+    sk: &str,
+    name: &str,
+    rgb_airdrops: &RgbAirdrops,
+) -> Result<(), StorageError> {
+    let data = to_allocvec(rgb_airdrops)
+        .map_err(|op| StorageError::StrictWrite(name.to_string(), op.to_string()))?;
+
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    store(
+        sk,
+        &format!("{hashed_name}.c15"),
+        &data,
+        true,
+        Some(RGB_STRICT_TYPE_VERSION.to_vec()),
+    )
+    .await
+    .map_err(|op| StorageError::CarbonadoWrite(name.to_string(), op.to_string()))
+}
+
+pub async fn store_consignment(sk: &str, name: &str, consig: &str) -> Result<(), StorageError> {
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    store(
+        sk,
+        &format!("{hashed_name}.c15"),
+        consig.as_bytes(),
+        true,
+        Some(RGB_STRICT_TYPE_VERSION.to_vec()),
+    )
+    .await
+    .map_err(|op| StorageError::CarbonadoWrite(name.to_string(), op.to_string()))
+}
+
 pub async fn retrieve_offers(sk: &str, name: &str) -> Result<RgbOffers, StorageError> {
     let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
         .to_hex()
@@ -271,6 +380,60 @@ pub async fn retrieve_bids(sk: &str, name: &str) -> Result<RgbBids, StorageError
     }
 }
 
+pub async fn retrieve_invoices(sk: &str, name: &str) -> Result<RgbInvoices, StorageError> {
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    let (data, _) = retrieve(sk, &format!("{hashed_name}.c15"), vec![])
+        .await
+        .map_err(|op| StorageError::CarbonadoRetrieve(name.to_string(), op.to_string()))?;
+
+    if data.is_empty() {
+        Ok(RgbInvoices::default())
+    } else {
+        let rgb_invoices = from_bytes(&data)
+            .map_err(|op| StorageError::StrictRetrieve(name.to_string(), op.to_string()))?;
+        Ok(rgb_invoices)
+    }
+}
+
+pub async fn retrieve_consignment(sk: &str, name: &str) -> Result<Option<String>, StorageError> {
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    let (data, _) = retrieve(sk, &format!("{hashed_name}.c15"), vec![])
+        .await
+        .map_err(|op| StorageError::CarbonadoRetrieve(name.to_string(), op.to_string()))?;
+
+    if data.is_empty() {
+        Ok(None)
+    } else {
+        let consig = String::from_utf8(data)
+            .map_err(|op| StorageError::SerializeRetrieve(name.to_string(), op.to_string()))?;
+        Ok(Some(consig))
+    }
+}
+
+pub async fn retrieve_airdrops(sk: &str, name: &str) -> Result<RgbAirdrops, StorageError> {
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    let (data, _) = retrieve(sk, &format!("{hashed_name}.c15"), vec![])
+        .await
+        .map_err(|op| StorageError::CarbonadoRetrieve(name.to_string(), op.to_string()))?;
+
+    if data.is_empty() {
+        Ok(RgbAirdrops::default())
+    } else {
+        let rgb_airdrops = from_bytes(&data)
+            .map_err(|op| StorageError::StrictRetrieve(name.to_string(), op.to_string()))?;
+        Ok(rgb_airdrops)
+    }
+}
+
 // CDRT Operations
 pub async fn cdrt_store_wallets(sk: &str, name: &str, changes: &[u8]) -> Result<(), StorageError> {
     let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
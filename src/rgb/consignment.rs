@@ -2,24 +2,38 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, HashMap},
     iter,
+    str::FromStr,
 };
 
-use amplify::ByteArray;
+use amplify::{ByteArray, Wrapper};
 use bitcoin_30::{hashes::Hash, psbt::Psbt};
 use bp::{Outpoint, Txid};
 use chrono::Utc;
 use rgbstd::{
     containers::{Bindle, BuilderSeal, Transfer},
     contract::{ContractId, GraphSeal, Operation, Opout, SecretSeal},
-    interface::{BuilderError, ContractSuppl, TypedState, VelocityHint},
+    interface::{
+        rgb21::{Allocation, OwnedFraction, TokenIndex},
+        BuilderError, ContractSuppl, TypedState, VelocityHint,
+    },
     persistence::{Inventory, Stash},
     schema::AssignmentType,
+    stl::Amount,
 };
 use rgbwallet::{
     psbt::{PsbtDbc, RgbExt, RgbInExt, RgbOutExt},
     Beneficiary, PayError, RgbInvoice,
 };
 use seals::txout::CloseMethod;
+use strict_encoding::{FieldName, StrictDeserialize, TypeName};
+
+use crate::rgb::{
+    constants::{
+        RGB20_BURNED_SUPPLY, RGB20_BURN_TRANSITION, RGB20_INFLATION_ASSIGNMENT,
+        RGB20_INFLATION_TRANSITION, RGB20_REPLACE_TRANSITION, RGB21_TOKEN_PARAM,
+    },
+    schemata::udc_schema,
+};
 
 #[derive(Clone, Debug, Display, Error, From)]
 #[display(doc_comments)]
@@ -41,6 +55,13 @@ pub struct NewTransferOptions {
     pub other_invoices: Vec<RgbInvoice>,
     pub offer_id: Option<String>,
     pub bid_id: Option<String>,
+    /// Issue the invoice amount from the inflation allowance (only inflatable RGB20)
+    pub inflation: bool,
+    /// Assign the invoice amount to an unspendable seal (only RGB20)
+    pub burn: bool,
+    /// Issue the invoice amount from the inflation allowance, recording it as burned
+    /// to replace lost allocations (only inflatable RGB20)
+    pub replace: bool,
 }
 
 impl NewTransferOptions {
@@ -53,6 +74,37 @@ impl NewTransferOptions {
     }
 }
 
+/// Seal of an output that can never exist, used to provably destroy state
+pub fn burn_seal(method: CloseMethod) -> GraphSeal {
+    GraphSeal::new(method, Txid::from_byte_array([0; 32]), 0)
+}
+
+/// Whether the seal is the unspendable one used to destroy state
+pub fn is_burn_seal(seal: &GraphSeal) -> bool {
+    let burn = burn_seal(seal.method);
+    seal.txid == burn.txid && seal.vout == burn.vout
+}
+
+/// Whether the outpoint is the one of the unspendable seal
+pub fn is_burn_outpoint(outpoint: Outpoint) -> bool {
+    outpoint.txid == Txid::from_byte_array([0; 32])
+}
+
+/// Token requested by a RGB21 invoice (only collections)
+pub fn invoice_token_index(invoice: &RgbInvoice) -> Option<u32> {
+    invoice
+        .unknown_query
+        .get(RGB21_TOKEN_PARAM)
+        .and_then(|index| u32::from_str(index).ok())
+}
+
+fn uda_allocation(state: &TypedState) -> Option<Allocation> {
+    match state {
+        TypedState::Data(data) => Allocation::from_strict_serialized(data.as_ref().to_owned()).ok(),
+        _ => None,
+    }
+}
+
 pub trait ConsignmentEx: Inventory {
     /// # Assumptions
     ///
@@ -76,11 +128,30 @@ pub trait ConsignmentEx: Inventory {
             }
         }
         let contract_id = invoice.contract.ok_or(PayError::NoContract)?;
+        let token_index = invoice_token_index(&invoice).map(TokenIndex::from_inner);
         let iface = invoice.iface.ok_or(PayError::NoIface)?;
-        let mut main_builder =
-            self.transition_builder(contract_id, iface.clone(), invoice.operation.clone())?;
+        let operation = match (options.replace, options.inflation, options.burn) {
+            (true, ..) => Some(TypeName::from(RGB20_REPLACE_TRANSITION)),
+            (_, true, _) => Some(TypeName::from(RGB20_INFLATION_TRANSITION)),
+            (.., true) => Some(TypeName::from(RGB20_BURN_TRANSITION)),
+            _ => invoice.operation.clone(),
+        };
+        // Contracts without a burn transition (e.g. NIA) burn through a plain transfer,
+        // so the burned supply is only recorded by the unspendable seal
+        let record_burn = options.burn
+            && self
+                .transition_builder(contract_id, iface.clone(), operation.clone())
+                .is_ok();
+        let operation = if options.burn && !record_burn {
+            None
+        } else {
+            operation
+        };
+        let mut main_builder = self.transition_builder(contract_id, iface.clone(), operation)?;
 
         let (beneficiary_output, beneficiary) = match invoice.beneficiary {
+            // Burned state is revealed, so anyone can check the seal is unspendable
+            _ if options.burn => (None, BuilderSeal::Revealed(burn_seal(method))),
             Beneficiary::BlindedSeal(seal) => {
                 let seal = BuilderSeal::Concealed(seal);
                 (None, seal)
@@ -164,25 +235,64 @@ pub trait ConsignmentEx: Inventory {
         let assignment_id = main_builder
             .assignments_type(assignment_name)
             .ok_or(BuilderError::InvalidStateField(assignment_name.clone()))?;
+        let assignment_name = assignment_name.clone();
+        let allowance_id = match options.inflation || options.replace {
+            true => {
+                let allowance_name = FieldName::from(RGB20_INFLATION_ASSIGNMENT);
+                let allowance_id = main_builder
+                    .assignments_type(&allowance_name)
+                    .ok_or(BuilderError::InvalidStateField(allowance_name))?;
+                Some(allowance_id)
+            }
+            false => None,
+        };
         // TODO: select supplement basing on the signer trust level
         let suppl = self
             .contract_suppl(contract_id)
             .and_then(|set| set.first())
             .cloned();
 
+        let splits_tokens = self
+            .genesis(contract_id)
+            .map(|genesis| genesis.schema_id == udc_schema().schema_id())
+            .unwrap_or_default();
+
         let mut sum_inputs = 0u64;
+        let mut sum_allowance = 0u64;
         let mut type_state = TypedState::Void;
+        let mut uda_token = None;
         for (opout, state) in self.state_for_outpoints(contract_id, prev_outputs.iter().copied())? {
             main_builder = main_builder.add_input(opout)?;
-            if opout.ty != assignment_id {
+            if Some(opout.ty) == allowance_id {
+                if let TypedState::Amount(value) = state {
+                    sum_allowance += value;
+                }
+            } else if opout.ty != assignment_id {
                 let seal = output_for_assignment(suppl.as_ref(), opout.ty)?;
                 main_builder = main_builder.add_raw_state(opout.ty, seal, state)?;
             } else if let TypedState::Amount(value) = state {
                 sum_inputs += value;
                 type_state = state;
             } else if let TypedState::Data(_) = state {
-                sum_inputs += 1;
-                type_state = state;
+                let allocation = uda_allocation(&state);
+                let token = allocation.clone().map(|allocation| allocation.token_id());
+                let selected = match token_index.or(uda_token) {
+                    Some(index) => token == Some(index),
+                    None => true,
+                };
+
+                if selected {
+                    // UDAs can be owned by fractions, so the token can be partially transferred
+                    sum_inputs += allocation
+                        .map(|allocation| allocation.fraction().into_inner())
+                        .unwrap_or(1);
+                    uda_token = token;
+                    type_state = state;
+                } else {
+                    // Other tokens of the collection are kept by the owner
+                    let seal = output_for_assignment(suppl.as_ref(), opout.ty)?;
+                    main_builder = main_builder.add_raw_state(opout.ty, seal, state)?;
+                }
             }
         }
 
@@ -226,6 +336,45 @@ pub trait ConsignmentEx: Inventory {
 
         // Add change
         let amt = match invoice.owned_state {
+            TypedState::Amount(amt) if allowance_id.is_some() => {
+                // The new supply comes from the allowance, the owned assets are kept as change
+                let issued = amt + previous_state_value;
+                if sum_allowance < issued {
+                    return Err(PayError::InsufficientState);
+                }
+
+                if sum_inputs > 0 {
+                    let seal = output_for_assignment(suppl.as_ref(), assignment_id)?;
+                    let change = TypedState::Amount(sum_inputs);
+                    main_builder = main_builder.add_raw_state(assignment_id, seal, change)?;
+                }
+
+                if let Some(allowance_id) = allowance_id {
+                    // The consumed allowance is destroyed, so the issue script can check
+                    // the spent allowance is conserved
+                    let consumed = BuilderSeal::Revealed(burn_seal(method));
+                    main_builder = main_builder.add_raw_state(
+                        allowance_id,
+                        consumed,
+                        TypedState::Amount(issued),
+                    )?;
+
+                    if sum_allowance > issued {
+                        let seal = output_for_assignment(suppl.as_ref(), allowance_id)?;
+                        let change = TypedState::Amount(sum_allowance - issued);
+                        main_builder = main_builder.add_raw_state(allowance_id, seal, change)?;
+                    }
+                }
+
+                main_builder =
+                    main_builder.add_global_state("issuedSupply", Amount::from(issued))?;
+                if options.replace {
+                    main_builder =
+                        main_builder.add_global_state(RGB20_BURNED_SUPPLY, Amount::from(issued))?;
+                }
+                type_state = TypedState::Amount(issued);
+                amt
+            }
             TypedState::Amount(amt) => {
                 if sum_inputs < amt + previous_state_value {
                     return Err(PayError::InsufficientState);
@@ -234,9 +383,27 @@ pub trait ConsignmentEx: Inventory {
                 match sum_inputs.cmp(&amt) {
                     Ordering::Greater => {
                         let seal = output_for_assignment(suppl.as_ref(), assignment_id)?;
-                        let change = amt + previous_state_value;
-                        let change = TypedState::Amount(sum_inputs - change);
-                        main_builder = main_builder.add_raw_state(assignment_id, seal, change)?;
+                        let change = sum_inputs - (amt + previous_state_value);
+                        main_builder = match uda_token {
+                            // Only collections allow to split the token fractions
+                            Some(_) if !splits_tokens => {
+                                return Err(PayError::InsufficientState);
+                            }
+                            Some(token) => {
+                                let fraction = OwnedFraction::from_inner(change);
+                                let change = Allocation::with(token, fraction);
+                                main_builder.add_data_state(
+                                    assignment_name.clone(),
+                                    seal,
+                                    change,
+                                )?
+                            }
+                            None => main_builder.add_raw_state(
+                                assignment_id,
+                                seal,
+                                TypedState::Amount(change),
+                            )?,
+                        };
                         amt
                     }
                     Ordering::Equal => amt,
@@ -248,6 +415,10 @@ pub trait ConsignmentEx: Inventory {
             }
         };
 
+        if record_burn {
+            main_builder = main_builder.add_global_state(RGB20_BURNED_SUPPLY, Amount::from(amt))?;
+        }
+
         for PreviousSeal { seal, state } in previous_states.clone() {
             let prev_seal = BuilderSeal::Concealed(seal);
             main_builder = match state {
@@ -270,9 +441,17 @@ pub trait ConsignmentEx: Inventory {
             TypedState::Amount(_) => main_builder
                 .add_raw_state(assignment_id, beneficiary, TypedState::Amount(amt))?
                 .complete_transition(contract_id)?,
-            TypedState::Data(_) => main_builder
-                .add_raw_state(assignment_id, beneficiary, type_state)?
-                .complete_transition(contract_id)?,
+            TypedState::Data(_) => match uda_token {
+                Some(token) => {
+                    let allocation = Allocation::with(token, OwnedFraction::from_inner(amt));
+                    main_builder
+                        .add_data_state(assignment_name, beneficiary, allocation)?
+                        .complete_transition(contract_id)?
+                }
+                None => main_builder
+                    .add_raw_state(assignment_id, beneficiary, type_state)?
+                    .complete_transition(contract_id)?,
+            },
             _ => {
                 todo!("Only TypedState::Amount and TypedState::Data are currently supported")
             }
@@ -8,6 +8,37 @@ pub const RGB_STRICT_TYPE_VERSION: [u8; 8] = *b"rgbst161";
 pub const RGB_DEFAULT_FETCH_LIMIT: u32 = 10;
 pub const BITCOIN_DEFAULT_FETCH_LIMIT: u32 = 20;
 
+// Contract Terms
+pub const RGB_TERMS_MEDIA_TYPE: &str = "text/plain";
+
+// RGB20 Inflation
+pub const RGB20_INFLATION_ASSIGNMENT: &str = "inflationAllowance";
+pub const RGB20_INFLATION_TRANSITION: &str = "Issue";
+
+// RGB20 Burn and Replace
+pub const RGB20_BURN_TRANSITION: &str = "Burn";
+pub const RGB20_BURNED_SUPPLY: &str = "burnedSupply";
+pub const RGB20_REPLACE_TRANSITION: &str = "Replace";
+
+// RGB21 Collections
+pub const RGB21_TOKEN_PARAM: &str = "token";
+
+// Open Amount Invoices (range in contract units, the payer picks the amount)
+pub const RGB_INVOICE_MIN_AMOUNT_PARAM: &str = "min_amount";
+pub const RGB_INVOICE_MAX_AMOUNT_PARAM: &str = "max_amount";
+
+// Airdrop
+pub const RGB_AIRDROP_MAX_RECIPIENTS: usize = 50;
+
+// Witness Invoices (sats locked in the receiving output created by the sender)
+pub const RGB_WITNESS_OUTPUT_AMOUNT: u64 = 1000;
+
+// Transfer Receiver (polling interval in seconds)
+pub const RGB_RECEIVER_MIN_INTERVAL: u64 = 10;
+
+// Reorg Monitor (anchors deeper than this are no longer rechecked)
+pub const RGB_REORG_SAFE_DEPTH: u32 = 6;
+
 // General Errors
 #[cfg(target_arch = "wasm32")]
 pub const CARBONADO_UNAVAILABLE: &str = "carbonado filesystem";
@@ -1,21 +1,32 @@
-use amplify::{confinement::U32, hex::ToHex};
+use amplify::{confinement::U32, hex::ToHex, Wrapper};
 use bech32::{encode, ToBase32};
+use bitcoin_hashes::{sha256, Hash};
 use rgb::{Resolver, RgbWallet};
 use rgbstd::{
     contract::ContractId,
-    interface::{rgb21::TokenData, IfaceId, IfacePair},
+    interface::{
+        rgb21::{Allocation, TokenData},
+        ContractIface, IfaceId, IfacePair,
+    },
     persistence::{Inventory, InventoryInconsistency, StashInconsistency, Stock},
-    stl::{ContractData, DivisibleAssetSpec, RicardianContract, Timestamp},
+    stl::{
+        Amount, Attachment, ContractData, Details, DivisibleAssetSpec, Name, Precision,
+        RicardianContract, Timestamp,
+    },
 };
 use std::str::FromStr;
 use strict_encoding::{FieldName, StrictDeserialize, StrictSerialize};
 
 use crate::rgb::{
-    resolvers::ResolveSpent, structs::ContractBoilerplate, wallet::contract_allocations,
+    consignment::is_burn_outpoint,
+    constants::{RGB20_BURNED_SUPPLY, RGB20_INFLATION_ASSIGNMENT, RGB_TERMS_MEDIA_TYPE},
+    resolvers::ResolveSpent,
+    structs::ContractBoilerplate,
+    wallet::contract_allocations,
 };
 use crate::structs::{
-    AllocationValue, ContractFormats, ContractMediaDetail, ContractResponse, GenesisFormats,
-    MediaInfo,
+    AllocationDetail, AllocationValue, AttachInfo, ContractFormats, ContractMediaDetail,
+    ContractResponse, ContractTermsDetail, GenesisFormats, MediaInfo, UDADetail,
 };
 
 use super::{
@@ -42,6 +53,45 @@ pub enum ExportContractError {
     GlobalNotFound(String, String),
 }
 
+struct AssetSpecs {
+    ticker: String,
+    name: String,
+    details: Option<String>,
+    precision: u8,
+}
+
+/// RGB20 and RGB21 keep the asset naming in the `spec` global,
+/// RGB25 (CFA) has no ticker and splits it into `name`, `details` and `precision`
+fn export_specs(contract_iface: &ContractIface) -> Result<AssetSpecs, String> {
+    if let Ok(values) = contract_iface.global(FieldName::from("spec")) {
+        let specs = DivisibleAssetSpec::from_strict_val_unchecked(&values[0]);
+        return Ok(AssetSpecs {
+            ticker: specs.ticker().into(),
+            name: specs.name().into(),
+            details: specs.details().map(|details| details.into()),
+            precision: specs.precision.into(),
+        });
+    }
+
+    let name = contract_iface
+        .global(FieldName::from("name"))
+        .map_err(|err| err.to_string())?;
+    let precision = contract_iface
+        .global(FieldName::from("precision"))
+        .map_err(|err| err.to_string())?;
+    let details = contract_iface
+        .global(FieldName::from("details"))
+        .ok()
+        .and_then(|values| values.first().map(Details::from_strict_val_unchecked));
+
+    Ok(AssetSpecs {
+        ticker: String::new(),
+        name: Name::from_strict_val_unchecked(&name[0]).to_string(),
+        details: details.map(|details| details.to_string()),
+        precision: Precision::from_strict_val_unchecked(&precision[0]).into(),
+    })
+}
+
 pub fn export_boilerplate(
     contract_id: ContractId,
     stock: &mut Stock,
@@ -61,24 +111,53 @@ pub fn export_boilerplate(
         .contract_iface(contract_id, iface_id.to_owned())
         .expect("invalid contracts state");
 
-    let ty: FieldName = FieldName::from("spec");
-    let specs = match contract_iface.global(ty) {
-        Ok(values) => DivisibleAssetSpec::from_strict_val_unchecked(&values[0]),
-        Err(err) => {
-            return Err(ExportContractError::StrictInconsistency(
-                contract_id.to_string(),
-                err.to_string(),
-            ))
-        }
-    };
+    let specs = export_specs(&contract_iface)
+        .map_err(|err| ExportContractError::StrictInconsistency(contract_id.to_string(), err))?;
 
     Ok(ContractBoilerplate {
         contract_id: contract_id.to_string(),
         iface_id: iface_id.to_string(),
-        precision: specs.precision.into(),
+        ticker: specs.ticker,
+        name: specs.name,
+        precision: specs.precision,
     })
 }
 
+/// Sums the issued supply of the contract: the genesis supply and the supply
+/// of each later issue and replace operation (only RGB20/RGB25)
+pub fn contract_issued_supply(contract_iface: &ContractIface) -> Option<u64> {
+    contract_iface
+        .global(FieldName::from("issuedSupply"))
+        .ok()
+        .map(|values| {
+            values
+                .iter()
+                .map(|value| Amount::from_strict_val_unchecked(value).value())
+                .sum()
+        })
+}
+
+/// Sums the burned supply of the contract (only RGB20): inflatable contracts
+/// record the burned supply, the others only assign it to the unspendable seal
+pub fn contract_burned_supply(contract_iface: &ContractIface) -> u64 {
+    match contract_iface.global(FieldName::from(RGB20_BURNED_SUPPLY)) {
+        Ok(values) => values
+            .iter()
+            .map(|value| Amount::from_strict_val_unchecked(value).value())
+            .sum(),
+        Err(_) => contract_iface
+            .fungible(FieldName::from("assetOwner"), &None)
+            .map(|allocations| {
+                allocations
+                    .iter()
+                    .filter(|a| is_burn_outpoint(a.owner))
+                    .map(|a| a.value)
+                    .sum()
+            })
+            .unwrap_or_default(),
+    }
+}
+
 pub fn export_contract<T>(
     contract_id: ContractId,
     stock: &mut Stock,
@@ -166,15 +245,9 @@ where
         .contract_iface(contract_id, iface_id.to_owned())
         .expect("invalid contracts state");
 
-    let ty: FieldName = FieldName::from("spec");
-    let specs = match contract_iface.global(ty) {
-        Ok(values) => DivisibleAssetSpec::from_strict_val_unchecked(&values[0]),
-        Err(err) => {
-            return Err(ExportContractError::StrictInconsistency(
-                contr_id,
-                err.to_string(),
-            ))
-        }
+    let specs = match export_specs(&contract_iface) {
+        Ok(specs) => specs,
+        Err(err) => return Err(ExportContractError::StrictInconsistency(contr_id, err)),
     };
 
     let ty: FieldName = FieldName::from("created");
@@ -189,24 +262,42 @@ where
     };
 
     let mut description = String::new();
+    let mut terms = None;
     let ty_data: FieldName = FieldName::from("data");
     if let Ok(values) = contract_iface.global(ty_data) {
         let contract = ContractData::from_strict_val_unchecked(&values[0]);
         description = contract.terms.to_string();
+        // Only RGB20 commits the terms document as contract media
+        let media = contract.media.filter(|_| iface.name.as_str() == "RGB20");
+        terms = Some(export_terms(&description, media));
     };
 
     let ty_terms: FieldName = FieldName::from("terms");
     if let Ok(values) = contract_iface.global(ty_terms) {
         let contract = RicardianContract::from_strict_val_unchecked(&values[0]);
         description = contract.to_string();
+        terms = Some(export_terms(&description, None));
     };
 
     let iface_index = match iface.name.as_str() {
         "RGB20" => 20,
         "RGB21" => 21,
+        "RGB25" => 25,
         _ => 9,
     };
 
+    // Only inflatable RGB20
+    let allowance = contract_iface
+        .fungible(FieldName::from(RGB20_INFLATION_ASSIGNMENT), &None)
+        .ok()
+        .map(|allocations| {
+            allocations
+                .iter()
+                .filter(|a| !is_burn_outpoint(a.owner))
+                .map(|a| a.value)
+                .sum()
+        });
+
     let mut balance = 0;
     let mut allocations = vec![];
     if let Some(wallet) = wallet {
@@ -220,30 +311,47 @@ where
             .filter(|a| a.is_mine && !a.is_spent)
             .map(|a| match a.value {
                 AllocationValue::Value(value) => value.to_owned(),
-                AllocationValue::UDA(_) => 1,
+                AllocationValue::UDA(position) => position.fraction,
             })
             .sum();
     }
 
-    let balance_normalised = ContractAmount::with(balance, specs.precision.into()).to_string();
+    let balance_normalised = ContractAmount::with(balance, specs.precision).to_string();
     let balance_normalised = f64::from_str(&balance_normalised)
         .map_err(|_| ExportContractError::WrongValue(contr_id.clone(), balance_normalised))?;
 
     let mut supply = 0;
-    for (index, (_, global_assign)) in contract_bindle.genesis.assignments.iter().enumerate() {
-        let idx = index as u16;
-        if global_assign.is_fungible() {
-            if let Ok(Some(reveal)) = global_assign.as_fungible_state_at(idx) {
-                supply += reveal.value.as_u64();
+    let ty: FieldName = FieldName::from("issuedSupply");
+    if let (Some(_), Ok(values)) = (allowance, contract_iface.global(ty)) {
+        // Inflatable assets increase the supply on each issue operation
+        supply = values
+            .iter()
+            .map(|value| Amount::from_strict_val_unchecked(value).value())
+            .sum();
+    } else {
+        for (index, (_, global_assign)) in contract_bindle.genesis.assignments.iter().enumerate() {
+            let idx = index as u16;
+            if global_assign.is_fungible() {
+                if let Ok(Some(reveal)) = global_assign.as_fungible_state_at(idx) {
+                    supply += reveal.value.as_u64();
+                }
+            } else if global_assign.is_structured() {
+                // UDAs are issued by fractions
+                if let Ok(Some(reveal)) = global_assign.as_structured_state_at(idx) {
+                    supply += Allocation::from_strict_serialized(reveal.as_ref().to_owned())
+                        .map(|allocation| allocation.fraction().into_inner())
+                        .unwrap_or(1);
+                }
             }
-        } else if global_assign.is_structured() && global_assign.as_structured_state_at(idx).is_ok()
-        {
-            supply += 1;
         }
     }
 
+    let issued = contract_issued_supply(&contract_iface).unwrap_or(supply);
+    let burned = contract_burned_supply(&contract_iface);
+
     // Only RGB21/UDA
     let mut meta = none!();
+    let mut tokens = vec![];
     let ty: FieldName = FieldName::from("tokens");
     if contract_iface.global(ty.clone()).is_ok() {
         if let Some(type_id) = contract_iface.iface.global_type(&ty) {
@@ -275,6 +383,66 @@ where
                 }
             };
 
+            for token_data in tokens_data.iter() {
+                let token_index: u32 = token_data
+                    .index
+                    .to_string()
+                    .parse()
+                    .expect("invalid token_index");
+
+                let token_allocations: Vec<AllocationDetail> = allocations
+                    .iter()
+                    .filter(|a| match &a.value {
+                        AllocationValue::UDA(position) => position.token_index == token_index,
+                        _ => false,
+                    })
+                    .cloned()
+                    .collect();
+
+                let token_balance = token_allocations
+                    .iter()
+                    .filter(|a| a.is_mine && !a.is_spent)
+                    .map(|a| match &a.value {
+                        AllocationValue::UDA(position) => position.fraction,
+                        AllocationValue::Value(value) => *value,
+                    })
+                    .sum();
+
+                let attach = token_data.preview.as_ref().map(|preview| AttachInfo {
+                    ty: preview.ty.to_string(),
+                    source: base64::encode(&preview.data),
+                });
+
+                let media = token_data
+                    .media
+                    .iter()
+                    .chain(token_data.attachments.values())
+                    .map(|attachment| MediaInfo {
+                        ty: attachment.ty.to_string(),
+                        source: attachment.digest.to_hex(),
+                    })
+                    .collect();
+
+                tokens.push(UDADetail {
+                    token_index,
+                    ticker: token_data
+                        .ticker
+                        .as_ref()
+                        .map(|ticker| ticker.to_string())
+                        .unwrap_or_else(|| specs.ticker.clone()),
+                    name: token_data
+                        .name
+                        .as_ref()
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| specs.name.clone()),
+                    description: specs.details.clone().unwrap_or_default(),
+                    balance: token_balance,
+                    media,
+                    attach,
+                    allocations: token_allocations,
+                });
+            }
+
             if tokens_data.len() <= 1 {
                 let token_data = tokens_data[0].clone();
 
@@ -311,9 +479,9 @@ where
                         .to_string()
                         .parse()
                         .expect("invalid token_index"),
-                    ticker: specs.ticker().into(),
-                    name: specs.name().into(),
-                    description: specs.details().unwrap_or_default().into(),
+                    ticker: specs.ticker.clone(),
+                    name: specs.name.clone(),
+                    description: specs.details.clone().unwrap_or_default(),
                     balance,
                     preview,
                     media,
@@ -330,11 +498,14 @@ where
         contract_id: contr_id,
         iimpl_id,
         iface: iface.name.to_string(),
-        ticker: specs.ticker().into(),
-        name: specs.name().into(),
+        ticker: specs.ticker.clone(),
+        name: specs.name.clone(),
         description,
-        precision: specs.precision.into(),
+        precision: specs.precision,
         supply,
+        issued,
+        burned,
+        circulating: issued.saturating_sub(burned),
         balance,
         balance_normalised,
         allocations,
@@ -350,11 +521,35 @@ where
             armored: "".to_string(),
         },
         meta,
+        allowance,
+        tokens,
+        terms,
     };
 
     Ok(resp)
 }
 
+/// Digest of a terms document (sha256, in hexadecimal)
+pub fn terms_digest(document: &[u8]) -> String {
+    sha256::Hash::hash(document).to_hex()
+}
+
+/// Terms committed in the genesis, with the digest of the terms document
+fn export_terms(text: &str, media: Option<Attachment>) -> ContractTermsDetail {
+    match media {
+        Some(attachment) => ContractTermsDetail {
+            text: text.to_string(),
+            ty: attachment.ty.to_string(),
+            digest: attachment.digest.to_hex(),
+        },
+        None => ContractTermsDetail {
+            text: text.to_string(),
+            ty: RGB_TERMS_MEDIA_TYPE.to_string(),
+            digest: terms_digest(text.as_bytes()),
+        },
+    }
+}
+
 pub async fn extract_metadata(
     metadata: ContractMediaDetail,
 ) -> Result<ContractMediaDetail, ProxyError> {
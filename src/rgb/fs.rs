@@ -1,23 +1,30 @@
 use rgbstd::persistence::Stock;
 
 use crate::constants::storage_keys::{
-    ASSETS_BIDS, ASSETS_OFFERS, ASSETS_STOCK, ASSETS_TRANSFERS, ASSETS_WALLETS, MARKETPLACE_OFFERS,
+    ASSETS_AIRDROPS, ASSETS_BIDS, ASSETS_CONSIGNMENTS, ASSETS_INVOICES, ASSETS_OFFERS,
+    ASSETS_SEAL_SECRETS, ASSETS_STOCK, ASSETS_TRANSFERS, ASSETS_WALLETS, MARKETPLACE_OFFERS,
 };
 use crate::rgb::{
     carbonado::{
-        cdrt_retrieve_wallets, cdrt_store_wallets, retrieve_bids as retrieve_rgb_bids,
-        retrieve_offers as retrieve_rgb_offers,
-        retrieve_public_offers as retrieve_rgb_public_offers, retrieve_stock as retrieve_rgb_stock,
+        cdrt_retrieve_wallets, cdrt_store_wallets, retrieve_airdrops as retrieve_rgb_airdrops,
+        retrieve_bids as retrieve_rgb_bids, retrieve_consignment as retrieve_rgb_consignment,
+        retrieve_invoices as retrieve_rgb_invoices, retrieve_offers as retrieve_rgb_offers,
+        retrieve_public_offers as retrieve_rgb_public_offers,
+        retrieve_seal_secrets as retrieve_rgb_seal_secrets, retrieve_stock as retrieve_rgb_stock,
         retrieve_swap_offer_bid as retrieve_rgb_swap_offer_bid,
         retrieve_transfers as retrieve_rgb_transfers, retrieve_wallets,
-        store_bids as store_rgb_bids, store_offers as store_rgb_offers,
-        store_public_offers as store_rgb_public_offers, store_stock as store_rgb_stock,
+        store_airdrops as store_rgb_airdrops, store_bids as store_rgb_bids,
+        store_consignment as store_rgb_consignment, store_invoices as store_rgb_invoices,
+        store_offers as store_rgb_offers, store_public_offers as store_rgb_public_offers,
+        store_seal_secrets as store_rgb_seal_secrets, store_stock as store_rgb_stock,
         store_swap_offer_bid, store_transfers as store_rgb_transfer, store_wallets,
     },
     crdt::LocalRgbAccount,
     crdt::{LocalRgbOfferBid, LocalRgbOffers},
-    structs::{RgbAccountV1, RgbTransfersV1},
+    ledger::RgbInvoices,
+    structs::{RgbAccountV1, RgbAirdrops, RgbTransfersV2},
     swap::{RgbBids, RgbOffers},
+    transfer::SealSecrets,
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, Display, From, Error)]
@@ -35,6 +42,14 @@ pub enum RgbPersistenceError {
     RetrieveRgbOffers(String),
     // Retrieve Bids Error. {0}
     RetrieveRgbBids(String),
+    // Retrieve Invoices Error. {0}
+    RetrieveRgbInvoices(String),
+    // Retrieve Airdrops Error. {0}
+    RetrieveRgbAirdrops(String),
+    // Retrieve Seal Secrets Error. {0}
+    RetrieveSealSecrets(String),
+    // Retrieve Consignment Error. {0}
+    RetrieveConsignment(String),
     // Retrieve Swap Bid Error. {0}
     RetrieveSwapBids(String),
     // Retrieve Public Offers Error. {0}
@@ -51,6 +66,14 @@ pub enum RgbPersistenceError {
     WriteRgbOffers(String),
     // Store Bids Error. {0}
     WriteRgbBids(String),
+    // Store Invoices Error. {0}
+    WriteRgbInvoices(String),
+    // Store Airdrops Error. {0}
+    WriteRgbAirdrops(String),
+    // Store Seal Secrets Error. {0}
+    WriteSealSecrets(String),
+    // Store Consignment Error. {0}
+    WriteConsignment(String),
     // Store Public Offers Error. {0}
     WriteRgbPublicOffers(String),
     // Store Swap Bid Error. {0}
@@ -65,7 +88,7 @@ pub async fn retrieve_stock(sk: &str) -> Result<Stock, RgbPersistenceError> {
     Ok(stock)
 }
 
-pub async fn retrieve_transfers(sk: &str) -> Result<RgbTransfersV1, RgbPersistenceError> {
+pub async fn retrieve_transfers(sk: &str) -> Result<RgbTransfersV2, RgbPersistenceError> {
     let rgb_account = retrieve_rgb_transfers(sk, ASSETS_TRANSFERS)
         .await
         .map_err(|op| RgbPersistenceError::RetrieveRgbTransfersV1(op.to_string()))?;
@@ -125,6 +148,42 @@ pub async fn retrieve_bids(sk: &str) -> Result<RgbBids, RgbPersistenceError> {
     Ok(bids)
 }
 
+pub async fn retrieve_invoices(sk: &str) -> Result<RgbInvoices, RgbPersistenceError> {
+    let invoices = retrieve_rgb_invoices(sk, ASSETS_INVOICES)
+        .await
+        .map_err(|op| RgbPersistenceError::RetrieveRgbInvoices(op.to_string()))?;
+
+    Ok(invoices)
+}
+
+pub async fn retrieve_airdrops(sk: &str) -> Result<RgbAirdrops, RgbPersistenceError> {
+    let airdrops = retrieve_rgb_airdrops(sk, ASSETS_AIRDROPS)
+        .await
+        .map_err(|op| RgbPersistenceError::RetrieveRgbAirdrops(op.to_string()))?;
+
+    Ok(airdrops)
+}
+
+pub async fn retrieve_seal_secrets(sk: &str) -> Result<SealSecrets, RgbPersistenceError> {
+    let seal_secrets = retrieve_rgb_seal_secrets(sk, ASSETS_SEAL_SECRETS)
+        .await
+        .map_err(|op| RgbPersistenceError::RetrieveSealSecrets(op.to_string()))?;
+
+    Ok(seal_secrets)
+}
+
+pub async fn retrieve_consignment(
+    sk: &str,
+    consig_id: &str,
+) -> Result<Option<String>, RgbPersistenceError> {
+    let consignment =
+        retrieve_rgb_consignment(sk, &format!("{ASSETS_CONSIGNMENTS}-{consig_id}.c15"))
+            .await
+            .map_err(|op| RgbPersistenceError::RetrieveConsignment(op.to_string()))?;
+
+    Ok(consignment)
+}
+
 pub async fn retrieve_stock_account(
     sk: &str,
 ) -> Result<(Stock, RgbAccountV1), RgbPersistenceError> {
@@ -133,13 +192,13 @@ pub async fn retrieve_stock_account(
 
 pub async fn retrieve_stock_transfers(
     sk: &str,
-) -> Result<(Stock, RgbTransfersV1), RgbPersistenceError> {
+) -> Result<(Stock, RgbTransfersV2), RgbPersistenceError> {
     Ok((retrieve_stock(sk).await?, retrieve_transfers(sk).await?))
 }
 
 pub async fn retrieve_stock_account_transfers(
     sk: &str,
-) -> Result<(Stock, RgbAccountV1, RgbTransfersV1), RgbPersistenceError> {
+) -> Result<(Stock, RgbAccountV1, RgbTransfersV2), RgbPersistenceError> {
     Ok((
         retrieve_stock(sk).await?,
         retrieve_account(sk).await?,
@@ -155,7 +214,7 @@ pub async fn store_stock(sk: &str, stock: Stock) -> Result<(), RgbPersistenceErr
 
 pub async fn store_transfers(
     sk: &str,
-    transfers: RgbTransfersV1,
+    transfers: RgbTransfersV2,
 ) -> Result<(), RgbPersistenceError> {
     store_rgb_transfer(sk, ASSETS_TRANSFERS, &transfers)
         .await
@@ -186,6 +245,47 @@ pub async fn store_bids(sk: &str, rgb_bids: RgbBids) -> Result<(), RgbPersistenc
         .map_err(|op| RgbPersistenceError::WriteRgbBids(op.to_string()))
 }
 
+pub async fn store_invoices(
+    sk: &str,
+    rgb_invoices: RgbInvoices,
+) -> Result<(), RgbPersistenceError> {
+    store_rgb_invoices(sk, ASSETS_INVOICES, &rgb_invoices)
+        .await
+        .map_err(|op| RgbPersistenceError::WriteRgbInvoices(op.to_string()))
+}
+
+pub async fn store_airdrops(
+    sk: &str,
+    rgb_airdrops: RgbAirdrops,
+) -> Result<(), RgbPersistenceError> {
+    store_rgb_airdrops(sk, ASSETS_AIRDROPS, &rgb_airdrops)
+        .await
+        .map_err(|op| RgbPersistenceError::WriteRgbAirdrops(op.to_string()))
+}
+
+pub async fn store_seal_secrets(
+    sk: &str,
+    seal_secrets: SealSecrets,
+) -> Result<(), RgbPersistenceError> {
+    store_rgb_seal_secrets(sk, ASSETS_SEAL_SECRETS, &seal_secrets)
+        .await
+        .map_err(|op| RgbPersistenceError::WriteSealSecrets(op.to_string()))
+}
+
+pub async fn store_consignment(
+    sk: &str,
+    consig_id: &str,
+    consignment: &str,
+) -> Result<(), RgbPersistenceError> {
+    store_rgb_consignment(
+        sk,
+        &format!("{ASSETS_CONSIGNMENTS}-{consig_id}.c15"),
+        consignment,
+    )
+    .await
+    .map_err(|op| RgbPersistenceError::WriteConsignment(op.to_string()))
+}
+
 pub async fn store_swap_bids(
     sk: &str,
     name: &str,
@@ -215,7 +315,7 @@ pub async fn store_stock_account(
 pub async fn store_stock_transfers(
     sk: &str,
     stock: Stock,
-    transfers: RgbTransfersV1,
+    transfers: RgbTransfersV2,
 ) -> Result<(), RgbPersistenceError> {
     store_stock(sk, stock).await?;
     store_transfers(sk, transfers).await
@@ -225,7 +325,7 @@ pub async fn store_stock_account_transfers(
     sk: &str,
     stock: Stock,
     account: RgbAccountV1,
-    transfers: RgbTransfersV1,
+    transfers: RgbTransfersV2,
 ) -> Result<(), RgbPersistenceError> {
     store_stock(sk, stock).await?;
     store_account(sk, account).await?;
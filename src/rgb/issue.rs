@@ -3,19 +3,19 @@ use amplify::{
     Wrapper,
 };
 use bp::{seals::txout::ExplicitSeal, Chain, Txid};
-use rgb_schemata::{nia_rgb20, nia_schema, uda_rgb21, uda_schema};
+use rgb_schemata::{cfa_rgb25, cfa_schema, nia_rgb20, nia_schema, uda_rgb21, uda_schema};
 use rgbstd::{
-    containers::Contract,
-    contract::GenesisSeal,
+    containers::{BuilderSeal, Contract},
+    contract::{GenesisSeal, SecretSeal},
     interface::{
         rgb20, rgb21,
         rgb21::{Allocation, EmbeddedMedia, OwnedFraction, TokenData, TokenIndex},
-        BuilderError, ContractBuilder,
+        rgb25, BuilderError, ContractBuilder,
     },
     persistence::{Inventory, Stash, Stock},
     resolvers::ResolveHeight,
     stl::{
-        Amount, Attachment, ContractData, DivisibleAssetSpec, MediaType, Precision,
+        Amount, Attachment, ContractData, Details, DivisibleAssetSpec, MediaType, Name, Precision,
         RicardianContract, Timestamp,
     },
     validation::{Failure, ResolveTx},
@@ -23,7 +23,16 @@ use rgbstd::{
 use std::str::FromStr;
 use strict_types::encoding::TypeName;
 
-use crate::structs::IssueMediaRequest;
+use crate::{
+    rgb::{
+        constants::RGB20_INFLATION_ASSIGNMENT,
+        schemata::{ifa_rgb20, ifa_schema, udc_rgb21, udc_schema},
+    },
+    structs::{
+        InflationAllowanceRequest, IssueBeneficiaryRequest, IssueMediaRequest, IssueTermsRequest,
+        MediaInfo,
+    },
+};
 
 #[derive(Clone, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
@@ -32,6 +41,14 @@ pub enum IssueContractError {
     Forge(BuilderError),
     /// The contract interface {0} is not supported in issuer operation
     NoContractSupport(String),
+    /// The contract interface {0} cannot distribute the supply in the genesis
+    NoDistributionSupport(String),
+    /// The contract interface {0} cannot commit a terms document attachment
+    NoTermsSupport(String),
+    /// The contract interface {0} cannot issue fractional tokens
+    NoFractionalSupport(String),
+    /// The beneficiaries amount ({0}) does not match the supply ({1})
+    WrongBeneficiaries(u64, u64),
     /// The contract {0} contains failures {1:?}
     ContractInvalid(String, Vec<Failure>),
     /// The contract {0} cannot be imported (reason: {1})
@@ -49,6 +66,12 @@ pub fn issue_contract<T>(
     seal: &str,
     network: &str,
     meta: Option<IssueMediaRequest>,
+    inflation: Vec<InflationAllowanceRequest>,
+    collection: Vec<IssueMediaRequest>,
+    beneficiaries: Vec<IssueBeneficiaryRequest>,
+    terms: Option<IssueTermsRequest>,
+    fractional: bool,
+    created: Option<i64>,
     resolver: &mut T,
     stock: &mut Stock,
 ) -> Result<Contract, IssueContractError>
@@ -63,19 +86,103 @@ where
         .iface_by_name(&iface_name)
         .map_err(|_| IssueContractError::Forge(BuilderError::InterfaceMismatch))?;
 
-    let contract_issued = match iface.name.as_str() {
-        "RGB20" => {
-            issue_fungible_asset(ticker, name, description, precision, supply, seal, network)
+    if !beneficiaries.is_empty() && iface.name.as_str() == "RGB21" {
+        return Err(IssueContractError::NoDistributionSupport(
+            iface.name.to_string(),
+        ));
+    }
+
+    if fractional && iface.name.as_str() != "RGB21" {
+        return Err(IssueContractError::NoFractionalSupport(
+            iface.name.to_string(),
+        ));
+    }
+
+    if !beneficiaries.is_empty() {
+        let total = beneficiaries
+            .iter()
+            .try_fold(0u64, |total, b| total.checked_add(b.amount))
+            .ok_or(IssueContractError::Issue(IssueError::SupplyOverflow))?;
+        if total != supply {
+            return Err(IssueContractError::WrongBeneficiaries(total, supply));
+        }
+    }
+
+    // Legal terms (the description is used as terms by default)
+    let terms = match terms {
+        Some(IssueTermsRequest { text, media }) => {
+            if media.is_some() && iface.name.as_str() != "RGB20" {
+                return Err(IssueContractError::NoTermsSupport(iface.name.to_string()));
+            }
+
+            let media = media
+                .map(|media| media_attachment(&media))
+                .transpose()
+                .map_err(IssueContractError::Issue)?;
+            let terms = RicardianContract::from_str(&text).expect("invalid contract text");
+            ContractData { terms, media }
         }
+        None => {
+            let terms = RicardianContract::from_str(description).expect("invalid contract text");
+            ContractData { terms, media: None }
+        }
+    };
+
+    // The same creation date reproduces the contract id of a previous preview
+    let created = created.map(Timestamp::from).unwrap_or_else(Timestamp::now);
+    let owners = genesis_owners(seal, supply, beneficiaries).map_err(IssueContractError::Issue)?;
+    let contract_issued = match iface.name.as_str() {
+        "RGB20" if !inflation.is_empty() => issue_inflatable_asset(
+            ticker, name, terms, precision, supply, owners, network, inflation, created,
+        ),
+        "RGB20" => issue_fungible_asset(
+            ticker, name, terms, precision, supply, owners, network, created,
+        ),
+        "RGB21" if !collection.is_empty() => issue_uda_collection(
+            ticker,
+            name,
+            terms.terms,
+            precision,
+            supply,
+            seal,
+            network,
+            collection,
+            created,
+        ),
+        // The UDA schema allows a single owner per transfer, so fractional tokens are
+        // issued as a one-token collection, which can split the token fractions
+        "RGB21" if fractional => issue_uda_collection(
+            ticker,
+            name,
+            terms.terms,
+            precision,
+            supply,
+            seal,
+            network,
+            vec![meta.unwrap_or_default()],
+            created,
+        ),
         "RGB21" => issue_uda_asset(
             ticker,
             name,
-            description,
+            terms.terms,
             precision,
             supply,
             seal,
             network,
             meta,
+            created,
+        ),
+        "RGB25" => issue_collectible_asset(
+            name,
+            description,
+            terms.terms,
+            precision,
+            supply,
+            owners,
+            network,
+            meta,
+            created,
         ),
         _ => {
             return Err(IssueContractError::NoContractSupport(
@@ -101,14 +208,16 @@ where
 }
 
 /// RGB20 interface
+#[allow(clippy::too_many_arguments)]
 fn issue_fungible_asset(
     ticker: &str,
     name: &str,
-    description: &str,
+    terms: ContractData,
     precision: u8,
     supply: u64,
-    seal: &str,
+    owners: Vec<(BuilderSeal<GenesisSeal>, u64)>,
     network: &str,
+    created: Timestamp,
 ) -> Result<Contract, IssueError> {
     let iface = rgb20();
     let schema = nia_schema();
@@ -116,31 +225,144 @@ fn issue_fungible_asset(
 
     let ticker: &'static str = Box::leak(ticker.to_string().into_boxed_str());
     let name: &'static str = Box::leak(name.to_string().into_boxed_str());
-    let description: &'static str = Box::leak(description.to_string().into_boxed_str());
-    let created = Timestamp::now();
 
     let precision = Precision::try_from(precision).expect("invalid precision");
     let spec = DivisibleAssetSpec::new(ticker, name, precision);
-    let terms = RicardianContract::from_str(description).expect("invalid contract text");
-    let contract_data = ContractData { terms, media: None };
+
+    let mut contract = ContractBuilder::with(iface, schema, iimpl)
+        .expect("schema fails to implement RGB20 interface")
+        .set_chain(Chain::from_str(network).expect("invalid network"))
+        .add_global_state("spec", spec)
+        .expect("invalid spec")
+        .add_global_state("created", created)
+        .expect("invalid created")
+        .add_global_state("data", terms)
+        .expect("invalid contract text")
+        .add_global_state("issuedSupply", Amount::from(supply))
+        .expect("invalid issued supply");
 
     // Issuer State
-    let seal = ExplicitSeal::<Txid>::from_str(seal).expect("invalid seal definition");
-    let seal = GenesisSeal::from(seal);
+    for (seal, amount) in owners {
+        contract = contract
+            .add_fungible_state("assetOwner", seal, amount)
+            .expect("invalid asset amount");
+    }
+
+    let contract = contract
+        .issue_contract()
+        .expect("contract doesn't fit schema requirements");
+    Ok(contract)
+}
+
+/// RGB20 interface (inflatable)
+#[allow(clippy::too_many_arguments)]
+fn issue_inflatable_asset(
+    ticker: &str,
+    name: &str,
+    terms: ContractData,
+    precision: u8,
+    supply: u64,
+    owners: Vec<(BuilderSeal<GenesisSeal>, u64)>,
+    network: &str,
+    inflation: Vec<InflationAllowanceRequest>,
+    created: Timestamp,
+) -> Result<Contract, IssueError> {
+    let iface = rgb20();
+    let schema = ifa_schema();
+    let iimpl = ifa_rgb20();
+
+    let ticker: &'static str = Box::leak(ticker.to_string().into_boxed_str());
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+
+    let precision = Precision::try_from(precision).expect("invalid precision");
+    let spec = DivisibleAssetSpec::new(ticker, name, precision);
 
-    let contract = ContractBuilder::with(iface, schema, iimpl)
+    let mut contract = ContractBuilder::with(iface, schema, iimpl)
         .expect("schema fails to implement RGB20 interface")
         .set_chain(Chain::from_str(network).expect("invalid network"))
         .add_global_state("spec", spec)
         .expect("invalid spec")
         .add_global_state("created", created)
         .expect("invalid created")
+        .add_global_state("data", terms)
+        .expect("invalid contract text")
+        .add_global_state("issuedSupply", Amount::from(supply))
+        .expect("invalid issued supply");
+
+    // Issuer State
+    for (seal, amount) in owners {
+        contract = contract
+            .add_fungible_state("assetOwner", seal, amount)
+            .expect("invalid asset amount");
+    }
+
+    // Inflation Rights
+    for InflationAllowanceRequest { seal, amount } in inflation {
+        let seal = GenesisSeal::from(explicit_seal(&seal)?);
+        contract = contract
+            .add_fungible_state(RGB20_INFLATION_ASSIGNMENT, seal, amount)
+            .expect("invalid inflation allowance");
+    }
+
+    let contract = contract
+        .issue_contract()
+        .expect("contract doesn't fit schema requirements");
+    Ok(contract)
+}
+
+/// RGB25 interface
+#[allow(clippy::too_many_arguments)]
+fn issue_collectible_asset(
+    name: &str,
+    description: &str,
+    terms: RicardianContract,
+    precision: u8,
+    supply: u64,
+    owners: Vec<(BuilderSeal<GenesisSeal>, u64)>,
+    network: &str,
+    meta: Option<IssueMediaRequest>,
+    created: Timestamp,
+) -> Result<Contract, IssueError> {
+    let iface = rgb25();
+    let schema = cfa_schema();
+    let iimpl = cfa_rgb25();
+
+    let name = Name::from_str(name).expect("invalid name");
+    let details =
+        Details::from_str(description).map_err(|err| IssueError::WrongDetails(err.to_string()))?;
+    let precision = Precision::try_from(precision).expect("invalid precision");
+
+    // Media
+    let media = meta
+        .and_then(|media_data| media_data.media)
+        .map(|media_item| media_attachment(&media_item))
+        .transpose()?;
+    let contract_data = ContractData { terms, media };
+
+    let mut contract = ContractBuilder::with(iface, schema, iimpl)
+        .expect("schema fails to implement RGB25 interface")
+        .set_chain(Chain::from_str(network).expect("invalid network"))
+        .add_global_state("name", name)
+        .expect("invalid name")
+        .add_global_state("details", details)
+        .expect("invalid details")
+        .add_global_state("precision", precision)
+        .expect("invalid precision")
+        .add_global_state("created", created)
+        .expect("invalid created")
         .add_global_state("data", contract_data)
         .expect("invalid contract text")
         .add_global_state("issuedSupply", Amount::from(supply))
-        .expect("invalid issued supply")
-        .add_fungible_state("assetOwner", seal, supply)
-        .expect("invalid asset amount")
+        .expect("invalid issued supply");
+
+    // Issuer State
+    for (seal, amount) in owners {
+        contract = contract
+            .add_fungible_state("assetOwner", seal, amount)
+            .expect("invalid asset amount");
+    }
+
+    let contract = contract
         .issue_contract()
         .expect("contract doesn't fit schema requirements");
     Ok(contract)
@@ -152,6 +374,63 @@ pub enum IssueError {
     RgbError(#[from] BuilderError),
 
     HexError(#[from] hex::FromHexError),
+
+    /// The asset details are not valid (reason: {0})
+    WrongDetails(String),
+
+    /// The media digest must have 32 bytes (current: {0})
+    WrongDigest(usize),
+
+    /// The seal {0} is not valid
+    WrongSeal(String),
+
+    /// The beneficiaries amounts overflow the maximum supply
+    SupplyOverflow,
+}
+
+/// Token data of a RGB21 token, with the asset naming and the token media
+fn uda_token_data(
+    index: TokenIndex,
+    spec: &DivisibleAssetSpec,
+    media_data: IssueMediaRequest,
+) -> Result<TokenData, IssueError> {
+    // Preview
+    let preview = if let Some(media_preview) = media_data.preview {
+        let ty_preview: &'static str = Box::leak(media_preview.ty.to_string().into_boxed_str());
+        let preview = base64::decode(&media_preview.source).expect("invalid preview data");
+
+        Some(EmbeddedMedia {
+            ty: MediaType::with(ty_preview),
+            data: SmallBlob::try_from_iter::<Vec<u8>>(preview).expect("invalid preview data"),
+        })
+    } else {
+        None
+    };
+
+    // Media
+    let media = media_data
+        .media
+        .map(|media| media_attachment(&media))
+        .transpose()?;
+
+    // Attachments
+    let mut attachments = bmap![];
+    for (pos, attach) in media_data.attachments.iter().enumerate() {
+        attachments.insert(pos as u8, media_attachment(attach)?);
+    }
+
+    let attachments = Confined::from_collection_unsafe(attachments);
+    let naming = spec.naming.clone();
+    let token_data = TokenData {
+        index,
+        name: Some(naming.name),
+        ticker: Some(naming.ticker),
+        preview,
+        media,
+        attachments,
+        ..Default::default()
+    };
+    Ok(token_data)
 }
 
 /// RGB21 interface
@@ -159,12 +438,13 @@ pub enum IssueError {
 fn issue_uda_asset(
     ticker: &str,
     name: &str,
-    description: &str,
+    terms: RicardianContract,
     precision: u8,
     supply: u64,
     seal: &str,
     network: &str,
     meta: Option<IssueMediaRequest>,
+    created: Timestamp,
 ) -> Result<Contract, IssueError> {
     let iface = rgb21();
     let schema = uda_schema();
@@ -172,11 +452,8 @@ fn issue_uda_asset(
 
     let ticker: &'static str = Box::leak(ticker.to_string().into_boxed_str());
     let name: &'static str = Box::leak(name.to_string().into_boxed_str());
-    let description: &'static str = Box::leak(description.to_string().into_boxed_str());
     let precision = Precision::try_from(precision).expect("invalid precision");
     let spec = DivisibleAssetSpec::new(ticker, name, precision);
-    let terms = RicardianContract::from_str(description).expect("invalid terms");
-    let created = Timestamp::now();
     let fraction = OwnedFraction::from_inner(supply);
 
     let mut tokens_data = vec![];
@@ -185,68 +462,13 @@ fn issue_uda_asset(
     // Toke Data
     let token_index = TokenIndex::from_inner(0);
     if let Some(media_data) = meta {
-        // Preview
-        let preview = if let Some(media_preview) = media_data.preview {
-            let ty_preview: &'static str = Box::leak(media_preview.ty.to_string().into_boxed_str());
-            let preview = base64::decode(&media_preview.source).expect("invalid preview data");
-
-            Some(EmbeddedMedia {
-                ty: MediaType::with(ty_preview),
-                data: SmallBlob::try_from_iter::<Vec<u8>>(preview).expect("invalid preview data"),
-            })
-        } else {
-            None
-        };
-
-        // Media
-        let media = if let Some(media) = media_data.media {
-            let mut digest: [u8; 32] = [0; 32];
-            digest.copy_from_slice(&hex::decode(&media.source)?);
-            let ty: &'static str = Box::leak(media.ty.to_string().into_boxed_str());
-
-            Some(Attachment {
-                ty: MediaType::with(ty),
-                digest,
-            })
-        } else {
-            None
-        };
-
-        // Attachments
-        let mut attachments = bmap![];
-        for (index, attach) in media_data.attachments.iter().enumerate() {
-            let mut digest: [u8; 32] = [0; 32];
-            digest.copy_from_slice(&hex::decode(&attach.source)?);
-            let ty: &'static str = Box::leak(attach.ty.to_string().into_boxed_str());
-
-            attachments.insert(
-                index as u8,
-                Attachment {
-                    ty: MediaType::with(ty),
-                    digest,
-                },
-            );
-        }
-
-        let attachments = Confined::from_collection_unsafe(attachments);
-        let naming = spec.naming.clone();
-        let token_data = TokenData {
-            index: token_index,
-            name: Some(naming.name),
-            ticker: Some(naming.ticker),
-            preview,
-            media,
-            attachments,
-            ..Default::default()
-        };
-
+        let token_data = uda_token_data(token_index, &spec, media_data)?;
         let allocation = Allocation::with(token_index, fraction);
         tokens_data.push(token_data);
         allocations.push(allocation);
     }
 
-    let seal = ExplicitSeal::<Txid>::from_str(seal).expect("invalid seal definition");
-    let seal = GenesisSeal::from(seal);
+    let seal = GenesisSeal::from(explicit_seal(seal)?);
 
     let mut contract = ContractBuilder::with(iface, schema, iimpl)
         .expect("schema fails to implement RGB21 interface")
@@ -275,3 +497,100 @@ fn issue_uda_asset(
         .expect("contract doesn't fit schema requirements");
     Ok(contract)
 }
+
+/// RGB21 interface (collection)
+#[allow(clippy::too_many_arguments)]
+fn issue_uda_collection(
+    ticker: &str,
+    name: &str,
+    terms: RicardianContract,
+    precision: u8,
+    supply: u64,
+    seal: &str,
+    network: &str,
+    collection: Vec<IssueMediaRequest>,
+    created: Timestamp,
+) -> Result<Contract, IssueError> {
+    let iface = rgb21();
+    let schema = udc_schema();
+    let iimpl = udc_rgb21();
+
+    let ticker: &'static str = Box::leak(ticker.to_string().into_boxed_str());
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    let precision = Precision::try_from(precision).expect("invalid precision");
+    let spec = DivisibleAssetSpec::new(ticker, name, precision);
+    let fraction = OwnedFraction::from_inner(supply);
+
+    let seal = GenesisSeal::from(explicit_seal(seal)?);
+
+    let mut contract = ContractBuilder::with(iface, schema, iimpl)
+        .expect("schema fails to implement RGB21 interface")
+        .set_chain(Chain::from_str(network).expect("invalid network"))
+        .add_global_state("spec", spec.clone())
+        .expect("invalid spec")
+        .add_global_state("created", created)
+        .expect("invalid created")
+        .add_global_state("terms", terms)
+        .expect("invalid contract text");
+
+    // Each token is allocated on its own, so it can be transferred alone
+    for (index, media_data) in collection.into_iter().enumerate() {
+        let token_index = TokenIndex::from_inner(index as u32);
+        let token_data = uda_token_data(token_index, &spec, media_data)?;
+        contract = contract
+            .add_global_state("tokens", token_data)
+            .expect("invalid tokens")
+            .add_data_state("assetOwner", seal, Allocation::with(token_index, fraction))
+            .expect("invalid asset blob");
+    }
+
+    let contract = contract
+        .issue_contract()
+        .expect("contract doesn't fit schema requirements");
+    Ok(contract)
+}
+
+/// Parse a genesis seal, either blinded (utxob) or explicit (tapret1st/opret1st)
+fn genesis_seal(seal: &str) -> Result<BuilderSeal<GenesisSeal>, IssueError> {
+    if let Ok(secret) = SecretSeal::from_str(seal) {
+        return Ok(BuilderSeal::Concealed(secret));
+    }
+
+    Ok(BuilderSeal::Revealed(GenesisSeal::from(explicit_seal(
+        seal,
+    )?)))
+}
+
+/// Parse an explicit seal (tapret1st/opret1st)
+fn explicit_seal(seal: &str) -> Result<ExplicitSeal<Txid>, IssueError> {
+    ExplicitSeal::<Txid>::from_str(seal).map_err(|_| IssueError::WrongSeal(seal.to_string()))
+}
+
+/// Genesis owners of the supply (issuer seal or the list of beneficiaries)
+fn genesis_owners(
+    seal: &str,
+    supply: u64,
+    beneficiaries: Vec<IssueBeneficiaryRequest>,
+) -> Result<Vec<(BuilderSeal<GenesisSeal>, u64)>, IssueError> {
+    if beneficiaries.is_empty() {
+        return Ok(vec![(genesis_seal(seal)?, supply)]);
+    }
+
+    beneficiaries
+        .into_iter()
+        .map(|IssueBeneficiaryRequest { seal, amount }| Ok((genesis_seal(&seal)?, amount)))
+        .collect()
+}
+
+/// Attachment of a media (the media source is the hex digest)
+fn media_attachment(media: &MediaInfo) -> Result<Attachment, IssueError> {
+    let digest: [u8; 32] = hex::decode(&media.source)?
+        .try_into()
+        .map_err(|source: Vec<u8>| IssueError::WrongDigest(source.len()))?;
+    let ty: &'static str = Box::leak(media.ty.to_string().into_boxed_str());
+
+    Ok(Attachment {
+        ty: MediaType::with(ty),
+        digest,
+    })
+}
@@ -0,0 +1,128 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use rgbwallet::{Beneficiary, RgbInvoice};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rgb::{
+        prebuild::prebuild_extract_transfer,
+        structs::RgbExtractTransfer,
+        transfer::{transfer_invoice_amount, witness_address_vouts},
+    },
+    structs::InvoiceStatus,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct RgbInvoices {
+    /// Ledger entries by invoice
+    pub invoices: BTreeMap<String, RgbInvoiceEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RgbInvoiceEntry {
+    pub contract_id: String,
+    pub iface: String,
+    pub amount: u64,
+    pub expiry: Option<i64>,
+    /// Outpoint of the blinded seal (None for witness invoices)
+    pub outpoint: Option<String>,
+    pub payments: Vec<RgbInvoicePayment>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RgbInvoicePayment {
+    pub consig_id: String,
+    pub txid: String,
+    pub amount: u64,
+}
+
+impl RgbInvoiceEntry {
+    pub fn with(invoice: &RgbInvoice, iface: &str, amount: u64, outpoint: Option<String>) -> Self {
+        Self {
+            contract_id: invoice
+                .contract
+                .map(|contract_id| contract_id.to_string())
+                .unwrap_or_default(),
+            iface: iface.to_string(),
+            amount,
+            expiry: invoice.expiry,
+            outpoint,
+            payments: vec![],
+        }
+    }
+
+    pub fn paid(&self) -> u64 {
+        self.payments.iter().map(|payment| payment.amount).sum()
+    }
+
+    pub fn status(&self, utc: i64) -> InvoiceStatus {
+        let paid = self.paid();
+        if self.amount == 0 && paid > 0 {
+            // Open amount invoices are paid with any amount
+            InvoiceStatus::Paid
+        } else if paid > self.amount {
+            InvoiceStatus::Overpaid
+        } else if paid == self.amount {
+            InvoiceStatus::Paid
+        } else if paid > 0 {
+            InvoiceStatus::PartiallyPaid
+        } else if self.expiry.map(|expiry| expiry <= utc).unwrap_or_default() {
+            InvoiceStatus::Expired
+        } else {
+            InvoiceStatus::Open
+        }
+    }
+}
+
+/// Matches the accepted consignment to the ledger invoices by beneficiary.
+///
+/// The paid amount is the state this consignment assigns to the invoice seal, or to
+/// the anchor outputs paying the invoice address (`witness_scripts` holds the scripts
+/// of the witness outputs by index). Returns the invoices that received a payment.
+pub fn record_invoice_payments(
+    consignment: &str,
+    rgb_invoices: &mut RgbInvoices,
+    witness_scripts: &BTreeMap<u32, Vec<u8>>,
+) -> Vec<String> {
+    let RgbExtractTransfer {
+        consig_id,
+        contract_id,
+        txid,
+        transfer,
+        ..
+    } = match prebuild_extract_transfer(consignment) {
+        Ok(extracted) => extracted,
+        _ => return vec![],
+    };
+
+    let mut paid = vec![];
+    for (invoice, entry) in rgb_invoices.invoices.iter_mut() {
+        if entry.contract_id != contract_id
+            || entry.payments.iter().any(|x| x.consig_id == consig_id)
+        {
+            continue;
+        }
+
+        let rgb_invoice = match RgbInvoice::from_str(invoice) {
+            Ok(rgb_invoice) => rgb_invoice,
+            _ => continue,
+        };
+        let witness_vouts = match &rgb_invoice.beneficiary {
+            Beneficiary::WitnessUtxo(address) => witness_address_vouts(address, witness_scripts),
+            _ => vec![],
+        };
+        let amount = match transfer_invoice_amount(&transfer, &rgb_invoice, &witness_vouts) {
+            Some(amount) => amount,
+            None => continue,
+        };
+
+        entry.payments.push(RgbInvoicePayment {
+            consig_id: consig_id.clone(),
+            txid: txid.to_string(),
+            amount,
+        });
+        paid.push(invoice.to_owned());
+    }
+
+    paid
+}
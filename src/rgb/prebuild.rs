@@ -12,7 +12,7 @@ use rgbstd::{
     interface::TypedState,
     persistence::{Inventory, Stash, Stock},
 };
-use rgbwallet::RgbInvoice;
+use rgbwallet::{Beneficiary, RgbInvoice};
 use strict_encoding::tn;
 
 use crate::{
@@ -26,8 +26,10 @@ use crate::{
 };
 
 use crate::rgb::{
-    constants::{BITCOIN_DEFAULT_FETCH_LIMIT, RGB_DEFAULT_FETCH_LIMIT},
+    consignment::invoice_token_index,
+    constants::{BITCOIN_DEFAULT_FETCH_LIMIT, RGB_DEFAULT_FETCH_LIMIT, RGB_WITNESS_OUTPUT_AMOUNT},
     contract::export_contract,
+    exclude_reorged_allocations,
     fs::RgbPersistenceError,
     prefetch::prefetch_resolver_txs,
     prefetch::{
@@ -36,7 +38,7 @@ use crate::rgb::{
     psbt::estimate_fee,
     resolvers::ExplorerResolver,
     structs::AddressAmount,
-    structs::RgbExtractTransfer,
+    structs::{RgbExtractTransfer, RgbTransfersV2},
     swap::{get_public_offer, RgbBid, RgbOfferSwap},
     transfer::extract_transfer,
     wallet::sync_wallet,
@@ -52,6 +54,7 @@ pub async fn prebuild_transfer_asset(
     request: FullRgbTransferRequest,
     stock: &mut Stock,
     rgb_wallet: &mut RgbWallet,
+    rgb_transfers: &RgbTransfersV2,
     resolver: &mut ExplorerResolver,
 ) -> Result<
     (
@@ -85,6 +88,7 @@ pub async fn prebuild_transfer_asset(
         TransferError::Validation(errors)
     })?;
 
+    let token_index = invoice_token_index(&invoice);
     let target_amount = match invoice.owned_state {
         TypedState::Amount(target_amount) => target_amount,
         _ => {
@@ -112,6 +116,7 @@ pub async fn prebuild_transfer_asset(
     for contract_type in [
         AssetType::RGB20,
         AssetType::RGB21,
+        AssetType::RGB25,
         AssetType::Contract,
         AssetType::Bitcoin,
         AssetType::Change,
@@ -128,10 +133,10 @@ pub async fn prebuild_transfer_asset(
     let mut all_unspents = vec![];
 
     // Get All Assets UTXOs
-    let contract_index = if let "RGB20" = iface_name.as_str() {
-        AssetType::RGB20
-    } else {
-        AssetType::RGB21
+    let contract_index = match iface_name.as_str() {
+        "RGB20" => AssetType::RGB20,
+        "RGB25" => AssetType::RGB25,
+        _ => AssetType::RGB21,
     };
 
     let iface = stock
@@ -152,13 +157,19 @@ pub async fn prebuild_transfer_asset(
     .await;
     prefetch_resolver_allocations(contract_iface, resolver, true).await;
 
-    let contract = export_contract(contract_id, stock, resolver, &mut Some(rgb_wallet.clone()))
+    let mut contract = export_contract(contract_id, stock, resolver, &mut Some(rgb_wallet.clone()))
         .map_err(TransferError::Export)?;
+    exclude_reorged_allocations(&mut contract, rgb_transfers);
 
     let allocations: Vec<AllocationDetail> = contract
         .allocations
         .into_iter()
         .filter(|x| x.is_mine && !x.is_spent)
+        // Only the requested token of a collection is spent
+        .filter(|x| match (&x.value, token_index) {
+            (AllocationValue::UDA(position), Some(index)) => position.token_index == index,
+            _ => true,
+        })
         .collect();
 
     let asset_total: u64 = allocations
@@ -167,7 +178,7 @@ pub async fn prebuild_transfer_asset(
         .filter(|a| a.is_mine && !a.is_spent)
         .map(|a| match a.value {
             AllocationValue::Value(value) => value.to_owned(),
-            AllocationValue::UDA(_) => 1,
+            AllocationValue::UDA(position) => position.fraction,
         })
         .sum();
 
@@ -219,7 +230,11 @@ pub async fn prebuild_transfer_asset(
                     asset_total += alloc_value;
                 }
             }
-            AllocationValue::UDA(_) => {
+            AllocationValue::UDA(ref position) => {
+                if asset_total >= target_amount {
+                    break;
+                }
+
                 let input = PsbtInputRequest {
                     descriptor: universal_desc.clone(),
                     utxo: alloc.utxo.clone(),
@@ -243,12 +258,17 @@ pub async fn prebuild_transfer_asset(
                         })
                         .map(|x| x.amount)
                         .sum::<u64>();
+                    asset_total += position.fraction;
                 }
-                break;
             }
         }
     }
 
+    // Witness invoices are paid to an output created in the anchor transaction
+    if let Some(witness_change) = witness_output_change(&invoice) {
+        bitcoin_changes.push(witness_change);
+    }
+
     // Get All Bitcoin UTXOs
     let total_bitcoin_spend: u64 = bitcoin_changes
         .clone()
@@ -386,10 +406,20 @@ pub async fn prebuild_transfer_asset(
     Ok((assets_inputs, bitcoin_inputs, bitcoin_changes, fee_value))
 }
 
+/// Returns the bitcoin change paying the witness output of the invoice, if
+/// the beneficiary is a witness address.
+pub fn witness_output_change(invoice: &RgbInvoice) -> Option<String> {
+    match &invoice.beneficiary {
+        Beneficiary::WitnessUtxo(address) => Some(format!("{address}:{RGB_WITNESS_OUTPUT_AMOUNT}")),
+        _ => None,
+    }
+}
+
 pub async fn prebuild_seller_swap(
     request: RgbOfferRequest,
     stock: &mut Stock,
     rgb_wallet: &mut RgbWallet,
+    rgb_transfers: &RgbTransfersV2,
     resolver: &mut ExplorerResolver,
 ) -> Result<
     (
@@ -426,7 +456,8 @@ pub async fn prebuild_seller_swap(
         ..
     } = request;
 
-    let target_amount = ContractAmount::from(target_amount, boilerplate.precision);
+    let target_amount = ContractAmount::from(target_amount, boilerplate.precision)
+        .map_err(|op| RgbSwapError::WrongValue(op.to_string()))?;
     if target_amount.precision != boilerplate.precision {
         return Err(RgbSwapError::WrongPrecision(
             boilerplate.precision,
@@ -440,6 +471,7 @@ pub async fn prebuild_seller_swap(
     for contract_type in [
         AssetType::RGB20,
         AssetType::RGB21,
+        AssetType::RGB25,
         AssetType::Contract,
         AssetType::Bitcoin,
         AssetType::Change,
@@ -456,10 +488,10 @@ pub async fn prebuild_seller_swap(
     let mut all_unspents = vec![];
 
     // Get All Assets UTXOs
-    let contract_index = if let "RGB20" = iface_name.as_str() {
-        AssetType::RGB20
-    } else {
-        AssetType::RGB21
+    let contract_index = match iface_name.as_str() {
+        "RGB20" => AssetType::RGB20,
+        "RGB25" => AssetType::RGB25,
+        _ => AssetType::RGB21,
     };
 
     let iface = stock
@@ -480,8 +512,9 @@ pub async fn prebuild_seller_swap(
     .await;
     prefetch_resolver_allocations(contract_iface, resolver, true).await;
 
-    let contract = export_contract(contract_id, stock, resolver, &mut Some(rgb_wallet.clone()))
+    let mut contract = export_contract(contract_id, stock, resolver, &mut Some(rgb_wallet.clone()))
         .map_err(RgbSwapError::Export)?;
+    exclude_reorged_allocations(&mut contract, rgb_transfers);
 
     let allocations: Vec<AllocationDetail> = contract
         .allocations
@@ -495,7 +528,7 @@ pub async fn prebuild_seller_swap(
         .filter(|a| a.is_mine && !a.is_spent)
         .map(|a| match a.value {
             AllocationValue::Value(value) => value.to_owned(),
-            AllocationValue::UDA(_) => 1,
+            AllocationValue::UDA(position) => position.fraction,
         })
         .sum();
 
@@ -559,7 +592,11 @@ pub async fn prebuild_seller_swap(
                     asset_total += alloc_value;
                 }
             }
-            AllocationValue::UDA(_) => {
+            AllocationValue::UDA(ref position) => {
+                if asset_total >= target_amount {
+                    break;
+                }
+
                 let input = PsbtInputRequest {
                     descriptor: universal_desc.clone(),
                     utxo: alloc.utxo.clone(),
@@ -584,8 +621,8 @@ pub async fn prebuild_seller_swap(
                         })
                         .map(|x| x.amount)
                         .sum::<u64>();
+                    asset_total += position.fraction;
                 }
-                break;
             }
         }
     }
@@ -714,6 +751,7 @@ pub async fn prebuild_buyer_swap(
         AssetType::Change,
         AssetType::RGB20,
         AssetType::RGB21,
+        AssetType::RGB25,
     ];
     for derive_type in derive_indexes {
         let derive_index = derive_type.clone() as u32;
@@ -863,7 +901,8 @@ pub async fn prebuild_buyer_swap(
     } = offer;
 
     let bitcoin_utxos = bitcoin_inputs.clone().into_iter().map(|x| x.utxo).collect();
-    let bid_amount = ContractAmount::from(asset_amount, asset_precision);
+    let bid_amount = ContractAmount::from(asset_amount, asset_precision)
+        .map_err(|op| RgbSwapError::WrongValue(op.to_string()))?;
     if bid_amount.precision != asset_precision {
         return Err(RgbSwapError::WrongPrecision(
             asset_precision,
@@ -2,13 +2,16 @@ use std::collections::BTreeMap;
 
 use amplify::confinement::U32;
 use postcard::from_bytes;
+use rgbwallet::RgbTransport;
 use strict_encoding::StrictSerialize;
 
 use crate::proxy::{
-    proxy_consig_retrieve, proxy_consig_store, proxy_media_data_store, proxy_media_retrieve,
-    proxy_metadata_retrieve,
+    proxy_consig_retrieve, proxy_consig_retrieve_at, proxy_consig_store, proxy_consig_store_at,
+    proxy_media_data_store, proxy_media_retrieve, proxy_metadata_retrieve,
 };
 
+use crate::constants::RGB_PROXY_ENDPOINT;
+use crate::error;
 use crate::proxy::ProxyServerError;
 use crate::structs::{MediaEncode, MediaItemRequest};
 
@@ -28,27 +31,168 @@ pub enum ProxyError {
     SerializeRetrieve(String, String),
     /// Write '{0}' serialize causes error. {1}
     SerializeWrite(String, String),
+    /// Consignments cannot be delivered to the '{0}' transport yet
+    UnsupportedTransport(String),
 }
 
 pub async fn post_consignments(consignments: BTreeMap<String, String>) -> Result<(), ProxyError> {
     for (recipient_id, transfer) in consignments {
+        let consig_rq = consignment_request(recipient_id, transfer)?;
+        proxy_consig_store(consig_rq)
+            .await
+            .map_err(ProxyError::IO)?;
+    }
+
+    Ok(())
+}
+
+/// Base URLs of the RGB proxies announced by the invoice transports.
+///
+/// Only JSON-RPC and HTTP transports are reachable. WebSocket and Storm
+/// transports are rejected by `unsupported_transport`.
+pub fn transport_endpoints(transports: &[RgbTransport]) -> Vec<String> {
+    transports
+        .iter()
+        .filter_map(|transport| match transport {
+            RgbTransport::JsonRpc { tls, host } | RgbTransport::RestHttp { tls, host } => {
+                let scheme = if *tls { "https" } else { "http" };
+                let host = host.trim_end_matches('/').trim_end_matches("/json-rpc");
+                Some(format!("{scheme}://{host}"))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the first transport the consignments cannot be delivered to.
+///
+/// Delivery over WebSocket and Storm is not supported yet, so these invoices
+/// are refused instead of silently falling back to the out of band delivery.
+/// Nostr relays have no invoice transport, and are refused when the invoice is
+/// created (see `parse_invoice_transport`).
+pub fn unsupported_transport(transports: &[RgbTransport]) -> Option<String> {
+    transports
+        .iter()
+        .find(|transport| {
+            matches!(
+                transport,
+                RgbTransport::WebSockets { .. } | RgbTransport::Storm { .. }
+            )
+        })
+        .map(|transport| transport.to_string())
+}
+
+/// Delivers the consignments to every endpoint of the invoice transports
+pub async fn post_transport_consignments(
+    sk: &str,
+    transports: &[RgbTransport],
+    consignments: BTreeMap<String, String>,
+) -> Result<(), ProxyError> {
+    if let Some(transport) = unsupported_transport(transports) {
+        return Err(ProxyError::UnsupportedTransport(transport));
+    }
+
+    let endpoints = transport_endpoints(transports);
+    if endpoints.is_empty() {
+        return Ok(());
+    }
+
+    for (recipient_id, transfer) in consignments {
+        let mut delivered = false;
+        for endpoint in &endpoints {
+            let consig_rq = consignment_request(recipient_id.clone(), transfer.clone())?;
+            delivered |= proxy_consig_store_at(endpoint, sk, consig_rq).await.is_ok();
+        }
+
+        if !delivered {
+            return Err(ProxyError::IO(ProxyServerError::AllEndpointsFailed));
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks up the consignment of the recipient in the endpoints of the invoice transports
+///
+/// Unreachable endpoints are skipped, so one of them cannot stop the polling of the others.
+/// Invoices without endpoints are paid through the default RGB proxy.
+pub async fn get_transport_consignment(
+    sk: &str,
+    transports: &[RgbTransport],
+    recipient_id: &str,
+) -> Result<Option<(String, String)>, ProxyError> {
+    let endpoints = transport_endpoints(transports);
+    if endpoints.is_empty() {
+        let endpoint = RGB_PROXY_ENDPOINT.read().await.to_string();
+        let consignment = get_consignment(recipient_id).await?;
+        return Ok(consignment.map(|consignment| (endpoint, consignment)));
+    }
+
+    for endpoint in endpoints {
+        let resp = match proxy_consig_retrieve_at(&endpoint, sk, recipient_id).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                error!(format!("Cannot poll {endpoint} for {recipient_id}: {err}"));
+                continue;
+            }
+        };
+
+        if let Some(resp) = resp {
+            let bytes = base64::decode(&resp.result.consignment).map_err(|op| {
+                ProxyError::SerializeRetrieve("consignment.get".to_string(), op.to_string())
+            })?;
+
+            if !bytes.is_empty() {
+                return Ok(Some((endpoint, hex::encode(bytes))));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn consignment_request(
+    recipient_id: String,
+    transfer: String,
+) -> Result<RgbProxyConsigFileReq, ProxyError> {
+    let hashed_name = blake3::hash(recipient_id.as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    let (txid, transfer) =
+        extract_transfer(transfer).map_err(|op| ProxyError::WrongConsig(op.to_string()))?;
+    let bytes = transfer
+        .unbindle()
+        .to_strict_serialized::<U32>()
+        .map_err(|op| ProxyError::WrongConsig(op.to_string()))?;
+
+    Ok(RgbProxyConsigFileReq {
+        params: RgbProxyConsigUpload {
+            recipient_id,
+            txid: txid.to_string(),
+        },
+        bytes: bytes.to_vec(),
+        file_name: hashed_name,
+    })
+}
+
+pub async fn post_contract_consignments(
+    consignments: BTreeMap<String, String>,
+) -> Result<(), ProxyError> {
+    for (recipient_id, contract) in consignments {
         let hashed_name = blake3::hash(recipient_id.as_bytes())
             .to_hex()
             .to_lowercase();
 
-        let (txid, transfer) =
-            extract_transfer(transfer).map_err(|op| ProxyError::WrongConsig(op.to_string()))?;
-        let bytes = transfer
-            .unbindle()
-            .to_strict_serialized::<U32>()
-            .map_err(|op| ProxyError::WrongConsig(op.to_string()))?;
+        let bytes = hex::decode(contract).map_err(|op| ProxyError::WrongConsig(op.to_string()))?;
 
+        // Genesis state is not anchored in a witness transaction
         let consig_rq = RgbProxyConsigFileReq {
             params: RgbProxyConsigUpload {
                 recipient_id,
-                txid: txid.to_string(),
+                txid: String::new(),
             },
-            bytes: bytes.to_vec(),
+            bytes,
             file_name: hashed_name,
         };
 
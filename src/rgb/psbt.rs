@@ -112,6 +112,7 @@ pub fn create_psbt(
     for contract_type in [
         AssetType::RGB20,
         AssetType::RGB21,
+        AssetType::RGB25,
         AssetType::Contract,
         AssetType::Bitcoin,
         AssetType::Change,
@@ -231,6 +232,18 @@ pub fn set_tapret_output(psbt: Psbt, pos: u16) -> Result<Psbt, CreatePsbtError>
     Ok(psbt)
 }
 
+/// Fee paid by the PSBT, from the previous outputs of its inputs
+pub fn psbt_fee(psbt: &Psbt) -> u64 {
+    let inputs: u64 = psbt
+        .inputs
+        .iter()
+        .filter_map(|input| input.witness_utxo.as_ref())
+        .map(|prevout| prevout.value)
+        .sum();
+    let outputs: u64 = psbt.outputs.iter().map(|output| output.amount).sum();
+    inputs.saturating_sub(outputs)
+}
+
 pub fn extract_output_commit(psbt: Psbt) -> Result<(Outpoint, u64, Vec<u8>), DbcPsbtError> {
     let (index, output) = psbt
         .outputs
@@ -319,6 +332,34 @@ pub fn save_tap_commit(
     wallet.descr = RgbDescr::Tapret(tapret);
 }
 
+/// Drops the outputs of a witness transaction that will never be published
+pub fn remove_tap_commit_str(txid: &str, wallet: &mut RgbWallet) {
+    let txid = bp::Txid::from_str(txid).expect("invalid txid parse");
+
+    let descr = wallet.descr.clone();
+    let RgbDescr::Tapret(mut tapret) = descr;
+
+    let outputs: Vec<Utxo> = wallet
+        .utxos
+        .iter()
+        .filter(|utxo| utxo.outpoint.txid == txid)
+        .cloned()
+        .collect();
+    for utxo in outputs {
+        let DeriveInfo { terminal, tweak } = utxo.derivation.clone();
+        if let Some(tap_commit) = tweak {
+            if let Some(taprets) = tapret.taprets.get_mut(&terminal) {
+                taprets.remove(&tap_commit);
+                if taprets.is_empty() {
+                    tapret.taprets.remove(&terminal);
+                }
+            }
+        }
+        wallet.utxos.remove(&utxo);
+    }
+    wallet.descr = RgbDescr::Tapret(tapret);
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
 pub enum EstimateFeeError {
@@ -369,6 +410,7 @@ where
     for contract_type in [
         AssetType::RGB20,
         AssetType::RGB21,
+        AssetType::RGB25,
         AssetType::Contract,
         AssetType::Bitcoin,
         AssetType::Change,
@@ -0,0 +1,23 @@
+use std::collections::BTreeSet;
+
+use bitcoin_hashes::{sha256, Hash};
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+static RGB_RECEIVERS: Lazy<Mutex<BTreeSet<String>>> = Lazy::new(Default::default);
+
+fn receiver_key(sk: &str) -> String {
+    sha256::Hash::hash(sk.as_bytes()).to_string()
+}
+
+pub async fn start_receiver(sk: &str) -> bool {
+    RGB_RECEIVERS.lock().await.insert(receiver_key(sk))
+}
+
+pub async fn stop_receiver(sk: &str) -> bool {
+    RGB_RECEIVERS.lock().await.remove(&receiver_key(sk))
+}
+
+pub async fn is_receiving(sk: &str) -> bool {
+    RGB_RECEIVERS.lock().await.contains(&receiver_key(sk))
+}
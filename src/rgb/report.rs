@@ -0,0 +1,291 @@
+use amplify::hex::ToHex;
+use rgbstd::{
+    contract::OpId,
+    validation::{Failure, Info, Status, Validity, Warning},
+};
+
+use crate::structs::{RgbValidationItem, RgbValidationLevel, RgbValidationReport};
+
+/// Plain-language causes of the RGB validation items
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cause {
+    Unmined,
+    Schema,
+    Missing,
+    Anchor,
+    Seal,
+    State,
+    Script,
+    Invalid,
+    Unexpected,
+    Info,
+}
+
+impl Cause {
+    /// Returns the explanation and the remediation hint
+    fn describe(self) -> (String, String) {
+        let (explanation, hint) = match self {
+            Cause::Unmined => (
+                "anchor tx not mined yet or not found by the explorer",
+                "wait for the anchor transaction to be mined and try again",
+            ),
+            Cause::Schema => (
+                "unknown schema or the operation does not follow the contract schema",
+                "import the contract schema or ask the sender for a consignment of a supported contract",
+            ),
+            Cause::Missing => (
+                "the consignment references data that is not included",
+                "ask the sender to export the full consignment history",
+            ),
+            Cause::Anchor => (
+                "the anchor does not commit to the operation",
+                "ask the sender to create a new transfer, the witness transaction is not valid",
+            ),
+            Cause::Seal => (
+                "a seal is not closed or defined as expected",
+                "check the invoice seal and ask the sender to create a new transfer",
+            ),
+            Cause::State => (
+                "the operation state does not match the types defined by the schema",
+                "ask the sender to create a new transfer, this consignment cannot be accepted",
+            ),
+            Cause::Script => (
+                "the contract validation script rejected the operation",
+                "ask the sender to create a new transfer, this consignment cannot be accepted",
+            ),
+            Cause::Invalid => (
+                "the consignment is not valid",
+                "ask the sender to create a new transfer",
+            ),
+            Cause::Unexpected => (
+                "the consignment is valid, but contains unexpected data",
+                "review the consignment before accepting it",
+            ),
+            Cause::Info => (
+                "additional information about the consignment",
+                "no action required",
+            ),
+        };
+        (explanation.to_string(), hint.to_string())
+    }
+}
+
+/// What a validation item refers to, taken from the fields of its variant
+struct ItemDetails {
+    code: &'static str,
+    cause: Cause,
+    operation_id: Option<OpId>,
+    seal: Option<String>,
+    anchor_txid: Option<String>,
+}
+
+impl ItemDetails {
+    fn new(code: &'static str, cause: Cause) -> Self {
+        Self {
+            code,
+            cause,
+            operation_id: None,
+            seal: None,
+            anchor_txid: None,
+        }
+    }
+
+    fn operation(mut self, opid: OpId) -> Self {
+        self.operation_id = Some(opid);
+        self
+    }
+
+    fn seal(mut self, seal: String) -> Self {
+        self.seal = Some(seal);
+        self
+    }
+
+    fn anchor(mut self, txid: String) -> Self {
+        self.anchor_txid = Some(txid);
+        self
+    }
+
+    fn into_item(self, level: RgbValidationLevel, message: String) -> RgbValidationItem {
+        let (explanation, hint) = self.cause.describe();
+        RgbValidationItem {
+            level,
+            code: self.code.to_string(),
+            message,
+            explanation,
+            hint,
+            operation_id: self.operation_id.map(|opid| opid.to_string()),
+            seal: self.seal,
+            anchor_txid: self.anchor_txid,
+        }
+    }
+}
+
+fn failure_details(failure: &Failure) -> ItemDetails {
+    match failure {
+        Failure::SchemaMismatch { .. } => ItemDetails::new("SchemaMismatch", Cause::Schema),
+        Failure::SchemaUnknownExtensionType(opid, _) => {
+            ItemDetails::new("SchemaUnknownExtensionType", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaUnknownTransitionType(opid, _) => {
+            ItemDetails::new("SchemaUnknownTransitionType", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaUnknownGlobalStateType(opid, _) => {
+            ItemDetails::new("SchemaUnknownGlobalStateType", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaUnknownAssignmentType(opid, _) => {
+            ItemDetails::new("SchemaUnknownAssignmentType", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaUnknownValencyType(opid, _) => {
+            ItemDetails::new("SchemaUnknownValencyType", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaGlobalStateOccurrences(opid, _, _) => {
+            ItemDetails::new("SchemaGlobalStateOccurrences", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaInputOccurrences(opid, _, _) => {
+            ItemDetails::new("SchemaInputOccurrences", Cause::Schema).operation(*opid)
+        }
+        Failure::SchemaAssignmentOccurrences(opid, _, _) => {
+            ItemDetails::new("SchemaAssignmentOccurrences", Cause::Schema).operation(*opid)
+        }
+        Failure::OperationAbsent(opid) => {
+            ItemDetails::new("OperationAbsent", Cause::Missing).operation(*opid)
+        }
+        Failure::TransitionAbsent(opid) => {
+            ItemDetails::new("TransitionAbsent", Cause::Missing).operation(*opid)
+        }
+        Failure::ExtensionAbsent(opid) => {
+            ItemDetails::new("ExtensionAbsent", Cause::Missing).operation(*opid)
+        }
+        Failure::NoPrevState { opid, .. } => {
+            ItemDetails::new("NoPrevState", Cause::Missing).operation(*opid)
+        }
+        Failure::NoPrevOut(opid, prev_out) => ItemDetails::new("NoPrevOut", Cause::Missing)
+            .operation(*opid)
+            .seal(prev_out.to_string()),
+        Failure::BundleInvalid(_) => ItemDetails::new("BundleInvalid", Cause::Anchor),
+        Failure::TransitionNotAnchored(opid) => {
+            ItemDetails::new("TransitionNotAnchored", Cause::Anchor).operation(*opid)
+        }
+        Failure::TransitionNotInAnchor(opid, txid) => {
+            ItemDetails::new("TransitionNotInAnchor", Cause::Anchor)
+                .operation(*opid)
+                .anchor(txid.to_hex())
+        }
+        Failure::MpcInvalid(opid, txid) => ItemDetails::new("MpcInvalid", Cause::Anchor)
+            .operation(*opid)
+            .anchor(txid.to_hex()),
+        Failure::SealNoWitnessTx(txid) => {
+            ItemDetails::new("SealNoWitnessTx", Cause::Unmined).anchor(txid.to_hex())
+        }
+        Failure::ConfidentialSeal(opout) => ItemDetails::new("ConfidentialSeal", Cause::Seal)
+            .operation(opout.op)
+            .seal(opout.to_string()),
+        Failure::StateTypeMismatch { opid, .. } => {
+            ItemDetails::new("StateTypeMismatch", Cause::State).operation(*opid)
+        }
+        Failure::FungibleTypeMismatch { opid, .. } => {
+            ItemDetails::new("FungibleTypeMismatch", Cause::State).operation(*opid)
+        }
+        Failure::ScriptFailure(opid) => {
+            ItemDetails::new("ScriptFailure", Cause::Script).operation(*opid)
+        }
+        Failure::Custom(_) => ItemDetails::new("Custom", Cause::Invalid),
+        _ => ItemDetails::new("Failure", Cause::Invalid),
+    }
+}
+
+fn warning_details(warning: &Warning) -> ItemDetails {
+    match warning {
+        Warning::EndpointDuplication(opid, seal) => {
+            ItemDetails::new("EndpointDuplication", Cause::Unexpected)
+                .operation(*opid)
+                .seal(seal.to_string())
+        }
+        Warning::EndpointTransitionSealNotFound(opid, seal) => {
+            ItemDetails::new("EndpointTransitionSealNotFound", Cause::Seal)
+                .operation(*opid)
+                .seal(seal.to_string())
+        }
+        Warning::ExcessiveNode(opid) => {
+            ItemDetails::new("ExcessiveNode", Cause::Unexpected).operation(*opid)
+        }
+        Warning::EndpointTransactionMissed(txid) => {
+            ItemDetails::new("EndpointTransactionMissed", Cause::Unmined).anchor(txid.to_hex())
+        }
+        Warning::Custom(_) => ItemDetails::new("Custom", Cause::Unexpected),
+    }
+}
+
+fn info_details(_info: &Info) -> ItemDetails {
+    ItemDetails::new("Custom", Cause::Info)
+}
+
+/// Builds a human-readable report of the consignment validation status
+pub fn validation_report(status: &Status) -> RgbValidationReport {
+    let failures = status
+        .failures
+        .iter()
+        .map(|x| failure_details(x).into_item(RgbValidationLevel::Failure, x.to_string()));
+    let warnings = status
+        .warnings
+        .iter()
+        .map(|x| warning_details(x).into_item(RgbValidationLevel::Warning, x.to_string()));
+    let info = status
+        .info
+        .iter()
+        .map(|x| info_details(x).into_item(RgbValidationLevel::Info, x.to_string()));
+    let mut items: Vec<RgbValidationItem> = failures.chain(warnings).chain(info).collect();
+
+    for txid in status.unresolved_txids.iter() {
+        let txid = txid.to_hex();
+        if items
+            .iter()
+            .any(|item| item.anchor_txid.as_ref() == Some(&txid))
+        {
+            continue;
+        }
+        items.push(
+            ItemDetails::new("UnresolvedTransaction", Cause::Unmined)
+                .anchor(txid.clone())
+                .into_item(
+                    RgbValidationLevel::Failure,
+                    format!("transaction {txid} cannot be resolved"),
+                ),
+        );
+    }
+
+    let validity = status.validity();
+    let valid = validity == Validity::Valid;
+    if !valid
+        && !items
+            .iter()
+            .any(|item| item.level == RgbValidationLevel::Failure)
+    {
+        items.push(RgbValidationItem {
+            level: RgbValidationLevel::Failure,
+            code: format!("{validity:?}"),
+            message: format!("consignment validity is {validity:?}"),
+            explanation: "the consignment cannot be fully verified".to_string(),
+            hint: "wait for the anchor transactions to be mined and try again".to_string(),
+            operation_id: None,
+            seal: None,
+            anchor_txid: None,
+        });
+    }
+
+    RgbValidationReport {
+        validity: format!("{validity:?}"),
+        valid,
+        items,
+    }
+}
+
+/// Builds a report for a consignment rejected outside the validation
+pub fn rejection_report(message: String) -> RgbValidationReport {
+    RgbValidationReport {
+        validity: "Invalid".to_string(),
+        valid: false,
+        items: vec![ItemDetails::new("Rejected", Cause::Invalid)
+            .into_item(RgbValidationLevel::Failure, message)],
+    }
+}
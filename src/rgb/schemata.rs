@@ -0,0 +1,466 @@
+use std::collections::BTreeMap;
+
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use rgbstd::{
+    containers::Consignment,
+    contract::{OpId, Operation, Opout, Transition},
+    interface::{
+        rgb20, rgb21, rgb21::Allocation, rgb21_stl, IfaceImpl, NamedField, NamedType, VerNo,
+    },
+    schema::{
+        FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, Schema, Script, StateSchema,
+        SubSchema, TransitionSchema,
+    },
+    stl::{rgb_contract_stl, Amount, StandardTypes},
+    validation::ConsignmentApi,
+    vm::{AluScript, ContractOp, EntryPoint, RgbIsa},
+};
+use strict_encoding::{fname, tn, FieldName, StrictDeserialize, TypeName};
+use strict_types::{SemId, Ty};
+
+use crate::rgb::{
+    consignment::is_burn_seal,
+    constants::{
+        RGB20_BURNED_SUPPLY, RGB20_BURN_TRANSITION, RGB20_INFLATION_ASSIGNMENT,
+        RGB20_INFLATION_TRANSITION, RGB20_REPLACE_TRANSITION,
+    },
+};
+
+const GS_NOMINAL: u16 = 2000;
+const GS_CONTRACT: u16 = 2001;
+const GS_TIMESTAMP: u16 = 2002;
+const GS_ISSUED_SUPPLY: u16 = 2003;
+const GS_BURNED_SUPPLY: u16 = 2004;
+const GS_TOKENS: u16 = 2005;
+const OS_ASSET: u16 = 4000;
+const OS_INFLATION: u16 = 4001;
+const TS_TRANSFER: u16 = 10000;
+const TS_ISSUE: u16 = 10001;
+const TS_BURN: u16 = 10002;
+const TS_REPLACE: u16 = 10003;
+
+/// Inflatable Fungible Asset (IFA) schema.
+///
+/// Works as the NIA schema, but the genesis can assign inflation allowances
+/// to seals, and the owners of these seals can create new supply through the
+/// `Issue` transition, up to the allowance they spend. Asset owners can
+/// destroy supply through the `Burn` transition, which assigns the burned
+/// amount to an unspendable seal and records it in the `burnedSupply` state.
+/// Lost allocations are replaced through the `Replace` transition, which works
+/// as `Issue` but also records the new supply as burned.
+///
+/// The script checks the allowance and the assets with the sum of their
+/// pedersen commitments: `Transfer` and `Burn` conserve both, and `Issue` and
+/// `Replace` conserve the allowance, as the consumed allowance is assigned to
+/// an unspendable seal. Scripts of this RGB version only run the RGB ISA, which
+/// has no arithmetic nor comparison ops, so the issued and burned supply cannot
+/// be checked against the consumed state by the script. These rules are
+/// checked by `check_schema_rules`, which runs on every consignment before it
+/// enters the stash.
+pub fn ifa_schema() -> SubSchema {
+    let types = StandardTypes::with(rgb_contract_stl());
+
+    let code = [
+        RgbIsa::Contract(ContractOp::PcVs(OS_ASSET)),
+        RgbIsa::Contract(ContractOp::PcVs(OS_INFLATION)),
+    ];
+    let alu_lib = Lib::assemble(&code).expect("invalid IFA validation script");
+    let alu_id = alu_lib.id();
+
+    let issue_code = [RgbIsa::Contract(ContractOp::PcVs(OS_INFLATION))];
+    let issue_lib = Lib::assemble(&issue_code).expect("invalid IFA issue script");
+    let issue_id = issue_lib.id();
+
+    Schema {
+        ffv: zero!(),
+        subset_of: None,
+        type_system: types.type_system(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.DivisibleAssetSpec")),
+            GS_CONTRACT => GlobalStateSchema::once(types.get("RGBContract.ContractData")),
+            GS_TIMESTAMP => GlobalStateSchema::once(types.get("RGBContract.Timestamp")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_BURNED_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => StateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_INFLATION => StateSchema::Fungible(FungibleType::Unsigned64Bit),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: Ty::<SemId>::UNIT.id(None),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_CONTRACT => Occurrences::Once,
+                GS_TIMESTAMP => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_INFLATION => Occurrences::NoneOrMore,
+            },
+            valencies: none!(),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: Ty::<SemId>::UNIT.id(None),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::NoneOrMore,
+                    OS_INFLATION => Occurrences::NoneOrMore,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::NoneOrMore,
+                    OS_INFLATION => Occurrences::NoneOrMore,
+                },
+                valencies: none!(),
+            },
+            TS_ISSUE => TransitionSchema {
+                metadata: Ty::<SemId>::UNIT.id(None),
+                globals: tiny_bmap! {
+                    GS_ISSUED_SUPPLY => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::NoneOrMore,
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                },
+                valencies: none!(),
+            },
+            TS_BURN => TransitionSchema {
+                metadata: Ty::<SemId>::UNIT.id(None),
+                globals: tiny_bmap! {
+                    GS_BURNED_SUPPLY => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_INFLATION => Occurrences::NoneOrMore,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_INFLATION => Occurrences::NoneOrMore,
+                },
+                valencies: none!(),
+            },
+            TS_REPLACE => TransitionSchema {
+                metadata: Ty::<SemId>::UNIT.id(None),
+                globals: tiny_bmap! {
+                    GS_ISSUED_SUPPLY => Occurrences::Once,
+                    GS_BURNED_SUPPLY => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::NoneOrMore,
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                },
+                valencies: none!(),
+            },
+        },
+        script: Script::AluVM(AluScript {
+            libs: confined_bmap! { alu_id => alu_lib, issue_id => issue_lib },
+            entry_points: confined_bmap! {
+                EntryPoint::ValidateTransition(TS_TRANSFER) => LibSite::with(0, alu_id),
+                EntryPoint::ValidateTransition(TS_ISSUE) => LibSite::with(0, issue_id),
+                EntryPoint::ValidateTransition(TS_BURN) => LibSite::with(0, alu_id),
+                EntryPoint::ValidateTransition(TS_REPLACE) => LibSite::with(0, issue_id),
+            },
+        }),
+    }
+}
+
+/// Checks the rules of the bitmask schemata the scripts cannot express (see
+/// `ifa_schema` and `udc_schema`).
+///
+/// Consignments are validated with this check on accept, on import and on the
+/// reorg checks, so a transition breaking these rules never enters the stash.
+pub fn check_schema_rules<const TYPE: bool>(consignment: &Consignment<TYPE>) -> Result<(), String> {
+    if !ifa_supply_is_valid(consignment) {
+        return Err("issued or burned supply does not match the consumed state".to_string());
+    }
+    if !udc_tokens_are_conserved(consignment) {
+        return Err("assigned tokens do not match the spent tokens".to_string());
+    }
+    Ok(())
+}
+
+/// Checks the supply changes of each IFA transition of the consignment (see
+/// `ifa_transition_is_valid`)
+pub fn ifa_supply_is_valid<const TYPE: bool>(consignment: &Consignment<TYPE>) -> bool {
+    if consignment.genesis.schema_id != ifa_schema().schema_id() {
+        return true;
+    }
+
+    consignment_transitions(consignment)
+        .values()
+        .all(|transition| ifa_transition_is_valid(transition))
+}
+
+/// Checks the supply changes of an IFA transition:
+///
+/// - `Issue` and `Replace` issue the same supply as the allowance they consume;
+/// - `Burn` records the same `burnedSupply` as the assets it assigns to the
+///   unspendable seal, which never exceeds the spent inputs (`Burn` conserves the
+///   asset amounts);
+/// - `Replace` records the replaced supply as burned, so it does not change the
+///   circulating supply.
+pub fn ifa_transition_is_valid(transition: &Transition) -> bool {
+    let global_amount = |global_ty: u16| -> u64 {
+        transition
+            .globals
+            .iter()
+            .filter(|(ty, _)| **ty == global_ty)
+            .flat_map(|(_, values)| values.iter())
+            .filter_map(|value| Amount::from_strict_serialized(value.as_ref().to_owned()).ok())
+            .map(|amount| amount.value())
+            .sum()
+    };
+    let burned_amount = |assignment_ty: u16| -> u64 {
+        let mut amount = 0;
+        for (_, assigns) in transition
+            .assignments
+            .iter()
+            .filter(|(ty, _)| **ty == assignment_ty)
+        {
+            for idx in 0..assigns.to_confidential_seals().len() as u16 {
+                if !matches!(assigns.revealed_seal_at(idx), Ok(Some(seal)) if is_burn_seal(&seal)) {
+                    continue;
+                }
+                if let Ok(Some(reveal)) = assigns.as_fungible_state_at(idx) {
+                    amount += reveal.value.as_u64();
+                }
+            }
+        }
+        amount
+    };
+
+    match transition.transition_type {
+        TS_ISSUE => global_amount(GS_ISSUED_SUPPLY) == burned_amount(OS_INFLATION),
+        TS_REPLACE => {
+            let issued = global_amount(GS_ISSUED_SUPPLY);
+            issued == burned_amount(OS_INFLATION) && issued == global_amount(GS_BURNED_SUPPLY)
+        }
+        TS_BURN => global_amount(GS_BURNED_SUPPLY) == burned_amount(OS_ASSET),
+        _ => true,
+    }
+}
+
+/// RGB20 implementation of the IFA schema
+pub fn ifa_rgb20() -> IfaceImpl {
+    let schema = ifa_schema();
+    let iface = rgb20();
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        script: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_CONTRACT, fname!("data")),
+            NamedField::with(GS_TIMESTAMP, fname!("created")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_BURNED_SUPPLY, FieldName::from(RGB20_BURNED_SUPPLY)),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_INFLATION, FieldName::from(RGB20_INFLATION_ASSIGNMENT)),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedType::with(TS_TRANSFER, tn!("Transfer")),
+            NamedType::with(TS_ISSUE, TypeName::from(RGB20_INFLATION_TRANSITION)),
+            NamedType::with(TS_BURN, TypeName::from(RGB20_BURN_TRANSITION)),
+            NamedType::with(TS_REPLACE, TypeName::from(RGB20_REPLACE_TRANSITION)),
+        },
+        extensions: none!(),
+    }
+}
+
+/// Unique Digital Asset Collection (UDC) schema.
+///
+/// Works as the UDA schema, but the genesis can define many tokens, each one
+/// with its own `TokenData`, and every token is allocated as a separate
+/// state, so each one can be transferred on its own.
+///
+/// Tokens are owned by fractions, and a `Transfer` must assign the same
+/// fractions of each token it spends. The ownership is a structured state
+/// (`RGB21.Allocation`): scripts of this RGB version only run the RGB ISA,
+/// which loads structured state but has no arithmetic nor comparison ops, so
+/// a `Transfer` entry point cannot check the conservation of each token. It is
+/// checked by `check_schema_rules` instead (see `udc_tokens_are_conserved`),
+/// which rejects inflating transfers before they enter the stash.
+pub fn udc_schema() -> SubSchema {
+    let types = StandardTypes::with(rgb21_stl());
+
+    Schema {
+        ffv: zero!(),
+        subset_of: None,
+        type_system: types.type_system(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.DivisibleAssetSpec")),
+            GS_CONTRACT => GlobalStateSchema::once(types.get("RGBContract.RicardianContract")),
+            GS_TIMESTAMP => GlobalStateSchema::once(types.get("RGBContract.Timestamp")),
+            GS_TOKENS => GlobalStateSchema::many(types.get("RGB21.TokenData")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => StateSchema::Structured(types.get("RGB21.Allocation")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: Ty::<SemId>::UNIT.id(None),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_CONTRACT => Occurrences::Once,
+                GS_TIMESTAMP => Occurrences::Once,
+                GS_TOKENS => Occurrences::OnceOrMore,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: Ty::<SemId>::UNIT.id(None),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                },
+                valencies: none!(),
+            },
+        },
+        script: Script::AluVM(AluScript {
+            libs: none!(),
+            entry_points: none!(),
+        }),
+    }
+}
+
+/// Checks each UDC transition of the consignment (see
+/// `udc_transition_is_valid`)
+pub fn udc_tokens_are_conserved<const TYPE: bool>(consignment: &Consignment<TYPE>) -> bool {
+    if consignment.genesis.schema_id != udc_schema().schema_id() {
+        return true;
+    }
+
+    consignment_transitions(consignment)
+        .values()
+        .all(|transition| udc_transition_is_valid(consignment, transition))
+}
+
+/// Checks an UDC transition assigns the same fractions of each token it spends.
+///
+/// The spent state is taken from the consignment history, so it must be
+/// revealed, otherwise the conservation cannot be checked.
+pub fn udc_transition_is_valid<const TYPE: bool>(
+    consignment: &Consignment<TYPE>,
+    transition: &Transition,
+) -> bool {
+    let transitions = consignment_transitions(consignment);
+
+    let mut spent = BTreeMap::<u32, u64>::new();
+    for input in transition.inputs.iter() {
+        let Opout { op, ty, no } = input.prev_out;
+        if ty != OS_ASSET {
+            continue;
+        }
+
+        let state = if op == consignment.genesis.id() {
+            consignment
+                .genesis
+                .assignments
+                .get(&ty)
+                .and_then(|assigns| assigns.as_structured_state_at(no).ok().flatten())
+                .cloned()
+        } else {
+            transitions
+                .get(&op)
+                .and_then(|prev| prev.assignments.get(&ty))
+                .and_then(|assigns| assigns.as_structured_state_at(no).ok().flatten())
+                .cloned()
+        };
+        match state.and_then(|data| udc_allocation(data.as_ref())) {
+            Some(allocation) => {
+                *spent.entry(allocation.token_id().into_inner()).or_default() +=
+                    allocation.fraction().into_inner()
+            }
+            None => return false,
+        }
+    }
+
+    let mut assigned = BTreeMap::<u32, u64>::new();
+    if let Some(assigns) = transition.assignments.get(&OS_ASSET) {
+        for idx in 0..assigns.to_confidential_seals().len() as u16 {
+            let state = assigns.as_structured_state_at(idx).ok().flatten();
+            match state.and_then(|data| udc_allocation(data.as_ref())) {
+                Some(allocation) => {
+                    *assigned
+                        .entry(allocation.token_id().into_inner())
+                        .or_default() += allocation.fraction().into_inner()
+                }
+                None => return false,
+            }
+        }
+    }
+
+    spent == assigned
+}
+
+fn consignment_transitions<const TYPE: bool>(
+    consignment: &Consignment<TYPE>,
+) -> BTreeMap<OpId, &Transition> {
+    consignment
+        .bundles
+        .iter()
+        .flat_map(|anchored_bundle| {
+            consignment
+                .known_transitions_by_bundle_id(anchored_bundle.bundle.bundle_id())
+                .into_iter()
+                .flatten()
+        })
+        .map(|transition| (transition.id(), transition))
+        .collect()
+}
+
+fn udc_allocation(data: &[u8]) -> Option<Allocation> {
+    Allocation::from_strict_serialized(data.to_owned()).ok()
+}
+
+/// RGB21 implementation of the UDC schema
+pub fn udc_rgb21() -> IfaceImpl {
+    let schema = udc_schema();
+    let iface = rgb21();
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        script: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_CONTRACT, fname!("terms")),
+            NamedField::with(GS_TIMESTAMP, fname!("created")),
+            NamedField::with(GS_TOKENS, fname!("tokens")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedType::with(TS_TRANSFER, tn!("Transfer")),
+        },
+        extensions: none!(),
+    }
+}
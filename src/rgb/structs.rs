@@ -5,13 +5,15 @@ use bp::Txid;
 use core::fmt::Display;
 use rgb::{RgbWallet, TerminalPath};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     str::FromStr,
 };
 
 use rgbstd::containers::{Bindle, Transfer};
 use serde::{Deserialize, Serialize};
 
+use crate::structs::TransferState;
+
 pub type RgbAccountV0 = RgbAccount;
 pub type RgbTransferV0 = RgbTransfer;
 pub type RgbTransfersV0 = RgbTransfers;
@@ -45,6 +47,15 @@ impl FromStr for AddressAmount {
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ContractAmountError {
+    /// '{0}' is not a valid decimal amount
+    WrongAmount(String),
+    /// '{0}' has more decimals than the contract precision ({1})
+    WrongPrecision(String, u8),
+}
+
 pub struct ContractAmount {
     pub int: u64,
     pub fract: u64,
@@ -129,37 +140,15 @@ impl ContractAmount {
     ///
     /// ```
     /// // Define the initial value
-    /// let amount = ContractAmount::from("1.100");
+    /// let amount = ContractAmount::from("1.1".to_string(), 3)?;
     ///
     /// assert_eq!(amount.int, 1);
     /// assert_eq!(amount.fract, 100);
     /// assert_eq!(amount.to_value(), 1100);
     /// assert_eq!(amount.to_string(), "1.100");
     /// ```
-    pub fn from(value: String, precision: u8) -> Self {
-        let mut fract = 0;
-
-        let int = if value.contains('.') {
-            let parts = value.split('.');
-            let collection: Vec<&str> = parts.collect();
-
-            fract = collection[1].parse().unwrap();
-            let precision_repr = collection[1].len() as u8;
-            if precision_repr < precision {
-                let pow = 10_u64.pow((precision - precision_repr) as u32);
-                fract *= pow;
-            }
-
-            collection[0].parse().unwrap()
-        } else {
-            value.parse().unwrap()
-        };
-
-        ContractAmount {
-            int,
-            fract,
-            precision,
-        }
+    pub fn from(value: String, precision: u8) -> Result<Self, ContractAmountError> {
+        Self::parse(&value, Some(precision))
     }
 
     /// Convert a raw string representation of the
@@ -174,34 +163,58 @@ impl ContractAmount {
     ///
     /// ```
     /// // Define the initial value
-    /// let amount = ContractAmount::from_raw("1.100");
+    /// let amount = ContractAmount::from_raw("1.100".to_string())?;
     ///
     /// assert_eq!(amount.int, 1);
     /// assert_eq!(amount.fract, 100);
     /// assert_eq!(amount.to_value(), 1100);
     /// assert_eq!(amount.to_string(), "1.100");
     /// ```
-    pub fn from_raw(value: String) -> Self {
-        let mut fract = 0;
-        let mut precision = 0;
+    pub fn from_raw(value: String) -> Result<Self, ContractAmountError> {
+        Self::parse(&value, None)
+    }
 
-        let int = if value.contains('.') {
-            let parts = value.split('.');
-            let collection: Vec<&str> = parts.collect();
+    /// Parses a decimal amount. Without precision, it is the number of decimals.
+    fn parse(value: &str, precision: Option<u8>) -> Result<Self, ContractAmountError> {
+        let wrong_amount = || ContractAmountError::WrongAmount(value.to_string());
 
-            fract = collection[1].parse().unwrap();
-            precision = collection[1].len() as u8;
+        let (int, fract) = value.split_once('.').unwrap_or((value, ""));
+        let is_number = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if int.is_empty() || !is_number(int) || !is_number(fract) {
+            return Err(wrong_amount());
+        }
 
-            collection[0].parse().unwrap()
-        } else {
-            value.parse().unwrap()
-        };
+        let decimals = u8::try_from(fract.len()).map_err(|_| wrong_amount())?;
+        let precision = precision.unwrap_or(decimals);
+        if decimals > precision {
+            return Err(ContractAmountError::WrongPrecision(
+                value.to_string(),
+                precision,
+            ));
+        }
 
-        ContractAmount {
+        let int: u64 = int.parse().map_err(|_| wrong_amount())?;
+        let fract: u64 = match fract.is_empty() {
+            true => 0,
+            false => fract.parse().map_err(|_| wrong_amount())?,
+        };
+        let fract = 10_u64
+            .checked_pow((precision - decimals) as u32)
+            .and_then(|pow| fract.checked_mul(pow))
+            .ok_or_else(wrong_amount)?;
+
+        // The contract value must fit in u64
+        10_u64
+            .checked_pow(precision as u32)
+            .and_then(|pow| int.checked_mul(pow))
+            .and_then(|value| value.checked_add(fract))
+            .ok_or_else(wrong_amount)?;
+
+        Ok(ContractAmount {
             int,
             fract,
             precision,
-        }
+        })
     }
 
     pub fn to_value(self) -> u64 {
@@ -226,6 +239,8 @@ impl Display for ContractAmount {
 pub struct ContractBoilerplate {
     pub contract_id: String,
     pub iface_id: String,
+    pub ticker: String,
+    pub name: String,
     pub precision: u8,
 }
 
@@ -278,6 +293,135 @@ pub struct RgbTransfersV1 {
     pub transfers: BTreeMap<String, Vec<RgbTransferV1>>,
 }
 
+#[derive(
+    Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize, Default, Display,
+)]
+#[display(doc_comments)]
+
+pub struct RgbTransfersV2 {
+    pub transfers: BTreeMap<String, Vec<RgbTransferV1>>,
+    /// Lifecycle by consignment, kept while the transfer is pending or its anchor is watched
+    pub lifecycle: BTreeMap<String, RgbTransferLifecycle>,
+    /// Mined anchors of accepted transfers, rechecked until they are buried
+    pub anchors: BTreeMap<String, RgbTransferAnchor>,
+}
+
+impl RgbTransfersV2 {
+    /// Drops the lifecycle of the transfers which are neither pending nor watched
+    pub fn prune_lifecycle(&mut self) {
+        let pending: BTreeSet<&String> = self
+            .transfers
+            .values()
+            .flatten()
+            .map(|transfer| &transfer.consig_id)
+            .collect();
+        let anchors = &self.anchors;
+        self.lifecycle
+            .retain(|consig_id, _| pending.contains(consig_id) || anchors.contains_key(consig_id));
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Default)]
+pub struct RgbAirdrops {
+    /// Invoices waiting for the next batch, by contract
+    pub pending: BTreeMap<String, Vec<String>>,
+}
+
+impl RgbAirdrops {
+    /// Takes the pending invoices of the contract, which are sent first when the
+    /// airdrop is resumed.
+    ///
+    /// A new airdrop is refused while the contract has pending invoices (the
+    /// number of pending invoices is returned), so they are never overwritten.
+    pub fn take_pending(&mut self, contract_id: &str, resume: bool) -> Result<Vec<String>, usize> {
+        match self.pending.get(contract_id) {
+            Some(pending) if !resume => Err(pending.len()),
+            _ => Ok(self.pending.remove(contract_id).unwrap_or_default()),
+        }
+    }
+
+    /// Keeps the invoices of the contract which do not fit in the current batch
+    pub fn keep_pending(&mut self, contract_id: &str, pending: Vec<String>) {
+        if !pending.is_empty() {
+            self.pending.insert(contract_id.to_string(), pending);
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct RgbTransferAnchor {
+    pub contract_id: String,
+    pub tx_id: Txid,
+    /// Block height of the anchor when the transfer was accepted
+    pub height: u32,
+    /// Allocations assigned to our seals by the transfer
+    pub utxos: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize, Default)]
+pub struct RgbTransferLifecycle {
+    pub state: TransferState,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub error: Option<String>,
+    /// State changes with their timestamps
+    pub history: Vec<(TransferState, i64)>,
+    /// Fee of the witness transaction, when the wallet built it
+    pub fee: Option<u64>,
+}
+
+impl RgbTransferLifecycle {
+    pub fn new(utc: i64) -> Self {
+        Self {
+            state: TransferState::Draft,
+            created_at: utc,
+            updated_at: utc,
+            error: None,
+            history: vec![(TransferState::Draft, utc)],
+            fee: None,
+        }
+    }
+
+    /// Moves the transfer to the state, accepted transfers are not changed
+    pub fn move_to(&mut self, state: TransferState, utc: i64) {
+        if self.state == state || self.state == TransferState::Accepted {
+            return;
+        }
+
+        // New confirmations only update the depth
+        if !self.state.is(&state) {
+            self.history.push((state.clone(), utc));
+        }
+        if state != TransferState::Failed {
+            self.error = None;
+        }
+        self.state = state;
+        self.updated_at = utc;
+    }
+
+    pub fn fail(&mut self, error: String, utc: i64) {
+        self.move_to(TransferState::Failed, utc);
+        if self.state == TransferState::Failed {
+            self.error = Some(error);
+        }
+    }
+
+    /// Only the transfers the wallet did not broadcast yet can be cancelled
+    pub fn is_cancellable(&self) -> bool {
+        matches!(self.state, TransferState::Draft | TransferState::Signed)
+    }
+
+    /// The anchor left the chain, so accepted transfers are changed too
+    pub fn reorg(&mut self, error: String, utc: i64) {
+        if self.state != TransferState::Reorged {
+            self.history.push((TransferState::Reorged, utc));
+        }
+        self.state = TransferState::Reorged;
+        self.error = Some(error);
+        self.updated_at = utc;
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug, Display)]
 #[display("{consig_id}:{tx}")]
 pub struct RgbTransfer {
@@ -478,6 +622,18 @@ pub struct RgbProxyCarbonadoReq<T> {
     pub hex: String,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RgbProxyTransportConsigReq {
+    pub endpoint: String,
+    pub request: RgbProxyConsigCarbonadoReq,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RgbProxyTransportRetrieveReq {
+    pub endpoint: String,
+    pub recipient_id: String,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct RgbProxyUploadReq<T> {
     pub params: T,
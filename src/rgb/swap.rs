@@ -125,7 +125,7 @@ impl RgbOffer {
             .into_iter()
             .map(|a| match a.value {
                 crate::structs::AllocationValue::Value(amount) => amount,
-                crate::structs::AllocationValue::UDA(_) => 1,
+                crate::structs::AllocationValue::UDA(position) => position.fraction,
             })
             .sum();
 
@@ -1,30 +1,47 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+};
 
 use amplify::{
-    confinement::{Confined, U32},
+    confinement::{Confined, MediumOrdSet, U32},
     hex::ToHex,
+    Wrapper,
 };
-use bitcoin_30::psbt::Psbt as PSBT;
+use bitcoin_30::{psbt::Psbt as PSBT, Address, Network};
 use bitcoin_hashes::hex::FromHex;
-use bp::{seals::txout::CloseMethod, Chain, Txid};
+use bp::{
+    seals::txout::{CloseMethod, TxPtr},
+    Chain, ScriptPubkey, Txid,
+};
 use indexmap::IndexMap;
-use psbt::{serialize::Serialize, Psbt};
+use psbt::{serialize::Serialize, Psbt, PsbtVersion};
 use rgbstd::{
-    containers::{Bindle, Transfer},
-    contract::{ContractId, GraphSeal},
-    interface::TypedState,
+    containers::{Bindle, TerminalSeal, Transfer},
+    contract::{ContractId, GraphSeal, SecretSeal},
+    interface::{rgb21::Allocation, TypedState},
     persistence::{Inventory, Stash, Stock},
     resolvers::ResolveHeight,
     validation::{AnchoredBundle, ConsignmentApi, ResolveTx, Status},
 };
-use rgbwallet::{InvoiceParseError, RgbInvoice, RgbTransport};
+use rgbwallet::{Beneficiary, InvoiceParseError, RgbInvoice, RgbTransport};
 use seals::txout::ExplicitSeal;
-use strict_encoding::{StrictDeserialize, TypeName};
+use strict_encoding::{
+    StrictDecode, StrictDeserialize, StrictDumb, StrictEncode, StrictSerialize, StrictType,
+    TypeName,
+};
 
 use crate::rgb::{
-    consignment::{ConsignmentEx, NewTransferOptions},
+    consignment::{burn_seal, ConsignmentEx, NewTransferOptions},
+    constants::{
+        LIB_NAME_BITMASK, RGB20_BURN_TRANSITION, RGB_INVOICE_MAX_AMOUNT_PARAM,
+        RGB_INVOICE_MIN_AMOUNT_PARAM,
+    },
     prebuild::prebuild_extract_transfer,
+    report::{rejection_report, validation_report},
+    schemata::check_schema_rules,
 };
+use crate::structs::RgbValidationReport;
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
 #[display(doc_comments)]
@@ -35,6 +52,8 @@ pub enum NewInvoiceError {
     WrongContract(String),
     /// '{0}' is an invalid seal definition
     WrongSeal(String),
+    /// '{0}' is an invalid witness address for the invoice network
+    WrongAddress(String),
     /// Network cannot be decoded. {0}
     WrongNetwork(String),
     /// {0} is unspecified or wrong contract id
@@ -43,6 +62,8 @@ pub enum NewInvoiceError {
     EmptyContracts,
     /// Error saving secret seal: {0}
     StoreSeal(String),
+    /// Error removing secret seals: {0}
+    RemoveSeal(String),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
@@ -58,6 +79,10 @@ pub enum NewPaymentError {
     WrongPSBT(String),
     /// Consignmnet has not been completed. {0}
     NoPay(String),
+    /// The invoice has no amount, the payment amount is required
+    NoAmount,
+    /// The amount {0} is not allowed by the invoice
+    WrongAmount(u64),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
@@ -72,8 +97,8 @@ pub enum AcceptTransferError {
     WrongConsig(String),
     /// Network cannot be decoded. {0}
     WrongNetwork(String),
-    /// The Consignment is invalid. Details: {0:?}
-    InvalidConsig(Vec<String>),
+    /// The Consignment is invalid. Details: {0}
+    InvalidConsig(RgbValidationReport),
     /// The Consignment is invalid (Unexpected behavior on validation).
     Inconclusive,
 }
@@ -86,6 +111,7 @@ pub fn create_invoice(
     network: &str,
     params: HashMap<String, String>,
     stock: &mut Stock,
+    seal_secrets: &mut SealSecrets,
 ) -> Result<RgbInvoice, NewInvoiceError> {
     let ty =
         TypeName::from_str(iface).map_err(|_| NewInvoiceError::WrongIface(iface.to_string()))?;
@@ -131,13 +157,183 @@ pub fn create_invoice(
         expiry: None,
     };
 
-    stock
-        .store_seal_secret(seal)
-        .map_err(|op| NewInvoiceError::StoreSeal(op.to_string()))?;
+    seal_secrets.store(seal)?;
+
+    Ok(invoice)
+}
+
+/// Invoice paid to a witness output: the sender creates the output for `address`
+/// in the anchor transaction, so no seal secret is stored
+pub fn create_witness_invoice(
+    contract_id: &str,
+    iface: &str,
+    amount: u64,
+    address: &str,
+    network: &str,
+    params: HashMap<String, String>,
+    stock: &mut Stock,
+) -> Result<RgbInvoice, NewInvoiceError> {
+    let ty =
+        TypeName::from_str(iface).map_err(|_| NewInvoiceError::WrongIface(iface.to_string()))?;
+    let iface = stock
+        .iface_by_name(&ty)
+        .map_err(|_| NewInvoiceError::WrongIface(iface.to_string()))?;
+
+    let contract_id = ContractId::from_str(contract_id)
+        .map_err(|_| NewInvoiceError::NoContract(contract_id.to_string()))?;
+
+    let chain =
+        Chain::from_str(network).map_err(|op| NewInvoiceError::WrongNetwork(op.to_string()))?;
+    let address_network =
+        Network::from_str(network).map_err(|op| NewInvoiceError::WrongNetwork(op.to_string()))?;
+
+    let address = Address::from_str(address)
+        .map_err(|_| NewInvoiceError::WrongAddress(address.to_string()))?
+        .require_network(address_network)
+        .map_err(|_| NewInvoiceError::WrongAddress(address.to_string()))?;
+
+    // Query Params
+    let mut query = IndexMap::default();
+    for (k, v) in params {
+        query.insert(k, v);
+    }
+
+    let invoice = RgbInvoice {
+        transports: vec![RgbTransport::UnspecifiedMeans],
+        contract: Some(contract_id),
+        iface: Some(iface.name.clone()),
+        operation: None,
+        assignment: None,
+        beneficiary: Beneficiary::WitnessUtxo(address),
+        owned_state: TypedState::Amount(amount),
+        chain: Some(chain),
+        unknown_query: query,
+        expiry: None,
+    };
 
     Ok(invoice)
 }
 
+/// Invoice destroying `amount` of the contract, paid through the `Burn` transition
+/// (or a plain transfer, if the contract has no `Burn` transition)
+pub fn create_burn_invoice(
+    contract_id: &str,
+    iface: &str,
+    amount: u64,
+    network: &str,
+    stock: &mut Stock,
+) -> Result<RgbInvoice, NewInvoiceError> {
+    let ty =
+        TypeName::from_str(iface).map_err(|_| NewInvoiceError::WrongIface(iface.to_string()))?;
+    let iface = stock
+        .iface_by_name(&ty)
+        .map_err(|_| NewInvoiceError::WrongIface(iface.to_string()))?;
+
+    let contract_id = ContractId::from_str(contract_id)
+        .map_err(|_| NewInvoiceError::NoContract(contract_id.to_string()))?;
+
+    let chain =
+        Chain::from_str(network).map_err(|op| NewInvoiceError::WrongNetwork(op.to_string()))?;
+
+    let seal = burn_seal(CloseMethod::TapretFirst);
+    let invoice = RgbInvoice {
+        transports: vec![RgbTransport::UnspecifiedMeans],
+        contract: Some(contract_id),
+        iface: Some(iface.name.clone()),
+        operation: Some(TypeName::from(RGB20_BURN_TRANSITION)),
+        assignment: None,
+        beneficiary: seal.to_concealed_seal().into(),
+        owned_state: TypedState::Amount(amount),
+        chain: Some(chain),
+        unknown_query: none!(),
+        expiry: None,
+    };
+
+    Ok(invoice)
+}
+
+/// Amount range of the open amount invoices (amounts in contract units)
+pub fn invoice_amount_range(invoice: &RgbInvoice) -> (Option<u64>, Option<u64>) {
+    let bound = |param: &str| {
+        invoice
+            .unknown_query
+            .get(param)
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+    (
+        bound(RGB_INVOICE_MIN_AMOUNT_PARAM),
+        bound(RGB_INVOICE_MAX_AMOUNT_PARAM),
+    )
+}
+
+/// Checks the amount paid to an invoice against its constraints
+pub fn check_invoice_amount(invoice: &RgbInvoice, amount: u64) -> bool {
+    match invoice.owned_state {
+        TypedState::Void => {
+            let (min, max) = invoice_amount_range(invoice);
+            amount > 0
+                && min.map(|min| amount >= min).unwrap_or(true)
+                && max.map(|max| amount <= max).unwrap_or(true)
+        }
+        TypedState::Amount(value) => amount == value,
+        _ => true,
+    }
+}
+
+/// Sets the amount picked by the payer in an open amount invoice
+pub fn fill_invoice_amount(invoice: &str, amount: Option<u64>) -> Result<String, NewPaymentError> {
+    let mut invoice = RgbInvoice::from_str(invoice).map_err(NewPaymentError::WrongInvoice)?;
+    match (&invoice.owned_state, amount) {
+        (TypedState::Void, None) => return Err(NewPaymentError::NoAmount),
+        (_, Some(amount)) if !check_invoice_amount(&invoice, amount) => {
+            return Err(NewPaymentError::WrongAmount(amount))
+        }
+        (TypedState::Void, Some(amount)) => invoice.owned_state = TypedState::Amount(amount),
+        _ => {}
+    }
+    Ok(invoice.to_string())
+}
+
+/// State assigned to the seal by the consignment, UDAs are counted by fractions
+pub fn transfer_seal_amount(transfer: &Transfer, seal: &SecretSeal) -> u64 {
+    let bundle_ids: BTreeSet<_> = transfer
+        .terminals()
+        .map(|(bundle_id, _)| bundle_id)
+        .collect();
+
+    let mut amount = 0;
+    for bundle_id in bundle_ids {
+        for transition in transfer
+            .known_transitions_by_bundle_id(bundle_id)
+            .into_iter()
+            .flatten()
+        {
+            for (_, assigns) in transition.assignments.iter() {
+                for (idx, secret) in assigns.to_confidential_seals().iter().enumerate() {
+                    if secret != seal {
+                        continue;
+                    }
+                    let idx = idx as u16;
+                    if assigns.is_fungible() {
+                        if let Ok(Some(reveal)) = assigns.as_fungible_state_at(idx) {
+                            amount += reveal.value.as_u64();
+                        }
+                    } else if assigns.is_structured() {
+                        if let Ok(Some(reveal)) = assigns.as_structured_state_at(idx) {
+                            amount +=
+                                Allocation::from_strict_serialized(reveal.as_ref().to_owned())
+                                    .map(|allocation| allocation.fraction().into_inner())
+                                    .unwrap_or_default();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    amount
+}
+
 pub fn pay_invoice(
     invoice: String,
     psbt: String,
@@ -145,7 +341,11 @@ pub fn pay_invoice(
     stock: &mut Stock,
 ) -> Result<(Psbt, Vec<Bindle<Transfer>>), NewPaymentError> {
     let invoice = RgbInvoice::from_str(&invoice).map_err(NewPaymentError::WrongInvoice)?;
-    let psbt_file = Psbt::from_str(&psbt).map_err(|_| NewPaymentError::WrongHex)?;
+    let mut psbt_file = Psbt::from_str(&psbt).map_err(|_| NewPaymentError::WrongHex)?;
+
+    // RGB stock only processes PSBT v0, the original version is restored at the end
+    let psbt_version = psbt_file.psbt_version;
+    psbt_file.psbt_version = PsbtVersion::V0;
 
     let psbt = base64::decode(&base64::encode(&psbt_file.serialize()))
         .map_err(|err| NewPaymentError::WrongPSBT(err.to_string()))?;
@@ -157,32 +357,74 @@ pub fn pay_invoice(
         .process(invoice, &mut psbt_final, CloseMethod::TapretFirst, options)
         .map_err(|err| NewPaymentError::NoPay(err.to_string()))?;
 
-    let psbt_file = Psbt::from_str(&PSBT::serialize(&psbt_final).to_hex())
+    let mut psbt_file = Psbt::from_str(&PSBT::serialize(&psbt_final).to_hex())
         .map_err(|err| NewPaymentError::WrongPSBT(err.to_string()))?;
+    psbt_file.psbt_version = psbt_version;
     Ok((psbt_file, transfers))
 }
 
+/// Validates the consignment, and the amounts it pays to the open amount `invoices`
 pub fn validate_transfer<R: ResolveTx>(
     transfer: String,
+    invoices: &[RgbInvoice],
     resolver: &mut R,
 ) -> Result<(ContractId, Status), AcceptTransferError> {
-    let serialized = Vec::<u8>::from_hex(&transfer).map_err(|_| AcceptTransferError::WrongHex)?;
-    let confined = Confined::try_from_iter(serialized.iter().copied())
-        .map_err(|err| AcceptTransferError::WrongConsig(err.to_string()))?;
-    let transfer = Transfer::from_strict_serialized::<{ usize::MAX }>(confined)
-        .map_err(|err| AcceptTransferError::WrongConsig(err.to_string()))?;
+    let (anchor_txid, transfer) = extract_transfer(transfer)?;
+    let transfer = transfer.unbindle();
 
     let consig = transfer.clone().validate(resolver).map_err(|err| {
         if let Some(status) = err.into_validation_status() {
-            let mut messages = vec![];
-            messages.append(&mut status.warnings.into_iter().map(|x| x.to_string()).collect());
-            messages.append(&mut status.failures.into_iter().map(|x| x.to_string()).collect());
-            AcceptTransferError::InvalidConsig(messages)
+            AcceptTransferError::InvalidConsig(validation_report(&status))
         } else {
             AcceptTransferError::Inconclusive
         }
     })?;
 
+    check_schema_rules(&transfer)
+        .map_err(|err| AcceptTransferError::InvalidConsig(rejection_report(err)))?;
+
+    // Open amount invoices only accept the amounts allowed by their range
+    let open_invoices: Vec<&RgbInvoice> = invoices
+        .iter()
+        .filter(|invoice| matches!(invoice.owned_state, TypedState::Void))
+        .collect();
+
+    // Witness invoices are paid to the outputs of the anchor transaction
+    let witness_tx = if open_invoices
+        .iter()
+        .any(|invoice| matches!(invoice.beneficiary, Beneficiary::WitnessUtxo(_)))
+        && !transfer_witness_vouts(&transfer).is_empty()
+    {
+        let tx = resolver
+            .resolve_tx(anchor_txid)
+            .map_err(|_| AcceptTransferError::Inconclusive)?;
+        Some(tx)
+    } else {
+        None
+    };
+
+    for invoice in open_invoices {
+        let witness_vouts: Vec<u32> = match (&invoice.beneficiary, &witness_tx) {
+            (Beneficiary::WitnessUtxo(address), Some(tx)) => {
+                let script = ScriptPubkey::from(address.script_pubkey().to_bytes());
+                tx.outputs
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, output)| output.script_pubkey == script)
+                    .map(|(vout, _)| vout as u32)
+                    .collect()
+            }
+            _ => vec![],
+        };
+
+        let amount = transfer_invoice_amount(&transfer, invoice, &witness_vouts);
+        if amount.is_some_and(|amount| !check_invoice_amount(invoice, amount)) {
+            return Err(AcceptTransferError::InvalidConsig(rejection_report(
+                format!("the amount paid is not allowed by the invoice {invoice}"),
+            )));
+        }
+    }
+
     let status = consig.into_validation_status();
     Ok((transfer.contract_id(), status.unwrap_or_default()))
 }
@@ -201,24 +443,247 @@ where
         .map_err(|op| AcceptTransferError::WrongConsig(op.to_string()))?;
 
     let transfer = transfer_extracted.transfer.unbindle();
-    let consig = transfer.validate(resolver).map_err(|err| {
+    let consig = transfer.clone().validate(resolver).map_err(|err| {
         if let Some(status) = err.into_validation_status() {
-            let mut messages = vec![];
-            messages.append(&mut status.warnings.into_iter().map(|x| x.to_string()).collect());
-            messages.append(&mut status.failures.into_iter().map(|x| x.to_string()).collect());
-            AcceptTransferError::InvalidConsig(messages)
+            AcceptTransferError::InvalidConsig(validation_report(&status))
         } else {
             AcceptTransferError::Inconclusive
         }
     })?;
 
+    check_schema_rules(&transfer)
+        .map_err(|err| AcceptTransferError::InvalidConsig(rejection_report(err)))?;
+
     let bindle = Bindle::new(consig.clone());
     match stock.accept_transfer(consig, resolver, force) {
         Ok(_) => Ok(bindle),
-        Err(err) => Err(AcceptTransferError::InvalidConsig(vec![err.to_string()])),
+        Err(err) => Err(AcceptTransferError::InvalidConsig(rejection_report(
+            err.to_string(),
+        ))),
     }
 }
 
+/// Blinded seals receiving state in the consignment terminals
+pub fn transfer_secret_seals(transfer: &Transfer) -> BTreeSet<SecretSeal> {
+    transfer
+        .terminals()
+        .flat_map(|(_, terminal)| terminal.seals.iter())
+        .filter_map(|seal| match seal {
+            TerminalSeal::ConcealedUtxo(secret) => Some(*secret),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Outpoints of our seals receiving state in the consignment terminals
+pub fn transfer_seal_utxos(transfer: &Transfer, anchor_txid: Txid, stock: &Stock) -> Vec<String> {
+    let secret_seals = transfer_secret_seals(transfer);
+    stock
+        .seal_secrets()
+        .map(|secrets| {
+            secrets
+                .into_iter()
+                .filter(|secret| secret_seals.contains(&secret.to_concealed_seal()))
+                .map(|secret| {
+                    let txid = match secret.txid {
+                        TxPtr::Txid(txid) => txid,
+                        TxPtr::WitnessTx => anchor_txid,
+                    };
+                    format!("{txid}:{}", secret.vout.to_u32())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Witness outputs receiving state in the consignment terminals
+pub fn transfer_witness_vouts(transfer: &Transfer) -> BTreeSet<u32> {
+    transfer
+        .terminals()
+        .flat_map(|(_, terminal)| terminal.seals.iter())
+        .filter_map(|seal| match seal {
+            TerminalSeal::WitnessVout { vout, .. } => Some(vout.to_u32()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// State assigned by the consignment to the witness output `vout`
+pub fn transfer_witness_amount(transfer: &Transfer, vout: u32) -> u64 {
+    let seals: BTreeSet<SecretSeal> = transfer
+        .terminals()
+        .flat_map(|(_, terminal)| terminal.seals.iter())
+        .filter_map(|seal| match *seal {
+            TerminalSeal::WitnessVout {
+                method,
+                vout: witness_vout,
+                blinding,
+            } if witness_vout.to_u32() == vout => {
+                Some(GraphSeal::with_vout(method, witness_vout, blinding).to_concealed_seal())
+            }
+            _ => None,
+        })
+        .collect();
+
+    seals
+        .iter()
+        .map(|seal| transfer_seal_amount(transfer, seal))
+        .sum()
+}
+
+/// Witness outputs of the anchor transaction paying the invoice address
+/// (`witness_scripts` holds the scripts of the witness outputs by index)
+pub fn witness_address_vouts(
+    address: &Address,
+    witness_scripts: &BTreeMap<u32, Vec<u8>>,
+) -> Vec<u32> {
+    let script = address.script_pubkey();
+    witness_scripts
+        .iter()
+        .filter(|(_, witness)| witness.as_slice() == script.as_bytes())
+        .map(|(vout, _)| *vout)
+        .collect()
+}
+
+/// State paid by the consignment to the invoice, `None` if the invoice is not paid.
+///
+/// Witness invoices are paid to the `witness_vouts` of the anchor transaction
+/// paying the invoice address.
+pub fn transfer_invoice_amount(
+    transfer: &Transfer,
+    invoice: &RgbInvoice,
+    witness_vouts: &[u32],
+) -> Option<u64> {
+    match invoice.beneficiary {
+        Beneficiary::BlindedSeal(seal) => transfer_secret_seals(transfer)
+            .contains(&seal)
+            .then(|| transfer_seal_amount(transfer, &seal)),
+        Beneficiary::WitnessUtxo(_) => {
+            let terminal_vouts = transfer_witness_vouts(transfer);
+            let vouts: Vec<u32> = witness_vouts
+                .iter()
+                .copied()
+                .filter(|vout| terminal_vouts.contains(vout))
+                .collect();
+            if vouts.is_empty() {
+                return None;
+            }
+            Some(
+                vouts
+                    .into_iter()
+                    .map(|vout| transfer_witness_amount(transfer, vout))
+                    .sum(),
+            )
+        }
+    }
+}
+
+/// Seal secrets of the invoices waiting for a payment.
+///
+/// `Stock` cannot forget a seal secret, so the secrets of our invoices are kept
+/// here and copied to the stock only when a consignment paying the invoice is
+/// accepted (see `reveal_seal_secrets`). The secrets of the invoices which never
+/// receive a payment can then be dropped.
+#[derive(Clone, Debug, Default, StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_BITMASK)]
+pub struct SealSecrets(MediumOrdSet<GraphSeal>);
+
+impl StrictSerialize for SealSecrets {}
+impl StrictDeserialize for SealSecrets {}
+
+impl SealSecrets {
+    pub fn secrets(&self) -> BTreeSet<GraphSeal> {
+        self.0.iter().cloned().collect()
+    }
+
+    pub fn store(&mut self, seal: GraphSeal) -> Result<(), NewInvoiceError> {
+        let mut secrets = self.secrets();
+        secrets.insert(seal);
+        self.0 =
+            Confined::try_from(secrets).map_err(|op| NewInvoiceError::StoreSeal(op.to_string()))?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, seals: &BTreeSet<GraphSeal>) -> Result<(), NewInvoiceError> {
+        let secrets: BTreeSet<GraphSeal> = self.secrets().difference(seals).cloned().collect();
+        self.0 = Confined::try_from(secrets)
+            .map_err(|op| NewInvoiceError::RemoveSeal(op.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Copies to the stock the seal secrets of the consignment terminals, so the
+/// stock reveals the state received by our invoices when it accepts the transfer
+pub fn reveal_seal_secrets(
+    consignment: &str,
+    seal_secrets: &SealSecrets,
+    stock: &mut Stock,
+) -> Result<(), AcceptTransferError> {
+    let transfer = prebuild_extract_transfer(consignment)
+        .map_err(|op| AcceptTransferError::WrongConsig(op.to_string()))?
+        .transfer
+        .unbindle();
+
+    let secret_seals = transfer_secret_seals(&transfer);
+    for secret in seal_secrets.secrets() {
+        if secret_seals.contains(&secret.to_concealed_seal()) {
+            stock
+                .store_seal_secret(secret)
+                .map_err(|op| AcceptTransferError::WrongConsig(op.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Seal secrets of the invoices kept out of the stock
+pub fn invoice_seal_secrets(
+    invoices: &[RgbInvoice],
+    seal_secrets: &SealSecrets,
+) -> BTreeSet<GraphSeal> {
+    let blinded: BTreeSet<SecretSeal> = invoices
+        .iter()
+        .filter_map(|invoice| match invoice.beneficiary {
+            Beneficiary::BlindedSeal(seal) => Some(seal),
+            _ => None,
+        })
+        .collect();
+
+    seal_secrets
+        .secrets()
+        .into_iter()
+        .filter(|secret| blinded.contains(&secret.to_concealed_seal()))
+        .collect()
+}
+
+/// Seal secrets of the invoices which never received state
+pub fn unused_seal_secrets(
+    invoices: &[RgbInvoice],
+    seal_secrets: &SealSecrets,
+    stock: &mut Stock,
+) -> Result<BTreeSet<GraphSeal>, NewInvoiceError> {
+    let revealed = stock
+        .seal_secrets()
+        .map_err(|op| NewInvoiceError::RemoveSeal(op.to_string()))?;
+
+    Ok(invoice_seal_secrets(invoices, seal_secrets)
+        .difference(&revealed)
+        .cloned()
+        .collect())
+}
+
+/// Forgets the seal secrets of the invoices.
+///
+/// Secrets already copied to the stock are kept there, they are required to
+/// spend the received state.
+pub fn remove_seal_secrets(
+    invoices: &[RgbInvoice],
+    seal_secrets: &mut SealSecrets,
+) -> Result<(), NewInvoiceError> {
+    let seals = invoice_seal_secrets(invoices, seal_secrets);
+    seal_secrets.remove(&seals)
+}
+
 pub fn extract_transfer(transfer: String) -> Result<(Txid, Bindle<Transfer>), AcceptTransferError> {
     let serialized = Vec::<u8>::from_hex(&transfer).map_err(|_| AcceptTransferError::WrongHex)?;
     let confined = Confined::try_from_iter(serialized.iter().copied())
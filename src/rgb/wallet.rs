@@ -17,11 +17,13 @@ use rgbstd::{
     persistence::{Inventory, Stash, Stock},
     validation::ResolveTx,
 };
-use strict_encoding::tn;
+use strict_encoding::{tn, FieldName};
 
 use crate::{
     debug,
-    rgb::{resolvers::ResolveSpent, structs::AddressTerminal},
+    rgb::{
+        constants::RGB20_INFLATION_ASSIGNMENT, resolvers::ResolveSpent, structs::AddressTerminal,
+    },
     structs::{
         AllocationDetail, AllocationValue, TxStatus, UDAPosition, UtxoSpentStatus, WatcherDetail,
     },
@@ -327,6 +329,7 @@ where
     let iface_name = match iface_index {
         20 => "RGB20",
         21 => "RGB21",
+        25 => "RGB25",
         _ => "Contract",
     };
 
@@ -337,6 +340,11 @@ where
         if let Ok(contract) = stock.contract_iface(contract_id, iface.iface_id()) {
             let mut owners = vec![];
             for owned in &contract.iface.assignments {
+                // Inflation rights are not part of the balance
+                if owned.name == FieldName::from(RGB20_INFLATION_ASSIGNMENT) {
+                    continue;
+                }
+
                 if let Ok(allocations) = contract.fungible(owned.name.clone(), &None) {
                     for allocation in allocations {
                         let txid = bitcoin::Txid::from_str(&allocation.owner.txid.to_hex())
@@ -437,6 +445,7 @@ where
     let iface_name = match iface_index {
         20 => "RGB20",
         21 => "RGB21",
+        25 => "RGB25",
         _ => "Contract",
     };
     let iface = stock.iface_by_name(&tn!(iface_name))?;
@@ -445,6 +454,11 @@ where
     if let Ok(contract) = stock.contract_iface(contract_id, iface.iface_id()) {
         sync_wallet(iface_index, wallet, resolver);
         for owned in &contract.iface.assignments {
+            // Inflation rights are not part of the balance
+            if owned.name == FieldName::from(RGB20_INFLATION_ASSIGNMENT) {
+                continue;
+            }
+
             if let Ok(allocations) = contract.fungible(owned.name.clone(), &None) {
                 for allocation in allocations {
                     let txid = bitcoin::Txid::from_str(&allocation.owner.txid.to_hex())
@@ -1,7 +1,7 @@
 #![allow(deprecated)]
 use bp::Outpoint;
 use garde::Validate;
-use psbt::Psbt;
+use psbt::{Psbt, PsbtVersion};
 use rgb::MiningStatus;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
@@ -13,11 +13,13 @@ use rgbstd::interface::rgb21::Allocation as AllocationUDA;
 
 use crate::{
     rgb::{
+        constants::{RGB_REORG_SAFE_DEPTH, RGB_TERMS_MEDIA_TYPE},
         structs::MediaMetadata,
         swap::{PublicRgbBid, RgbBid, RgbOffer, RgbOfferSwap},
     },
     validators::{
-        verify_descriptor, verify_media_request, verify_rgb_invoice, verify_tapret_seal,
+        verify_beneficiary_seal, verify_descriptor, verify_invoice_beneficiary,
+        verify_invoice_transports, verify_media_request, verify_rgb_invoice, verify_tapret_seal,
         verify_terminal_path, RGBContext,
     },
 };
@@ -105,6 +107,78 @@ pub struct FundVaultDetails {
     pub fund_txid: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VaultTimelock {
+    /// Relative timelock (BIP-68), in blocks since the vault output confirmed
+    Relative(u16),
+    /// Absolute timelock (BIP-65), as block height
+    Absolute(u32),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InheritanceVaultDetails {
+    /// Vault public descriptor (owner key-path and heir recovery branch)
+    pub descriptor: String,
+    /// Current vault address
+    pub address: String,
+    /// Heir recovery timelock
+    pub timelock: VaultTimelock,
+    /// Funding or refresh transaction id
+    pub txid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentWatcherRequest {
+    /// Wallet Descriptor
+    pub descriptor: SecretString,
+    /// Wallet Change Descriptor
+    pub change_descriptor: Option<SecretString>,
+    /// Confirmation depths to notify (default: 1, 3 and 6)
+    #[serde(default = "default_payment_confirmations")]
+    pub confirmations: Vec<u32>,
+    /// Polling interval in seconds (default: 30, at least 10)
+    #[serde(default = "default_payment_interval")]
+    pub interval: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentUnwatchRequest {
+    /// Subscription id returned when the watcher was started
+    pub subscription: String,
+}
+
+fn default_payment_confirmations() -> Vec<u32> {
+    vec![1, 3, 6]
+}
+
+fn default_payment_interval() -> u64 {
+    30
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum PaymentEvent {
+    /// Watcher started, the subscription id is required to stop it
+    Subscribed { subscription: String },
+    /// Incoming payment seen in the mempool
+    Seen { txid: String, amount: u64 },
+    /// Incoming payment reached a confirmation threshold
+    Confirmed {
+        txid: String,
+        height: u32,
+        confirmations: u32,
+    },
+    /// Incoming payment was replaced by another transaction spending the same inputs
+    #[serde(rename_all = "camelCase")]
+    Replaced { txid: String, replaced_by: String },
+    /// Incoming payment left the mempool without confirmation
+    Dropped { txid: String },
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IssueAssetRequest {
@@ -117,7 +191,7 @@ pub struct IssueAssetRequest {
 #[derive(Validate)]
 #[garde(context(RGBContext))]
 pub struct IssueRequest {
-    /// The ticker of the asset
+    /// The ticker of the asset (ignored by RGB25)
     #[garde(ascii)]
     #[garde(length(min = 1, max = 8))]
     pub ticker: String,
@@ -129,7 +203,7 @@ pub struct IssueRequest {
     #[garde(ascii)]
     #[garde(length(min = u8::MIN.into(), max = u8::MAX.into()))]
     pub description: String,
-    /// Amount of the asset
+    /// Amount of the asset (RGB21: owned fractions of each token)
     #[garde(range(min = u64::MIN, max = u64::MAX))]
     pub supply: u64,
     /// Precision of the asset
@@ -142,9 +216,81 @@ pub struct IssueRequest {
     /// The name of the iface (ex: RGB20)
     #[garde(alphanumeric)]
     pub iface: String,
-    /// contract metadata (only RGB21/UDA)
+    /// contract metadata (only RGB21/UDA and RGB25/CFA)
     #[garde(custom(verify_media_request))]
     pub meta: Option<IssueMediaRequest>,
+    /// Inflation allowances (only RGB20). If present, the asset is issued as inflatable
+    #[garde(dive)]
+    #[serde(default)]
+    pub inflation: Vec<InflationAllowanceRequest>,
+    /// Collection tokens metadata (only RGB21). If present, the asset is issued as a
+    /// collection, with one token per item
+    #[garde(skip)]
+    #[serde(default)]
+    pub collection: Vec<IssueMediaRequest>,
+    /// Genesis beneficiaries (only RGB20 and RGB25). If present, the supply is
+    /// distributed between them instead of the issuer seal
+    #[garde(dive)]
+    #[serde(default)]
+    pub beneficiaries: Vec<IssueBeneficiaryRequest>,
+    /// Legal terms of the contract. If not present, the description is used as terms
+    #[garde(dive)]
+    #[serde(default)]
+    pub terms: Option<IssueTermsRequest>,
+    /// Issue the supply as the fractions of a single token, which can be split
+    /// on transfer (only RGB21). The fractions are checked by bitmask when
+    /// accepting the transfers, but not by the RGB scripts, so other wallets may
+    /// accept transfers creating new fractions.
+    #[garde(skip)]
+    #[serde(default)]
+    pub fractional: bool,
+    /// Creation date of the contract (unix timestamp). If not present, the
+    /// current date is used. The `created` returned by the preview issues the
+    /// same contract id
+    #[garde(skip)]
+    #[serde(default)]
+    pub created: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct IssueTermsRequest {
+    /// Text of the terms (ricardian contract)
+    #[garde(length(min = u8::MIN.into(), max = u16::MAX.into()))]
+    pub text: String,
+    /// Terms document attachment, with the document digest as source (only RGB20)
+    #[garde(dive)]
+    pub media: Option<MediaInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct InflationAllowanceRequest {
+    /// Seal of the inflation right owner
+    #[garde(ascii)]
+    #[garde(custom(verify_tapret_seal))]
+    pub seal: String,
+    /// Maximum amount of the asset that can be issued with this seal
+    #[garde(range(min = u64::MIN, max = u64::MAX))]
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct IssueBeneficiaryRequest {
+    /// Seal of the beneficiary (blinded utxob or explicit tapret1st)
+    #[garde(ascii)]
+    #[garde(custom(verify_beneficiary_seal))]
+    pub seal: String,
+    /// Amount of the asset assigned to this seal
+    #[garde(range(min = u64::MIN, max = u64::MAX))]
+    pub amount: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -197,6 +343,22 @@ impl From<ContractMediaDetail> for IssueMediaRequest {
     }
 }
 
+impl From<UDADetail> for IssueMediaRequest {
+    fn from(value: UDADetail) -> Self {
+        let preview = value.attach.map(|attach| MediaInfo {
+            ty: attach.ty,
+            source: attach.source,
+        });
+        let mut media = value.media.into_iter();
+
+        Self {
+            preview,
+            media: media.next(),
+            attachments: media.collect(),
+        }
+    }
+}
+
 impl From<MediaResponse> for IssueMediaRequest {
     fn from(value: MediaResponse) -> Self {
         let preview = value.preview.map(MediaInfo::from);
@@ -344,6 +506,10 @@ pub struct IssueResponse {
     pub genesis: GenesisFormats,
     /// contract metadata (only RGB21/UDA)
     pub meta: Option<ContractMediaDetail>,
+    /// Genesis consignments by beneficiary seal (only when the supply is distributed).
+    /// Every beneficiary receives the same contract, which holds the whole genesis
+    #[serde(default)]
+    pub consignments: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -387,6 +553,8 @@ pub enum AssetType {
     RGB20 = 20,
     #[serde(rename = "rgb21")]
     RGB21 = 21,
+    #[serde(rename = "rgb25")]
+    RGB25 = 25,
 }
 
 impl std::fmt::Display for AssetType {
@@ -451,6 +619,94 @@ pub struct ContractResponse {
     pub genesis: GenesisFormats,
     /// contract metadata (only RGB21/UDA)
     pub meta: Option<ContractMediaDetail>,
+    /// Remaining inflation allowance (only inflatable RGB20)
+    #[serde(default)]
+    pub allowance: Option<u64>,
+    /// Total issued supply, including the later issue and replace operations
+    #[serde(default)]
+    pub issued: u64,
+    /// Total burned supply
+    #[serde(default)]
+    pub burned: u64,
+    /// Circulating supply (issued - burned)
+    #[serde(default)]
+    pub circulating: u64,
+    /// All tokens of the contract, with their owners (only RGB21/UDA)
+    #[serde(default)]
+    pub tokens: Vec<UDADetail>,
+    /// Legal terms committed in the genesis
+    #[serde(default)]
+    pub terms: Option<ContractTermsDetail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractTermsDetail {
+    /// Text of the terms (ricardian contract)
+    pub text: String,
+    /// Media type of the terms document
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Digest of the terms document (attachment digest or sha256 of the text)
+    pub digest: String,
+}
+
+impl From<ContractTermsDetail> for IssueTermsRequest {
+    fn from(value: ContractTermsDetail) -> Self {
+        // Text terms are committed by themselves, without attachment
+        let media = if value.ty == RGB_TERMS_MEDIA_TYPE {
+            None
+        } else {
+            Some(MediaInfo {
+                ty: value.ty,
+                source: value.digest,
+            })
+        };
+
+        Self {
+            text: value.text,
+            media,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct VerifyTermsRequest {
+    /// The contract id
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 100))]
+    pub contract_id: String,
+    /// Terms document (text or base64 encoded file)
+    #[garde(length(min = 0, max = usize::MAX))]
+    pub document: String,
+    /// Terms document encoding
+    #[garde(skip)]
+    #[serde(default)]
+    pub encode: TermsEncode,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TermsEncode {
+    #[default]
+    #[serde(rename = "text")]
+    Text,
+    #[serde(rename = "base64")]
+    Base64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyTermsResponse {
+    /// The document matches the committed terms?
+    pub valid: bool,
+    /// Digest of the document
+    pub digest: String,
+    /// Digest committed in the genesis
+    pub expected: String,
 }
 
 #[deprecated(
@@ -523,9 +779,9 @@ pub struct UDADetail {
     pub description: String,
     /// The user contract balance
     pub balance: u64,
-    /// Media of the uda
+    /// Media of the uda (media first, then attachments)
     pub media: Vec<MediaInfo>,
-    /// Attach of the uda
+    /// Attach of the uda (preview)
     pub attach: Option<AttachInfo>,
     /// The contract allocations
     pub allocations: Vec<AllocationDetail>,
@@ -552,16 +808,34 @@ pub struct InvoiceRequest {
     #[garde(ascii)]
     #[garde(length(min = 0, max = 32))]
     pub iface: String,
-    /// Amount of the asset
+    /// Amount of the asset (empty for open amount invoices)
     #[garde(skip)]
     pub amount: String,
-    /// Blinded UTXO
+    /// Minimum amount of an open amount invoice
+    #[garde(skip)]
+    #[serde(default)]
+    pub min_amount: Option<String>,
+    /// Maximum amount of an open amount invoice
+    #[garde(skip)]
+    #[serde(default)]
+    pub max_amount: Option<String>,
+    /// Blinded UTXO or witness address (the sender creates the receiving output)
     #[garde(ascii)]
-    #[garde(custom(verify_tapret_seal))]
+    #[garde(custom(verify_invoice_beneficiary))]
     pub seal: String,
-    /// Query parameters
+    /// Query parameters (ex: `token` selects the token of a RGB21 collection)
     #[garde(skip)]
     pub params: HashMap<String, String>,
+    /// Endpoints where the payer delivers the consignment (ex: rpcs://proxy.host/json-rpc).
+    /// Only RGB proxies (JSON-RPC or HTTP) are supported: WebSocket, Storm and Nostr
+    /// relay endpoints are rejected
+    #[garde(custom(verify_invoice_transports))]
+    #[serde(default)]
+    pub transports: Vec<String>,
+    /// Expiration date of the invoice (unix timestamp)
+    #[garde(skip)]
+    #[serde(default)]
+    pub expire_at: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -585,6 +859,223 @@ pub struct InvoiceResponse {
     pub invoice: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct CancelInvoiceRequest {
+    /// RGB Invoice
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 512))]
+    #[garde(custom(verify_rgb_invoice))]
+    pub invoice: String,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum InvoiceStatus {
+    /// Waiting for payment
+    #[serde(rename = "open")]
+    Open,
+    /// Received less than the invoice amount
+    #[serde(rename = "partially_paid")]
+    PartiallyPaid,
+    /// Received the invoice amount
+    #[serde(rename = "paid")]
+    Paid,
+    /// Received more than the invoice amount
+    #[serde(rename = "overpaid")]
+    Overpaid,
+    /// Expired without payments
+    #[serde(rename = "expired")]
+    Expired,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct InvoicesRequest {
+    /// Only invoices of the contract
+    #[garde(skip)]
+    #[serde(default)]
+    pub contract_id: Option<String>,
+    /// Only invoices with the status
+    #[garde(skip)]
+    #[serde(default)]
+    pub status: Option<InvoiceStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoicesResponse {
+    /// Invoices of the ledger
+    pub invoices: Vec<InvoiceDetail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceDetail {
+    /// Invoice encoded in Baid58
+    pub invoice: String,
+    /// The contract id
+    pub contract_id: String,
+    /// The contract interface
+    pub iface: String,
+    /// Amount requested by the invoice
+    pub amount: u64,
+    /// Amount received by the invoice
+    pub paid: u64,
+    /// Payment status
+    pub status: InvoiceStatus,
+    /// Expiration date of the invoice (unix timestamp)
+    pub expiry: Option<i64>,
+    /// Lowest confirmation depth of the payments
+    pub confirmations: Option<u32>,
+    /// Accepted consignments paying the invoice
+    pub payments: Vec<InvoicePaymentDetail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoicePaymentDetail {
+    /// Consignment ID
+    pub consig_id: String,
+    /// Anchor transaction ID
+    pub txid: String,
+    /// Amount received
+    pub amount: u64,
+    /// Confirmation depth of the anchor transaction
+    pub confirmations: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceCleanupResponse {
+    /// Invoices removed from the account
+    pub invoices: Vec<String>,
+    /// Number of unused seal secrets removed from the stock
+    pub seal_secrets: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceConsignmentsResponse {
+    /// Consignments found in the transport endpoints of the invoices
+    pub consignments: Vec<InvoiceConsignment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceConsignment {
+    /// Invoice paid by the consignment
+    pub invoice: String,
+    /// Transport endpoint where the consignment was found
+    pub endpoint: String,
+    /// Consignment encoded in hexadecimal
+    pub consignment: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbReceiverRequest {
+    /// Confirmations of the anchor transaction required to accept (default: 1)
+    #[serde(default = "default_receiver_confirmations")]
+    pub min_confirmations: u32,
+    /// Polling interval in seconds (default: 60)
+    #[serde(default = "default_receiver_interval")]
+    pub interval: u64,
+}
+
+impl Default for RgbReceiverRequest {
+    fn default() -> Self {
+        Self {
+            min_confirmations: default_receiver_confirmations(),
+            interval: default_receiver_interval(),
+        }
+    }
+}
+
+fn default_receiver_confirmations() -> u32 {
+    1
+}
+
+fn default_receiver_interval() -> u64 {
+    60
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbReceiverResponse {
+    /// Consignments found for the invoices in this poll
+    pub transfers: Vec<RgbReceivedTransfer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbReceivedTransfer {
+    /// Invoice paid by the consignment
+    pub invoice: String,
+    /// Consignment ID
+    pub consig_id: String,
+    /// Contract ID
+    pub contract_id: String,
+    /// Lifecycle state after the poll (`accepted` once it is in the stock)
+    pub state: TransferState,
+    /// Reason of the failure
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbReorgRequest {
+    /// Confirmations after which an anchor is no longer rechecked (default: 6)
+    #[serde(default = "default_reorg_depth")]
+    pub depth: u32,
+}
+
+impl Default for RgbReorgRequest {
+    fn default() -> Self {
+        Self {
+            depth: default_reorg_depth(),
+        }
+    }
+}
+
+fn default_reorg_depth() -> u32 {
+    RGB_REORG_SAFE_DEPTH
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbReorgResponse {
+    /// Current chain tip
+    pub tip: Option<u32>,
+    /// Transfers affected by a reorg in this check
+    pub transfers: Vec<RgbReorgTransfer>,
+    /// Anchors still rechecked by the monitor
+    pub watching: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbReorgTransfer {
+    /// Consignment ID
+    pub consig_id: String,
+    /// Contract ID
+    pub contract_id: String,
+    /// Anchor transaction ID
+    pub txid: String,
+    /// Current block height of the anchor (if mined)
+    pub height: Option<u32>,
+    /// Lifecycle state after the check (`reorged` or `accepted` once re-validated)
+    pub state: TransferState,
+    /// Reason of the reorg
+    pub error: Option<String>,
+    /// Allocations excluded from the balance while the transfer is reorged
+    pub utxos: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[derive(Validate)]
@@ -613,6 +1104,10 @@ pub struct PsbtRequest {
     /// Allow RBF
     #[garde(skip)]
     pub rbf: bool,
+    /// PSBT Version (default: v0)
+    #[garde(skip)]
+    #[serde(default)]
+    pub psbt_version: PsbtFormat,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -673,6 +1168,34 @@ impl Default for PsbtFeeRequest {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PsbtFormat {
+    /// BIP-174: PSBT with global unsigned transaction
+    #[default]
+    V0,
+    /// BIP-370: PSBT with per-input and per-output transaction fields
+    V2,
+}
+
+impl From<PsbtVersion> for PsbtFormat {
+    fn from(value: PsbtVersion) -> Self {
+        match value {
+            PsbtVersion::V0 => PsbtFormat::V0,
+            PsbtVersion::V2 => PsbtFormat::V2,
+        }
+    }
+}
+
+impl From<PsbtFormat> for PsbtVersion {
+    fn from(value: PsbtFormat) -> Self {
+        match value {
+            PsbtFormat::V0 => PsbtVersion::V0,
+            PsbtFormat::V2 => PsbtVersion::V2,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 
@@ -688,7 +1211,7 @@ pub struct PsbtResponse {
 #[derive(Validate)]
 #[garde(context(RGBContext))]
 pub struct SignPsbtRequest {
-    /// PSBT encoded in Base64
+    /// PSBT encoded in Base64 (v0 or v2, the signed PSBT keeps the same version)
     #[garde(length(min = 0, max = usize::MAX))]
     pub psbt: String,
     /// Descriptors to Sign
@@ -716,6 +1239,63 @@ pub struct PublishPsbtRequest {
     pub psbt: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct ConvertPsbtRequest {
+    /// PSBT encoded in Base64 (v0 or v2)
+    #[garde(length(min = 0, max = usize::MAX))]
+    pub psbt: String,
+    /// Target PSBT Version
+    #[garde(skip)]
+    pub psbt_version: PsbtFormat,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedPsbtResponse {
+    /// PSBT encoded in the target version
+    pub psbt: String,
+    /// PSBT Version
+    pub psbt_version: PsbtFormat,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReservesProofResponse {
+    /// Proof of reserves PSBT (BIP-127), signed
+    pub psbt: String,
+    /// Challenge message
+    pub message: String,
+    /// Amount in the proof (in sats)
+    pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct VerifyReservesRequest {
+    /// Proof of reserves PSBT (BIP-127)
+    #[garde(length(min = 0, max = usize::MAX))]
+    pub psbt: String,
+    /// Challenge message
+    #[garde(length(min = 0, max = usize::MAX))]
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedReservesResponse {
+    /// Challenge message
+    pub message: String,
+    /// Proven amount of unspent outputs (in sats)
+    pub amount: u64,
+    /// Proven UTXOs
+    pub utxos: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PublishedPsbtResponse {
@@ -742,6 +1322,35 @@ pub struct RgbTransferRequest {
     /// Asset UTXO Terminal (ex. /0/0)
     #[garde(custom(verify_terminal_path))]
     pub terminal: String,
+    /// Amount picked by the payer (required by open amount invoices)
+    #[garde(skip)]
+    #[serde(default)]
+    pub amount: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct RgbBurnRequest {
+    /// The contract id
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 100))]
+    pub contract_id: String,
+    /// The contract interface
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 32))]
+    pub iface: String,
+    /// Amount of the asset to burn
+    #[garde(skip)]
+    pub amount: String,
+    /// PSBT File Information
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = usize::MAX))]
+    pub psbt: String,
+    /// Asset UTXO Terminal (ex. /0/0)
+    #[garde(custom(verify_terminal_path))]
+    pub terminal: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -772,6 +1381,10 @@ pub struct FullRgbTransferRequest {
     /// Bitcoin Change Addresses (format: {address}:{amount})
     #[garde(length(min = 0, max = 999))]
     pub bitcoin_changes: Vec<String>,
+    /// Amount picked by the payer (required by open amount invoices)
+    #[garde(skip)]
+    #[serde(default)]
+    pub amount: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -803,6 +1416,46 @@ pub struct SelfFullRgbTransferRequest {
     pub fee: Option<u64>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct RgbAirdropRequest {
+    /// The contract id
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 100))]
+    pub contract_id: String,
+    /// The contract interface
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 32))]
+    pub iface: String,
+    /// RGB Invoices of the recipients
+    #[garde(length(min = 0, max = 999))]
+    #[serde(default)]
+    pub invoices: Vec<String>,
+    /// RGB Invoices of the recipients in CSV format (format: {invoice},{amount}).
+    /// The amount column is optional, and fills open amount invoices
+    #[garde(skip)]
+    #[serde(default)]
+    pub csv: String,
+    /// Send the invoices pending from the previous batch of the contract first
+    #[garde(skip)]
+    #[serde(default)]
+    pub resume: bool,
+    /// Asset Descriptor
+    #[garde(custom(verify_descriptor))]
+    pub descriptor: SecretString,
+    /// Asset Terminal Change
+    #[garde(ascii)]
+    pub change_terminal: String,
+    /// Bitcoin Fee
+    #[garde(dive)]
+    pub fee: PsbtFeeRequest,
+    /// Bitcoin Change Addresses (format: {address}:{amount})
+    #[garde(length(min = 0, max = 999))]
+    pub bitcoin_changes: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[derive(Validate)]
@@ -900,6 +1553,43 @@ pub struct RgbReplaceResponse {
     pub txid: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbAirdropResponse {
+    /// Transfer of the batch (the PSBT must be signed and published)
+    pub transfer: RgbTransferResponse,
+    /// Report of each recipient
+    pub recipients: Vec<RgbAirdropRecipient>,
+    /// Invoices not included in this batch (send them in the next request)
+    pub pending: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbAirdropRecipient {
+    /// RGB Invoice
+    pub invoice: String,
+    /// Beneficiary of the invoice
+    pub beneficiary: String,
+    /// Amount of the asset
+    pub amount: u64,
+    /// Consignment ID (only sent recipients)
+    pub consig_id: Option<String>,
+    /// Recipient status
+    pub status: AirdropStatus,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum AirdropStatus {
+    /// Consignment posted to the proxy
+    #[serde(rename = "sent")]
+    Sent,
+    /// Waiting for the next batch
+    #[serde(rename = "pending")]
+    Pending,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RgbInternalTransferResponse {
@@ -944,6 +1634,114 @@ pub struct AcceptResponse {
     pub contract_id: String,
     /// Transfer accept status
     pub valid: bool,
+    /// Consignment validation report
+    #[serde(default)]
+    pub report: RgbValidationReport,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbTransferPreviewResponse {
+    /// Consignment ID
+    pub consig_id: String,
+    /// Contract ID
+    pub contract_id: String,
+    /// Contract interface (if the contract can be read)
+    pub iface: Option<String>,
+    /// Contract ticker (if the contract can be read)
+    pub ticker: Option<String>,
+    /// Contract name (if the contract can be read)
+    pub name: Option<String>,
+    /// Contract precision (if the contract can be read)
+    pub precision: Option<u8>,
+    /// The contract is not in the stock yet
+    pub new_contract: bool,
+    /// Anchor transaction ID
+    pub txid: String,
+    /// Anchor transaction status
+    pub tx_status: TxStatus,
+    /// Consignment validation status
+    pub valid: bool,
+    /// Consignment validation report
+    pub report: RgbValidationReport,
+    /// State assigned to our seals
+    pub allocations: Vec<RgbTransferPreviewAllocation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbValidationReport {
+    /// Consignment validity (ex. Valid, Invalid, UnresolvedTransactions)
+    pub validity: String,
+    /// The consignment can be accepted
+    pub valid: bool,
+    /// Failures, warnings and info items found by the validation
+    pub items: Vec<RgbValidationItem>,
+}
+
+impl std::fmt::Display for RgbValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.validity)?;
+        for item in &self.items {
+            write!(f, "; [{}] {} ({})", item.level, item.explanation, item.hint)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbValidationItem {
+    /// Severity of the item
+    pub level: RgbValidationLevel,
+    /// Name of the RGB validation item (ex. SealNoWitnessTx)
+    pub code: String,
+    /// Message of the RGB validation item
+    pub message: String,
+    /// Plain-language explanation
+    pub explanation: String,
+    /// What can be done about it
+    pub hint: String,
+    /// Offending operation ID
+    pub operation_id: Option<String>,
+    /// Offending seal
+    pub seal: Option<String>,
+    /// Offending anchor transaction ID
+    pub anchor_txid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RgbValidationLevel {
+    #[serde(rename = "failure")]
+    Failure,
+    #[serde(rename = "warning")]
+    Warning,
+    #[serde(rename = "info")]
+    Info,
+}
+
+impl std::fmt::Display for RgbValidationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RgbValidationLevel::Failure => write!(f, "failure"),
+            RgbValidationLevel::Warning => write!(f, "warning"),
+            RgbValidationLevel::Info => write!(f, "info"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbTransferPreviewAllocation {
+    /// Blinded seal
+    pub seal: String,
+    /// Amount received (fungible state)
+    pub amount: u64,
+    /// Amount received (with the contract precision)
+    pub decimal_amount: Option<String>,
+    /// Invoice paid by the seal (if any)
+    pub invoice: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -975,6 +1773,87 @@ pub struct RgbRemoveTransferRequest {
     pub consig_ids: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct RgbTransferStateRequest {
+    /// Consignment ID
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 100))]
+    pub consig_id: String,
+
+    /// New state (only `signed` and `broadcast` are set by the wallet)
+    #[garde(skip)]
+    pub state: TransferState,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbTransferStateResponse {
+    /// Consignment ID
+    pub consig_id: String,
+    /// Lifecycle state of the transfer
+    pub state: TransferState,
+    /// Date of the last state change (unix timestamp)
+    pub updated_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct RgbCancelTransferRequest {
+    /// Contract ID
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 100))]
+    pub contract_id: String,
+
+    /// Consignment ID
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 100))]
+    pub consig_id: String,
+
+    /// Spends the inputs again to ourselves (when the PSBT may have leaked)
+    #[garde(dive)]
+    #[serde(default)]
+    pub double_spend: Option<RgbDoubleSpendRequest>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Validate)]
+#[garde(context(RGBContext))]
+pub struct RgbDoubleSpendRequest {
+    /// RGB Invoice of the wallet itself
+    #[garde(ascii)]
+    #[garde(length(min = 0, max = 512))]
+    #[garde(custom(verify_rgb_invoice))]
+    pub rgb_invoice: String,
+    /// Asset Descriptor
+    #[garde(custom(verify_descriptor))]
+    pub descriptor: SecretString,
+    /// Asset Terminal Change
+    #[garde(ascii)]
+    pub change_terminal: String,
+    /// Bitcoin Fee (paid by the inputs of the cancelled transfer)
+    #[garde(dive)]
+    pub fee: PsbtFeeRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RgbCancelTransferResponse {
+    /// Contract ID
+    pub contract_id: String,
+    /// Consignment ID
+    pub consig_id: String,
+    /// UTXOs released by the cancelled transfer
+    pub utxos: Vec<String>,
+    /// Transfer spending the released UTXOs (must be signed and published)
+    pub double_spend: Option<RgbTransferResponse>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RgbTransferStatusResponse {
@@ -1235,6 +2114,82 @@ pub struct RgbTransferDetail {
     pub status: TxStatus,
     #[serde(rename = "type")]
     pub ty: TransferType,
+    /// Lifecycle state of the transfer
+    pub state: TransferState,
+    /// Creation date of the transfer (unix timestamp)
+    pub created_at: i64,
+    /// Date of the last state change (unix timestamp)
+    pub updated_at: i64,
+    /// Reason of the last failure
+    pub error: Option<String>,
+}
+
+#[derive(Eq, Ord, PartialEq, PartialOrd, Hash, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TransferState {
+    /// Consignment created, the witness transaction is not signed
+    #[default]
+    #[serde(rename = "draft")]
+    Draft,
+    /// Witness transaction signed
+    #[serde(rename = "signed")]
+    Signed,
+    /// Witness transaction broadcasted
+    #[serde(rename = "broadcast")]
+    Broadcast,
+    /// Witness transaction in the mempool
+    #[serde(rename = "mempool")]
+    Mempool,
+    /// Witness transaction confirmed (number of confirmations)
+    #[serde(rename = "confirmed")]
+    Confirmed(u32),
+    /// Consignment accepted in the stock
+    #[serde(rename = "accepted")]
+    Accepted,
+    /// Consignment rejected or not processed (see the error reason)
+    #[serde(rename = "failed")]
+    Failed,
+    /// Witness transaction replaced by fee (RBF)
+    #[serde(rename = "replaced")]
+    Replaced,
+    /// The invoice expired before the witness transaction was published
+    #[serde(rename = "expired")]
+    Expired,
+    /// Cancelled by the wallet before the witness transaction was published
+    #[serde(rename = "cancelled")]
+    Cancelled,
+    /// Witness transaction reorged out of the chain or double-spent
+    #[serde(rename = "reorged")]
+    Reorged,
+}
+
+impl TransferState {
+    /// Compares the states without the number of confirmations
+    pub fn is(&self, other: &TransferState) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Parses the state names (`confirmed` matches any number of confirmations)
+impl std::str::FromStr for TransferState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "draft" => Ok(TransferState::Draft),
+            "signed" => Ok(TransferState::Signed),
+            "broadcast" => Ok(TransferState::Broadcast),
+            "mempool" => Ok(TransferState::Mempool),
+            "confirmed" => Ok(TransferState::Confirmed(0)),
+            "accepted" => Ok(TransferState::Accepted),
+            "failed" => Ok(TransferState::Failed),
+            "replaced" => Ok(TransferState::Replaced),
+            "expired" => Ok(TransferState::Expired),
+            "cancelled" => Ok(TransferState::Cancelled),
+            "reorged" => Ok(TransferState::Reorged),
+            _ => Err(format!("'{s}' is an invalid transfer state")),
+        }
+    }
 }
 
 #[derive(
@@ -1269,8 +2224,47 @@ pub enum TransferType {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RgbInvoiceResponse {
+    /// The contract id
     pub contract_id: String,
+    /// Amount requested in contract units
     pub amount: u64,
+    /// Amount requested in decimal units (only for known contracts)
+    pub decimal_amount: Option<String>,
+    /// The contract interface
+    pub iface: Option<String>,
+    /// The contract operation
+    pub operation: Option<String>,
+    /// The contract assignment
+    pub assignment: Option<String>,
+    /// The beneficiary (blinded seal or witness address)
+    pub beneficiary: String,
+    /// The beneficiary type
+    pub beneficiary_type: InvoiceBeneficiaryType,
+    /// The chain of the invoice
+    pub chain: Option<String>,
+    /// Transport endpoints to deliver the consignment
+    pub transports: Vec<String>,
+    /// Expiration date of the invoice (unix timestamp)
+    pub expiry: Option<i64>,
+    /// Query parameters not known by the invoice format
+    pub unknown_query: BTreeMap<String, String>,
+    /// The contract is present in the local stock
+    pub known_contract: bool,
+    /// The contract ticker (only for known contracts)
+    pub ticker: Option<String>,
+    /// The contract precision (only for known contracts)
+    pub precision: Option<u8>,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum InvoiceBeneficiaryType {
+    /// Blinded UTXO seal
+    #[serde(rename = "blinded")]
+    Blinded,
+    /// Witness output address
+    #[serde(rename = "witness")]
+    Witness,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1341,6 +2335,10 @@ pub struct RgbOfferRequest {
     pub presig: bool,
     #[garde(skip)]
     pub expire_at: Option<i64>,
+    /// Seller PSBT Version (default: v0)
+    #[garde(skip)]
+    #[serde(default)]
+    pub psbt_version: PsbtFormat,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, Display, Default)]
@@ -2,11 +2,15 @@ use std::str::FromStr;
 
 use bp::{Chain, Txid};
 use miniscript_crate::Descriptor;
-use rgbwallet::RgbInvoice;
+use rgbstd::contract::SecretSeal;
+use rgbwallet::{RgbInvoice, RgbTransport};
 use seals::txout::ExplicitSeal;
 use wallet::hd::{DerivationAccount, DerivationSubpath, UnhardenedIndex};
 
-use crate::structs::{IssueMediaRequest, SecretString};
+use crate::{
+    rgb::proxy::unsupported_transport,
+    structs::{IssueMediaRequest, SecretString},
+};
 
 /// Errors happening during checking of requests to RGB operations
 #[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
@@ -31,6 +35,19 @@ pub enum RGBParamsError {
 
     /// Rgb Invoice cannot be decoded. {0}
     WrongInvoice(String),
+
+    /// '{0}' is not a supported invoice transport (ex: rpcs://proxy.host/json-rpc)
+    #[display(doc_comments)]
+    WrongTransport(String),
+
+    /// '{0}' transport is not supported yet, use a JSON-RPC or HTTP endpoint
+    #[display(doc_comments)]
+    UnsupportedTransport(String),
+
+    /// '{0}' is a Nostr relay, which cannot be announced in RGB invoices, use a
+    /// JSON-RPC or HTTP endpoint
+    #[display(doc_comments)]
+    NostrTransport(String),
 }
 
 #[derive(Debug, Display)]
@@ -75,6 +92,46 @@ pub fn verify_tapret_seal(value: &str, context: &RGBContext) -> garde::Result {
     Ok(())
 }
 
+pub fn verify_beneficiary_seal(value: &str, context: &RGBContext) -> garde::Result {
+    if SecretSeal::from_str(value).is_ok() {
+        return Ok(());
+    }
+    verify_tapret_seal(value, context)
+}
+
+pub fn verify_invoice_beneficiary(value: &str, context: &RGBContext) -> garde::Result {
+    if bitcoin_30::Address::from_str(value).is_ok() {
+        return Ok(());
+    }
+    verify_tapret_seal(value, context)
+}
+
+/// Parses an invoice transport the consignments can be delivered to.
+///
+/// Only RGB proxy endpoints (JSON-RPC or HTTP) are accepted. WebSocket and Storm
+/// transports have no delivery support yet, and Nostr relays have no RGB invoice
+/// transport, so all of them are rejected.
+pub fn parse_invoice_transport(value: &str) -> Result<RgbTransport, RGBParamsError> {
+    if value.starts_with("nostr:") {
+        return Err(RGBParamsError::NostrTransport(value.to_string()));
+    }
+
+    let transport = RgbTransport::from_str(value)
+        .map_err(|_| RGBParamsError::WrongTransport(value.to_string()))?;
+    if let Some(transport) = unsupported_transport(&[transport.clone()]) {
+        return Err(RGBParamsError::UnsupportedTransport(transport));
+    }
+
+    Ok(transport)
+}
+
+pub fn verify_invoice_transports(value: &[String], _context: &RGBContext) -> garde::Result {
+    for transport in value {
+        parse_invoice_transport(transport).map_err(|op| garde::Error::new(op.to_string()))?;
+    }
+    Ok(())
+}
+
 pub fn verify_terminal_path(value: &str, _context: &RGBContext) -> garde::Result {
     let resp = value
         .parse::<DerivationSubpath<UnhardenedIndex>>()
@@ -1,15 +1,19 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, str::FromStr};
 
 use js_sys::Promise;
 use serde::de::DeserializeOwned;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::{future_to_promise, JsFuture};
 
+use crate::constants::PAYMENT_WATCHER_MIN_INTERVAL;
 use crate::structs::{
-    AcceptRequest, FullRgbTransferRequest, ImportRequest, InvoiceRequest, IssueRequest,
-    MediaRequest, PsbtRequest, PublishPsbtRequest, ReIssueRequest, RgbBidRequest, RgbOfferRequest,
-    RgbRemoveTransferRequest, RgbSaveTransferRequest, RgbSwapRequest, RgbTransferRequest,
-    SecretString, SignPsbtRequest, WatcherRequest,
+    AcceptRequest, CancelInvoiceRequest, ConvertPsbtRequest, FullRgbTransferRequest, ImportRequest,
+    InvoiceRequest, InvoicesRequest, IssueRequest, MediaRequest, PaymentEvent,
+    PaymentWatcherRequest, PsbtRequest, PublishPsbtRequest, ReIssueRequest, RgbAirdropRequest,
+    RgbBidRequest, RgbBurnRequest, RgbCancelTransferRequest, RgbOfferRequest, RgbReceiverRequest,
+    RgbRemoveTransferRequest, RgbReorgRequest, RgbSaveTransferRequest, RgbSwapRequest,
+    RgbTransferRequest, RgbTransferStateRequest, SecretString, SignPsbtRequest, TransferState,
+    VaultTimelock, VerifyReservesRequest, VerifyTermsRequest, WatcherRequest,
 };
 
 pub fn set_panic_hook() {
@@ -369,6 +373,232 @@ pub mod bitcoin {
             }
         })
     }
+
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen]
+    pub fn create_inheritance_vault(
+        descriptor: String,
+        change_descriptor: String,
+        owner_descriptor: String,
+        heir_descriptor: String,
+        timelock: JsValue,
+        amount: u64,
+        fee_rate: Option<f32>,
+    ) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let timelock: VaultTimelock = serde_wasm_bindgen::from_value(timelock).unwrap();
+            match crate::bitcoin::create_inheritance_vault(
+                &SecretString(descriptor),
+                &SecretString(change_descriptor),
+                &SecretString(owner_descriptor),
+                &SecretString(heir_descriptor),
+                timelock,
+                amount,
+                fee_rate,
+            )
+            .await
+            {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn refresh_inheritance_vault(
+        owner_descriptor: String,
+        heir_descriptor: String,
+        timelock: JsValue,
+        new_timelock: JsValue,
+        fee_rate: Option<f32>,
+    ) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let timelock: VaultTimelock = serde_wasm_bindgen::from_value(timelock).unwrap();
+            let new_timelock: Option<VaultTimelock> =
+                serde_wasm_bindgen::from_value(new_timelock).unwrap();
+            match crate::bitcoin::refresh_inheritance_vault(
+                &SecretString(owner_descriptor),
+                &SecretString(heir_descriptor),
+                timelock,
+                new_timelock,
+                fee_rate,
+            )
+            .await
+            {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn sweep_inheritance_vault(
+        owner_descriptor: String,
+        heir_descriptor: String,
+        timelock: JsValue,
+        destination: String,
+        fee_rate: Option<f32>,
+    ) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let timelock: VaultTimelock = serde_wasm_bindgen::from_value(timelock).unwrap();
+            match crate::bitcoin::sweep_inheritance_vault(
+                &SecretString(owner_descriptor),
+                &SecretString(heir_descriptor),
+                timelock,
+                &destination,
+                fee_rate,
+            )
+            .await
+            {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    /// Starts a watcher of the descriptor and returns its subscription id
+    #[wasm_bindgen]
+    pub fn start_payment_watcher(descriptor: String) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let subscription =
+                crate::bitcoin::start_payment_watcher(&SecretString(descriptor)).await;
+
+            Ok(JsValue::from_string(
+                serde_json::to_string(&subscription).unwrap(),
+            ))
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn poll_payment_events(subscription: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let watcher_req: PaymentWatcherRequest =
+                serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::bitcoin::poll_payment_events(&subscription, &watcher_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    /// Polls the wallet until `unwatch_payments` is called, passing each payment event
+    /// (JSON encoded) to the callback. The first event carries the subscription id.
+    #[wasm_bindgen]
+    pub fn watch_payments(request: JsValue, callback: js_sys::Function) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let watcher_req: PaymentWatcherRequest =
+                serde_wasm_bindgen::from_value(request).unwrap();
+            let interval = i32::try_from(
+                watcher_req
+                    .interval
+                    .max(PAYMENT_WATCHER_MIN_INTERVAL)
+                    .saturating_mul(1000),
+            )
+            .unwrap_or(i32::MAX);
+
+            let subscription = crate::bitcoin::start_payment_watcher(&watcher_req.descriptor).await;
+            let event = PaymentEvent::Subscribed {
+                subscription: subscription.clone(),
+            };
+            let event = JsValue::from_string(serde_json::to_string(&event).unwrap());
+            callback.call1(&JsValue::NULL, &event)?;
+
+            while crate::bitcoin::is_watching_payments(&subscription).await {
+                match crate::bitcoin::poll_payment_events(&subscription, &watcher_req).await {
+                    Ok(events) => {
+                        for event in events {
+                            let event =
+                                JsValue::from_string(serde_json::to_string(&event).unwrap());
+                            callback.call1(&JsValue::NULL, &event)?;
+                        }
+                    }
+                    Err(err) => return Err(JsValue::from_string(err.to_string())),
+                }
+
+                JsFuture::from(crate::web::constants::sleep(interval)).await?;
+            }
+
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn unwatch_payments(subscription: String) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let result = crate::bitcoin::stop_payment_watcher(&subscription).await;
+
+            Ok(JsValue::from_string(
+                serde_json::to_string(&result).unwrap(),
+            ))
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn create_proof_of_reserves(
+        descriptor: String,
+        change_descriptor: Option<String>,
+        message: String,
+    ) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            match crate::bitcoin::create_proof_of_reserves(
+                &SecretString(descriptor),
+                change_descriptor.map(SecretString).as_ref(),
+                &message,
+            )
+            .await
+            {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn verify_proof_of_reserves(request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let reserves_req: VerifyReservesRequest =
+                serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::bitcoin::verify_proof_of_reserves(
+                &reserves_req.psbt,
+                &reserves_req.message,
+            )
+            .await
+            {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
 }
 
 pub mod rgb {
@@ -390,6 +620,21 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn preview_issue_contract(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let req: IssueRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::preview_issue_contract(&nostr_hex_sk, req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[wasm_bindgen]
     pub fn reissue_contract(nostr_hex_sk: String, request: JsValue) -> Promise {
@@ -466,6 +711,21 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn psbt_convert_file(_nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let psbt_req: ConvertPsbtRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::bitcoin::convert_psbt_file(psbt_req) {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[wasm_bindgen]
     pub fn psbt_sign_and_publish_file(_nostr_hex_sk: String, request: JsValue) -> Promise {
         set_panic_hook();
@@ -496,6 +756,66 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn inflate_asset(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let inflate_req: RgbTransferRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::inflate_asset(&nostr_hex_sk, inflate_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn replace_asset(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let replace_req: RgbTransferRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::replace_asset(&nostr_hex_sk, replace_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn burn_asset(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let burn_req: RgbBurnRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::burn_asset(&nostr_hex_sk, burn_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn airdrop_asset(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let airdrop_req: RgbAirdropRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::airdrop_asset(&nostr_hex_sk, airdrop_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[wasm_bindgen]
     pub fn full_transfer_asset(nostr_hex_sk: String, request: JsValue) -> Promise {
         set_panic_hook();
@@ -526,6 +846,126 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn preview_transfer(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let accept_req: AcceptRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::preview_transfer(&nostr_hex_sk, accept_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn inspect_transfer(request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let accept_req: AcceptRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::inspect_transfer(accept_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn cancel_invoice(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let cancel_req: CancelInvoiceRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::cancel_invoice(&nostr_hex_sk, cancel_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn cleanup_invoices(nostr_hex_sk: String) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            match crate::rgb::cleanup_invoices(&nostr_hex_sk).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn list_invoices(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let invoices_req: InvoicesRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::list_invoices(&nostr_hex_sk, invoices_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn receive_invoice_consignments(nostr_hex_sk: String) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            match crate::rgb::receive_invoice_consignments(&nostr_hex_sk).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn receive_transfers(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let receiver_req: RgbReceiverRequest =
+                serde_wasm_bindgen::from_value(request).unwrap_or_default();
+            match crate::rgb::receive_transfers(&nostr_hex_sk, receiver_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn check_reorgs(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let reorg_req: RgbReorgRequest =
+                serde_wasm_bindgen::from_value(request).unwrap_or_default();
+            match crate::rgb::check_reorgs(&nostr_hex_sk, reorg_req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[wasm_bindgen]
     pub fn verify_transfers(nostr_hex_sk: String) -> Promise {
         set_panic_hook();
@@ -554,6 +994,21 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn verify_contract_terms(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let req: VerifyTermsRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::verify_contract_terms(&nostr_hex_sk, req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     pub fn get_simple_contract(nostr_hex_sk: String, contract_id: String) -> Promise {
         set_panic_hook();
 
@@ -766,11 +1221,22 @@ pub mod rgb {
     }
 
     #[wasm_bindgen]
-    pub fn list_transfers(nostr_hex_sk: String, contract_id: String) -> Promise {
+    pub fn list_transfers(
+        nostr_hex_sk: String,
+        contract_id: String,
+        state: Option<String>,
+    ) -> Promise {
         set_panic_hook();
 
         future_to_promise(async move {
-            match crate::rgb::list_transfers(&nostr_hex_sk, contract_id).await {
+            let state = match state
+                .map(|state| TransferState::from_str(&state))
+                .transpose()
+            {
+                Ok(state) => state,
+                Err(err) => return Err(JsValue::from_string(err)),
+            };
+            match crate::rgb::list_transfers(&nostr_hex_sk, contract_id, state).await {
                 Ok(result) => Ok(JsValue::from_string(
                     serde_json::to_string(&result).unwrap(),
                 )),
@@ -794,6 +1260,21 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn update_transfer_state(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let req: RgbTransferStateRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::update_transfer_state(&nostr_hex_sk, req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[wasm_bindgen]
     pub fn remove_transfer(nostr_hex_sk: String, request: JsValue) -> Promise {
         set_panic_hook();
@@ -808,6 +1289,21 @@ pub mod rgb {
             }
         })
     }
+    #[wasm_bindgen]
+    pub fn cancel_transfer(nostr_hex_sk: String, request: JsValue) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            let req: RgbCancelTransferRequest = serde_wasm_bindgen::from_value(request).unwrap();
+            match crate::rgb::cancel_transfer(&nostr_hex_sk, req).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[wasm_bindgen]
     pub fn decode_invoice(invoice: String) -> Promise {
         set_panic_hook();
@@ -822,6 +1318,20 @@ pub mod rgb {
         })
     }
 
+    #[wasm_bindgen]
+    pub fn decode_full_invoice(nostr_hex_sk: String, invoice: String) -> Promise {
+        set_panic_hook();
+
+        future_to_promise(async move {
+            match crate::rgb::decode_full_invoice(&nostr_hex_sk, invoice).await {
+                Ok(result) => Ok(JsValue::from_string(
+                    serde_json::to_string(&result).unwrap(),
+                )),
+                Err(err) => Err(JsValue::from_string(err.to_string())),
+            }
+        })
+    }
+
     #[wasm_bindgen]
     pub fn create_offer(nostr_hex_sk: String, request: JsValue) -> Promise {
         set_panic_hook();
@@ -0,0 +1,200 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use bitcoin::{
+    hashes::{sha256d, Hash},
+    OutPoint, PackedLockTime, Transaction, TxIn, Txid,
+};
+use bitmask_core::{
+    bitcoin::{
+        is_watching_payments, record_payment_events, start_payment_watcher, stop_payment_watcher,
+    },
+    structs::{BlockTime, PaymentEvent, SecretString, TransactionDetails},
+};
+
+const CONFIRMATIONS: [u32; 3] = [1, 3, 6];
+
+fn txid(seed: &str) -> Txid {
+    Txid::from_hash(sha256d::Hash::hash(seed.as_bytes()))
+}
+
+fn payment(seed: &str, input: &str, height: Option<u32>) -> TransactionDetails {
+    let transaction = Transaction {
+        version: 2,
+        lock_time: PackedLockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(txid(input), 0),
+            ..Default::default()
+        }],
+        output: vec![],
+    };
+    TransactionDetails {
+        transaction: Some(transaction),
+        txid: txid(seed),
+        received: 1000,
+        sent: 0,
+        fee: Some(100),
+        confirmation_time: height.map(|height| BlockTime {
+            height,
+            timestamp: 0,
+        }),
+    }
+}
+
+#[tokio::test]
+async fn payment_watcher_seen_and_confirmed() -> anyhow::Result<()> {
+    let descriptor = SecretString("payment_watcher_seen_and_confirmed".to_owned());
+    let subscription = start_payment_watcher(&descriptor).await;
+
+    // The first poll only records the wallet history
+    let history = vec![payment("old", "old_input", Some(90))];
+    let events = record_payment_events(
+        &subscription,
+        &descriptor,
+        history.clone(),
+        100,
+        &CONFIRMATIONS,
+    )
+    .await?;
+    assert!(events.is_empty());
+
+    let mut mempool = history.clone();
+    mempool.push(payment("new", "new_input", None));
+    let events =
+        record_payment_events(&subscription, &descriptor, mempool, 100, &CONFIRMATIONS).await?;
+    assert_eq!(
+        events,
+        vec![PaymentEvent::Seen {
+            txid: txid("new").to_string(),
+            amount: 1000,
+        }]
+    );
+
+    let mut mined = history.clone();
+    mined.push(payment("new", "new_input", Some(101)));
+    let events = record_payment_events(
+        &subscription,
+        &descriptor,
+        mined.clone(),
+        103,
+        &CONFIRMATIONS,
+    )
+    .await?;
+    assert_eq!(
+        events,
+        vec![
+            PaymentEvent::Confirmed {
+                txid: txid("new").to_string(),
+                height: 101,
+                confirmations: 1,
+            },
+            PaymentEvent::Confirmed {
+                txid: txid("new").to_string(),
+                height: 101,
+                confirmations: 3,
+            },
+        ]
+    );
+
+    let events =
+        record_payment_events(&subscription, &descriptor, mined, 103, &CONFIRMATIONS).await?;
+    assert!(events.is_empty());
+
+    assert!(stop_payment_watcher(&subscription).await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_watcher_mined_without_seen() -> anyhow::Result<()> {
+    let descriptor = SecretString("payment_watcher_mined_without_seen".to_owned());
+    let subscription = start_payment_watcher(&descriptor).await;
+
+    let events =
+        record_payment_events(&subscription, &descriptor, vec![], 100, &CONFIRMATIONS).await?;
+    assert!(events.is_empty());
+
+    // Mined between two polls, so the payment is never seen in the mempool
+    let mined = vec![payment("mined", "mined_input", Some(100))];
+    let events =
+        record_payment_events(&subscription, &descriptor, mined, 100, &CONFIRMATIONS).await?;
+    assert_eq!(
+        events,
+        vec![PaymentEvent::Confirmed {
+            txid: txid("mined").to_string(),
+            height: 100,
+            confirmations: 1,
+        }]
+    );
+
+    assert!(stop_payment_watcher(&subscription).await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_watcher_replaced_and_dropped() -> anyhow::Result<()> {
+    let descriptor = SecretString("payment_watcher_replaced_and_dropped".to_owned());
+    let subscription = start_payment_watcher(&descriptor).await;
+
+    let events =
+        record_payment_events(&subscription, &descriptor, vec![], 100, &CONFIRMATIONS).await?;
+    assert!(events.is_empty());
+
+    let mempool = vec![
+        payment("original", "shared_input", None),
+        payment("dropped", "dropped_input", None),
+    ];
+    let events =
+        record_payment_events(&subscription, &descriptor, mempool, 100, &CONFIRMATIONS).await?;
+    assert_eq!(events.len(), 2);
+
+    let replaced = vec![payment("replacement", "shared_input", None)];
+    let events =
+        record_payment_events(&subscription, &descriptor, replaced, 100, &CONFIRMATIONS).await?;
+    assert!(events.contains(&PaymentEvent::Replaced {
+        txid: txid("original").to_string(),
+        replaced_by: txid("replacement").to_string(),
+    }));
+    assert!(events.contains(&PaymentEvent::Dropped {
+        txid: txid("dropped").to_string(),
+    }));
+
+    assert!(stop_payment_watcher(&subscription).await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn payment_watcher_subscriptions_are_isolated() -> anyhow::Result<()> {
+    let descriptor = SecretString("payment_watcher_subscriptions_are_isolated".to_owned());
+    let first = start_payment_watcher(&descriptor).await;
+    let second = start_payment_watcher(&descriptor).await;
+    assert_ne!(first, second);
+
+    record_payment_events(&first, &descriptor, vec![], 100, &CONFIRMATIONS).await?;
+    record_payment_events(&second, &descriptor, vec![], 100, &CONFIRMATIONS).await?;
+
+    // Both consumers receive the same payment, neither consumes the event of the other
+    let mempool = vec![payment("shared", "shared_input", None)];
+    let seen = vec![PaymentEvent::Seen {
+        txid: txid("shared").to_string(),
+        amount: 1000,
+    }];
+    let events =
+        record_payment_events(&first, &descriptor, mempool.clone(), 100, &CONFIRMATIONS).await?;
+    assert_eq!(events, seen);
+    let events = record_payment_events(&second, &descriptor, mempool, 100, &CONFIRMATIONS).await?;
+    assert_eq!(events, seen);
+
+    // A subscription only serves the descriptor it was started for
+    let other = SecretString("another descriptor".to_owned());
+    assert!(
+        record_payment_events(&first, &other, vec![], 100, &CONFIRMATIONS)
+            .await
+            .is_err()
+    );
+
+    // The descriptor is not enough to stop a watcher
+    assert!(!stop_payment_watcher(&descriptor.0).await);
+    assert!(stop_payment_watcher(&first).await);
+    assert!(is_watching_payments(&second).await);
+    assert!(stop_payment_watcher(&second).await);
+    Ok(())
+}
@@ -0,0 +1,164 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use anyhow::Result;
+use bitcoin::{
+    blockdata::{opcodes, script::Builder},
+    hashes::{sha256d, Hash},
+    secp256k1::{KeyPair, Message, Secp256k1, XOnlyPublicKey},
+    util::{
+        schnorr::{SchnorrSig, TapTweak},
+        sighash::{Prevouts, SchnorrSighashType, SighashCache},
+    },
+    OutPoint, PackedLockTime, Script, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+};
+use bitmask_core::bitcoin::{check_proof_of_reserves, BitcoinReservesError};
+
+const MESSAGE: &str = "bitmask reserves";
+const RESERVE_AMOUNT: u64 = 10_000;
+
+fn challenge_input(message: &str) -> TxIn {
+    let message = format!("Proof-of-Reserves: {message}");
+    let txid = Txid::from_hash(sha256d::Hash::hash(message.as_bytes()));
+    TxIn {
+        previous_output: OutPoint::new(txid, 0),
+        ..Default::default()
+    }
+}
+
+fn challenge_txout() -> TxOut {
+    TxOut {
+        value: 0,
+        script_pubkey: Builder::new().push_opcode(opcodes::OP_TRUE).into_script(),
+    }
+}
+
+fn reserve_outpoint() -> OutPoint {
+    OutPoint::new(Txid::from_hash(sha256d::Hash::hash(b"reserve")), 0)
+}
+
+/// Proof spending a single taproot reserve output, signed with the given sighash type
+fn signed_proof(message: &str, hash_ty: SchnorrSighashType) -> (Transaction, TxOut) {
+    let (tx, mut reserves) = signed_proof_of(message, hash_ty, &[reserve_outpoint()]);
+    (tx, reserves.remove(0))
+}
+
+/// Proof spending the taproot reserve outputs at the given outpoints, all of them
+/// signed with the same key and sighash type
+fn signed_proof_of(
+    message: &str,
+    hash_ty: SchnorrSighashType,
+    outpoints: &[OutPoint],
+) -> (Transaction, Vec<TxOut>) {
+    let secp = Secp256k1::new();
+    let keypair = KeyPair::from_seckey_slice(&secp, &[7; 32]).expect("valid key");
+    let (internal_key, _) = XOnlyPublicKey::from_keypair(&keypair);
+    let reserve = TxOut {
+        value: RESERVE_AMOUNT,
+        script_pubkey: Script::new_v1_p2tr(&secp, internal_key, None),
+    };
+    let reserves = vec![reserve; outpoints.len()];
+
+    let mut input = vec![challenge_input(message)];
+    input.extend(outpoints.iter().map(|outpoint| TxIn {
+        previous_output: *outpoint,
+        sequence: Sequence::MAX,
+        ..Default::default()
+    }));
+    let mut tx = Transaction {
+        version: 2,
+        lock_time: PackedLockTime::ZERO,
+        input,
+        output: vec![TxOut {
+            value: RESERVE_AMOUNT * outpoints.len() as u64,
+            script_pubkey: Builder::new()
+                .push_opcode(opcodes::all::OP_RETURN)
+                .into_script(),
+        }],
+    };
+
+    let mut prevouts = vec![challenge_txout()];
+    prevouts.extend(reserves.clone());
+    let tweaked = keypair.tap_tweak(&secp, None).to_inner();
+    for index in 1..tx.input.len() {
+        let sighash = SighashCache::new(&tx)
+            .taproot_key_spend_signature_hash(index, &Prevouts::All(&prevouts), hash_ty)
+            .expect("valid sighash");
+        let msg = Message::from_slice(&sighash.into_inner()).expect("valid message");
+        let sig = secp.sign_schnorr_no_aux_rand(&msg, &tweaked);
+        tx.input[index].witness = Witness::from_vec(vec![SchnorrSig { sig, hash_ty }.to_vec()]);
+    }
+
+    (tx, reserves)
+}
+
+#[test]
+fn proof_of_reserves_valid() -> Result<()> {
+    for hash_ty in [SchnorrSighashType::Default, SchnorrSighashType::All] {
+        let (tx, reserve) = signed_proof(MESSAGE, hash_ty);
+        let verified = check_proof_of_reserves(&tx, MESSAGE, &[reserve])?;
+        assert_eq!(verified.amount, RESERVE_AMOUNT);
+        assert_eq!(
+            verified.utxos,
+            vec![tx.input[1].previous_output.to_string()]
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn proof_of_reserves_tampered_message() -> Result<()> {
+    let (tx, reserve) = signed_proof(MESSAGE, SchnorrSighashType::Default);
+    let verified = check_proof_of_reserves(&tx, "another message", &[reserve]);
+    assert!(matches!(
+        verified,
+        Err(BitcoinReservesError::WrongChallenge)
+    ));
+    Ok(())
+}
+
+#[test]
+fn proof_of_reserves_reject_non_default_sighash() -> Result<()> {
+    for hash_ty in [
+        SchnorrSighashType::None,
+        SchnorrSighashType::Single,
+        SchnorrSighashType::AllPlusAnyoneCanPay,
+        SchnorrSighashType::NonePlusAnyoneCanPay,
+    ] {
+        let (tx, reserve) = signed_proof(MESSAGE, hash_ty);
+        let verified = check_proof_of_reserves(&tx, MESSAGE, &[reserve]);
+        assert!(
+            matches!(verified, Err(BitcoinReservesError::WrongSighash(_))),
+            "{hash_ty:?} must be rejected"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn proof_of_reserves_reject_duplicated_input() -> Result<()> {
+    // Every copy of the input is correctly signed, but its value must count once
+    let (tx, reserves) = signed_proof_of(
+        MESSAGE,
+        SchnorrSighashType::Default,
+        &[reserve_outpoint(), reserve_outpoint()],
+    );
+    let verified = check_proof_of_reserves(&tx, MESSAGE, &reserves);
+    assert!(matches!(
+        verified,
+        Err(BitcoinReservesError::DuplicateInput(outpoint)) if outpoint == reserve_outpoint()
+    ));
+
+    // A reserve input cannot spend the challenge outpoint either
+    let challenge = challenge_input(MESSAGE).previous_output;
+    let (tx, reserves) = signed_proof_of(
+        MESSAGE,
+        SchnorrSighashType::Default,
+        &[reserve_outpoint(), challenge],
+    );
+    let verified = check_proof_of_reserves(&tx, MESSAGE, &reserves);
+    assert!(matches!(
+        verified,
+        Err(BitcoinReservesError::DuplicateInput(outpoint)) if outpoint == challenge
+    ));
+    Ok(())
+}
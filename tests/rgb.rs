@@ -6,6 +6,7 @@ mod rgb {
         mod psbt;
         mod stl;
         mod stock;
+        mod transfer;
         pub mod utils;
     }
 
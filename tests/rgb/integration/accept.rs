@@ -1,20 +1,25 @@
 #![cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+
 use anyhow::Result;
+use bdk::wallet::AddressIndex;
 use bitmask_core::{
-    bitcoin::{save_mnemonic, sign_and_publish_psbt_file},
+    bitcoin::{get_wallet, save_mnemonic, sign_and_publish_psbt_file, sync_wallet},
     rgb::{
-        create_watcher, list_transfers, remove_transfer, save_transfer, structs::ContractAmount,
+        accept_transfer, create_invoice, create_watcher, full_transfer_asset, get_contract, import,
+        list_transfers, preview_transfer, remove_transfer, save_transfer, structs::ContractAmount,
         verify_transfers, watcher_next_address,
     },
     structs::{
-        DecryptedWalletData, RgbRemoveTransferRequest, RgbSaveTransferRequest, SecretString,
-        SignPsbtRequest, TransferType, TxStatus, WatcherRequest,
+        AcceptRequest, AssetType, DecryptedWalletData, FullRgbTransferRequest, ImportRequest,
+        InvoiceRequest, PsbtFeeRequest, RgbRemoveTransferRequest, RgbSaveTransferRequest,
+        RgbValidationLevel, SecretString, SignPsbtRequest, TransferType, TxStatus, WatcherRequest,
     },
 };
 
 use crate::rgb::integration::utils::{
-    create_new_invoice, create_new_psbt, create_new_transfer, issuer_issue_contract_v2,
-    send_some_coins, UtxoFilter, ISSUER_MNEMONIC, OWNER_MNEMONIC,
+    create_new_invoice, create_new_psbt, create_new_transfer, generate_new_block,
+    issuer_issue_contract_v2, send_some_coins, UtxoFilter, ISSUER_MNEMONIC, OWNER_MNEMONIC,
 };
 
 #[tokio::test]
@@ -118,7 +123,7 @@ pub async fn allow_save_read_remove_transfers() -> Result<()> {
     for sk in all_sks {
         let is_issuer = sk == issuer_sk;
 
-        let list_resp = list_transfers(&sk, contract_id.clone()).await;
+        let list_resp = list_transfers(&sk, contract_id.clone(), None).await;
         assert!(list_resp.is_ok());
 
         let list_resp = list_resp?;
@@ -144,7 +149,7 @@ pub async fn allow_save_read_remove_transfers() -> Result<()> {
     let contract_id = issuer_resp.contract_id.clone();
     let all_sks = [issuer_sk.clone(), owner_sk.clone()];
     for sk in all_sks {
-        let list_resp = list_transfers(&sk, contract_id.clone()).await;
+        let list_resp = list_transfers(&sk, contract_id.clone(), None).await;
         assert!(list_resp.is_ok());
 
         let list_resp = list_resp?;
@@ -168,7 +173,7 @@ pub async fn allow_save_read_remove_transfers() -> Result<()> {
         let list_resp = remove_transfer(&sk, req).await;
         assert!(list_resp.is_ok());
 
-        let list_resp = list_transfers(&sk, contract_id.clone()).await;
+        let list_resp = list_transfers(&sk, contract_id.clone(), None).await;
         assert!(list_resp.is_ok());
 
         let list_resp = list_resp?;
@@ -294,3 +299,211 @@ pub async fn accept_all_transfers() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test]
+pub async fn allow_preview_transfer_before_accept() -> Result<()> {
+    let issuer_keys: DecryptedWalletData = save_mnemonic(
+        &SecretString(ISSUER_MNEMONIC.to_string()),
+        &SecretString("".to_string()),
+    )
+    .await?;
+    let owner_keys = &save_mnemonic(
+        &SecretString(OWNER_MNEMONIC.to_string()),
+        &SecretString("".to_string()),
+    )
+    .await?;
+
+    let watcher_name = "default";
+    let owner_sk = owner_keys.private.nostr_prv.to_string();
+    let create_watch_req = WatcherRequest {
+        name: watcher_name.to_string(),
+        xpub: owner_keys.public.watcher_xpub.clone(),
+        force: true,
+    };
+    create_watcher(&owner_sk, create_watch_req.clone()).await?;
+
+    let issuer_resp = issuer_issue_contract_v2(
+        1,
+        "RGB20",
+        ContractAmount::new(5, 2).to_value(),
+        false,
+        true,
+        None,
+        Some("0.1".to_string()),
+        Some(UtxoFilter::with_amount_equal_than(10000000)),
+        None,
+    )
+    .await?;
+    let issuer_resp = &issuer_resp[0];
+
+    let owner_invoice = &create_new_invoice(
+        &issuer_resp.contract_id,
+        &issuer_resp.iface,
+        2.00,
+        owner_keys.clone(),
+        None,
+        Some(issuer_resp.clone().contract.strict),
+    )
+    .await?;
+
+    let psbt_resp = create_new_psbt(
+        &issuer_resp.contract_id,
+        &issuer_resp.iface,
+        vec![issuer_resp.issue_utxo.clone()],
+        issuer_keys.clone(),
+    )
+    .await?;
+    let transfer_resp = &create_new_transfer(
+        issuer_keys.clone(),
+        owner_invoice.clone(),
+        psbt_resp.clone(),
+    )
+    .await?;
+
+    let request = AcceptRequest {
+        consignment: transfer_resp.consig.clone(),
+        force: false,
+    };
+    let preview_resp = preview_transfer(&owner_sk, request).await?;
+
+    assert_eq!(preview_resp.consig_id, transfer_resp.consig_id);
+    assert_eq!(preview_resp.contract_id, issuer_resp.contract_id);
+    assert_eq!(preview_resp.precision, Some(2));
+    assert_eq!(preview_resp.valid, preview_resp.report.valid);
+    assert!(preview_resp
+        .report
+        .items
+        .iter()
+        .all(|x| x.level != RgbValidationLevel::Failure || !preview_resp.valid));
+    assert!(preview_resp
+        .allocations
+        .iter()
+        .any(|x| x.amount > 0 && x.invoice == Some(owner_invoice.invoice.clone())));
+
+    // The preview never accepts the consignment
+    let list_resp = list_transfers(&owner_sk, issuer_resp.contract_id.clone(), None).await?;
+    assert!(!list_resp
+        .transfers
+        .into_iter()
+        .any(|x| x.consig_id == transfer_resp.consig_id));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn accept_witness_invoice_payment() -> Result<()> {
+    // 0. Retrieve all keys
+    let issuer_keys: DecryptedWalletData = save_mnemonic(
+        &SecretString(ISSUER_MNEMONIC.to_string()),
+        &SecretString("".to_string()),
+    )
+    .await?;
+    let owner_keys = &save_mnemonic(
+        &SecretString(OWNER_MNEMONIC.to_string()),
+        &SecretString("".to_string()),
+    )
+    .await?;
+
+    // 1. Create All Watchers
+    let watcher_name = "default";
+    let issuer_sk = issuer_keys.private.nostr_prv.to_string();
+    let create_watch_req = WatcherRequest {
+        name: watcher_name.to_string(),
+        xpub: issuer_keys.public.watcher_xpub.clone(),
+        force: true,
+    };
+    create_watcher(&issuer_sk, create_watch_req.clone()).await?;
+
+    let owner_sk = owner_keys.private.nostr_prv.to_string();
+    let create_watch_req = WatcherRequest {
+        name: watcher_name.to_string(),
+        xpub: owner_keys.public.watcher_xpub.clone(),
+        force: true,
+    };
+    create_watcher(&owner_sk, create_watch_req.clone()).await?;
+
+    // 2. Issuer Contract
+    let issuer_resp = issuer_issue_contract_v2(
+        1,
+        "RGB20",
+        ContractAmount::new(5, 2).to_value(),
+        false,
+        true,
+        None,
+        Some("0.1".to_string()),
+        Some(UtxoFilter::with_amount_equal_than(10000000)),
+        None,
+    )
+    .await?;
+    let issuer_resp = &issuer_resp[0];
+
+    // 3. Owner Create Witness Invoice (paid to a new address of the owner)
+    let import_req = ImportRequest {
+        import: AssetType::RGB20,
+        data: issuer_resp.contract.strict.clone(),
+    };
+    import(&owner_sk, import_req).await?;
+
+    let owner_address = watcher_next_address(&owner_sk, watcher_name, "RGB20").await?;
+    let invoice_req = InvoiceRequest {
+        contract_id: issuer_resp.contract_id.clone(),
+        iface: issuer_resp.iface.clone(),
+        amount: "1".to_string(),
+        min_amount: None,
+        max_amount: None,
+        seal: owner_address.address,
+        params: HashMap::new(),
+        transports: vec![],
+        expire_at: None,
+    };
+    let owner_invoice = create_invoice(&owner_sk, invoice_req).await?;
+
+    // 4. Issuer Pay Invoice (creating the witness output)
+    let issuer_btc_desc = &issuer_keys.public.btc_change_descriptor_xpub;
+    let issuer_vault = get_wallet(&SecretString(issuer_btc_desc.to_string()), None).await?;
+    let issuer_address = &issuer_vault
+        .lock()
+        .await
+        .get_address(AddressIndex::LastUnused)?
+        .address
+        .to_string();
+
+    send_some_coins(issuer_address, "0.001").await;
+    sync_wallet(&issuer_vault).await?;
+
+    let full_transfer_req = FullRgbTransferRequest {
+        contract_id: issuer_resp.contract_id.clone(),
+        iface: issuer_resp.iface.clone(),
+        rgb_invoice: owner_invoice.invoice.clone(),
+        descriptor: SecretString(issuer_keys.public.rgb_assets_descriptor_xpub.to_string()),
+        change_terminal: "/20/1".to_string(),
+        fee: PsbtFeeRequest::Value(1000),
+        bitcoin_changes: vec![],
+        amount: None,
+    };
+    let transfer_resp = full_transfer_asset(&issuer_sk, full_transfer_req).await?;
+
+    let psbt_req = SignPsbtRequest {
+        psbt: transfer_resp.psbt,
+        descriptors: vec![
+            SecretString(issuer_keys.private.rgb_assets_descriptor_xprv.clone()),
+            SecretString(issuer_keys.private.btc_descriptor_xprv.clone()),
+            SecretString(issuer_keys.private.btc_change_descriptor_xprv.clone()),
+        ],
+    };
+    let psbt_resp = sign_and_publish_psbt_file(psbt_req).await;
+    assert!(psbt_resp.is_ok());
+    generate_new_block().await;
+
+    // 5. Owner Accept Transfer (registering the witness output)
+    let request = AcceptRequest {
+        consignment: transfer_resp.consig,
+        force: false,
+    };
+    let resp = accept_transfer(&owner_sk, request).await;
+    assert!(resp.is_ok());
+    assert!(resp?.valid);
+
+    let contract = get_contract(&owner_sk, &issuer_resp.contract_id).await?;
+    assert_eq!(1.0, contract.balance_normalised);
+    Ok(())
+}
@@ -7,16 +7,17 @@ use bitcoin::Txid;
 use bitmask_core::{
     bitcoin::{get_blockchain, new_mnemonic, sign_and_publish_psbt_file},
     rgb::{
-        accept_transfer, consignment::NewTransferOptions, create_watcher, full_transfer_asset,
-        get_contract, internal_replace_transfer, issue_contract, list_contracts,
-        structs::ContractAmount, watcher_next_address,
+        accept_transfer, airdrop_asset, consignment::NewTransferOptions, create_watcher,
+        full_transfer_asset, get_contract, internal_replace_transfer, issue_contract,
+        list_contracts, structs::ContractAmount, watcher_next_address,
     },
     structs::{
         AcceptRequest, FullRgbTransferRequest, IssueRequest, PsbtFeeRequest, PublishedPsbtResponse,
-        RgbReplaceResponse, RgbTransferRequest, RgbTransferResponse, SecretString, SignPsbtRequest,
-        WatcherRequest,
+        RgbAirdropRequest, RgbReplaceResponse, RgbTransferRequest, RgbTransferResponse,
+        SecretString, SignPsbtRequest, WatcherRequest,
     },
 };
+use rgbstd::interface::TypedState;
 use rgbwallet::RgbInvoice;
 
 use crate::rgb::integration::utils::{
@@ -59,6 +60,12 @@ pub async fn create_strict_transfer() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(issuer_sk, issue_uda_req).await?;
@@ -176,6 +183,12 @@ pub async fn create_transfer_rbf() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(issuer_sk, issue_uda_req).await?;
@@ -329,6 +342,12 @@ pub async fn create_batch_transfer() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(issuer_sk, issue_uda_req).await?;
@@ -438,6 +457,7 @@ pub async fn create_batch_transfer() -> Result<()> {
         psbt: psbt_resp_2.psbt,
         rgb_invoice: other_resp.invoice.clone(),
         terminal: psbt_resp_2.terminal,
+        amount: None,
     };
 
     let rgb_invoice = RgbInvoice::from_str(&owner_resp.invoice)?;
@@ -556,6 +576,12 @@ pub async fn create_transfer_skipping_invalid_states() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(issuer_sk, issue_uda_req).await?;
@@ -688,6 +714,7 @@ pub async fn create_transfer_skipping_invalid_states() -> Result<()> {
         change_terminal: "/20/1".to_string(),
         fee: PsbtFeeRequest::Value(546),
         bitcoin_changes: vec![],
+        amount: None,
     };
 
     let full_transfer_resp = full_transfer_asset(issuer_sk, full_transfer_req).await;
@@ -731,3 +758,132 @@ pub async fn create_transfer_skipping_invalid_states() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+pub async fn reject_airdrop_with_invalid_invoices() -> Result<()> {
+    let issuer_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let issuer_sk = &issuer_keys.private.nostr_prv;
+
+    let airdrop_req = RgbAirdropRequest {
+        contract_id: "rgb:2ZNvgYF-WQ8Cp6C-8Y9VbXX-kCDNqfH-MiYXmYJ-PCfrUJL".to_string(),
+        iface: "RGB20".to_string(),
+        invoices: vec!["invalid invoice".to_string()],
+        csv: "invoice,amount\nanother invalid invoice,10".to_string(),
+        resume: false,
+        descriptor: SecretString(issuer_keys.public.rgb_assets_descriptor_xpub.clone()),
+        change_terminal: "/20/1".to_string(),
+        fee: PsbtFeeRequest::Value(1000),
+        bitcoin_changes: vec![],
+    };
+
+    let airdrop_resp = airdrop_asset(issuer_sk, airdrop_req).await;
+    assert!(airdrop_resp.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn allow_airdrop_to_many_recipients() -> Result<()> {
+    // 1. Initial Setup
+    let issuer_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let owner_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let another_owner_keys = new_mnemonic(&SecretString("".to_string())).await?;
+
+    let issuer_sk = &issuer_keys.private.nostr_prv;
+    let fungibles_resp = issuer_issue_contract_v2(
+        1,
+        "RGB20",
+        ContractAmount::new(5, 2).to_value(),
+        false,
+        true,
+        None,
+        Some("0.10000000".to_string()),
+        Some(UtxoFilter::with_amount_equal_than(10_000_000)),
+        Some(issuer_keys.clone()),
+    )
+    .await?;
+    let issuer_resp = &fungibles_resp[0];
+    let contract_id = &issuer_resp.contract_id;
+
+    // 2. Generate Invoices (the second one is paid with the CSV amount)
+    let mut owner_sks = vec![];
+    let mut invoices = vec![];
+    for keys in [owner_keys.clone(), another_owner_keys.clone()] {
+        let owner_sk = keys.private.nostr_prv.to_string();
+        let create_watch_req = WatcherRequest {
+            name: "default".to_string(),
+            xpub: keys.public.watcher_xpub.clone(),
+            force: false,
+        };
+        create_watcher(&owner_sk, create_watch_req).await?;
+
+        let owner_resp = create_new_invoice(
+            contract_id,
+            &issuer_resp.iface,
+            1.0,
+            keys.clone(),
+            None,
+            Some(issuer_resp.clone().contract.strict),
+        )
+        .await?;
+        owner_sks.push(owner_sk);
+        invoices.push(owner_resp.invoice);
+    }
+
+    let mut open_invoice = RgbInvoice::from_str(&invoices[1])?;
+    open_invoice.owned_state = TypedState::Void;
+
+    // 3. Airdrop
+    let airdrop_req = RgbAirdropRequest {
+        contract_id: contract_id.clone(),
+        iface: issuer_resp.iface.clone(),
+        invoices: vec![invoices[0].clone()],
+        csv: format!("invoice,amount\n{open_invoice},2.0"),
+        resume: false,
+        descriptor: SecretString(issuer_keys.public.rgb_assets_descriptor_xpub.clone()),
+        change_terminal: "/20/1".to_string(),
+        fee: PsbtFeeRequest::Value(1000),
+        bitcoin_changes: vec![],
+    };
+
+    let airdrop_resp = airdrop_asset(issuer_sk, airdrop_req).await?;
+    assert!(airdrop_resp.pending.is_empty());
+    assert_eq!(airdrop_resp.recipients.len(), 2);
+    assert!(airdrop_resp
+        .recipients
+        .iter()
+        .all(|recipient| recipient.consig_id.is_some()));
+    assert_eq!(
+        airdrop_resp.recipients[1].amount,
+        ContractAmount::from("2.0".to_string(), 2)?.to_value()
+    );
+
+    // 4. Sign and Broadcast
+    let psbt_req = SignPsbtRequest {
+        psbt: airdrop_resp.transfer.psbt.clone(),
+        descriptors: vec![SecretString(
+            issuer_keys.private.rgb_assets_descriptor_xprv.clone(),
+        )],
+    };
+    let psbt_resp = sign_and_publish_psbt_file(psbt_req).await;
+    assert!(psbt_resp.is_ok());
+
+    // 5. Accept Transfers
+    generate_new_block().await;
+    let main_consig = airdrop_resp.transfer.consig.clone();
+    let request = AcceptRequest {
+        consignment: main_consig,
+        force: false,
+    };
+    let resp = accept_transfer(&owner_sks[0], request).await;
+    assert!(resp.is_ok());
+
+    // 6. Check Contract State
+    let contract = get_contract(issuer_sk, contract_id).await?;
+    assert_eq!(2., contract.balance_normalised);
+
+    let contract = get_contract(&owner_sks[0], contract_id).await?;
+    assert_eq!(1., contract.balance_normalised);
+
+    Ok(())
+}
@@ -59,6 +59,12 @@ pub async fn measure_batch_operation() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(&issuer_sk, issue_uda_req).await?;
@@ -177,6 +183,12 @@ pub async fn create_transfer_rbf() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(&issuer_sk, issue_uda_req).await?;
@@ -330,6 +342,12 @@ pub async fn create_batch_transfer() -> Result<()> {
         seal: issue_seal.to_owned(),
         iface: "RGB21".to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let _uda_resp = issue_contract(&issuer_sk, issue_uda_req).await?;
@@ -439,6 +457,7 @@ pub async fn create_batch_transfer() -> Result<()> {
         psbt: psbt_resp_2.psbt,
         rgb_invoice: other_resp.invoice.clone(),
         terminal: psbt_resp_2.terminal,
+        amount: None,
     };
 
     let rgb_invoice = RgbInvoice::from_str(&owner_resp.invoice)?;
@@ -15,7 +15,10 @@ use bitmask_core::{
         },
         constants::{RGB_DEFAULT_NAME, RGB_OLDEST_VERSION, RGB_STRICT_TYPE_VERSION},
         crdt::{LocalRgbAccount, RawRgbAccount},
-        structs::{RgbAccountV0, RgbAccountV1, RgbTransfersV0, RgbTransfersV1},
+        structs::{
+            RgbAccountV0, RgbAccountV1, RgbTransferV1, RgbTransfersV0, RgbTransfersV1,
+            RgbTransfersV2,
+        },
     },
     structs::SecretString,
 };
@@ -175,29 +178,59 @@ async fn migrate_rgb_transfer_from_v0_to_v1() -> anyhow::Result<()> {
     // Case 1: no version
     save_transfers_v0(user_sk, name, &v0, None).await?;
 
-    let v1 = get_transfers_v1(user_sk, name).await?;
+    let v1 = get_transfers_v2(user_sk, name).await?;
     assert_eq!(v0.transfers.len(), v1.transfers.len());
 
     // Case 2: oldest version
     save_transfers_v0(user_sk, name, &v0, Some(RGB_OLDEST_VERSION.to_vec())).await?;
 
-    let v1 = get_transfers_v1(user_sk, name).await?;
+    let v1 = get_transfers_v2(user_sk, name).await?;
     assert_eq!(v0.transfers.len(), v1.transfers.len());
 
     // Case 3: strict-type 1.6.x version
     save_transfers_v0(user_sk, name, &v0, Some(RGB_STRICT_TYPE_VERSION.to_vec())).await?;
 
-    let v1 = get_transfers_v1(user_sk, name).await?;
+    let v1 = get_transfers_v2(user_sk, name).await?;
     assert_eq!(v0.transfers.len(), v1.transfers.len());
 
     // Case 4: v0
     save_transfers_v0(user_sk, name, &v0, Some(b"v0".to_vec())).await?;
 
-    let v1 = get_transfers_v1(user_sk, name).await?;
+    let v1 = get_transfers_v2(user_sk, name).await?;
     assert_eq!(v0.transfers.len(), v1.transfers.len());
 
-    // Case 5: Save v1
-    save_transfers_v1(user_sk, name, v1).await?;
+    // Case 5: Save latest version
+    save_transfers_v2(user_sk, name, v1).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn migrate_rgb_transfer_from_v1_to_v2() -> anyhow::Result<()> {
+    let name = "migrate_rgb_transfer_from_v1_to_v2.c15";
+
+    let user_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let user_sk = &user_keys.private.nostr_prv;
+
+    let mut v1 = RgbTransfersV1::default();
+    v1.transfers.insert(
+        "contract".to_string(),
+        vec![RgbTransferV1 {
+            consig_id: "consig".to_string(),
+            ..default!()
+        }],
+    );
+    save_transfers_v1(user_sk, name, &v1, Some(b"v1".to_vec())).await?;
+
+    let v2 = get_transfers_v2(user_sk, name).await?;
+    assert_eq!(v1.transfers, v2.transfers);
+    assert!(v2.lifecycle.is_empty());
+    assert!(v2.anchors.is_empty());
+
+    save_transfers_v2(user_sk, name, v2.clone()).await?;
+
+    let latest = get_transfers_v2(user_sk, name).await?;
+    assert_eq!(v2, latest);
 
     Ok(())
 }
@@ -309,11 +342,29 @@ async fn save_transfers_v0(
 async fn save_transfers_v1(
     sk: &str,
     name: &str,
-    rgb_transfers: RgbTransfersV1,
+    rgb_transfers: &RgbTransfersV1,
+    metadata: Option<Vec<u8>>,
+) -> Result<(), StorageError> {
+    let data = to_allocvec(rgb_transfers)
+        .map_err(|op| StorageError::StrictWrite(name.to_string(), op.to_string()))?;
+
+    let hashed_name = blake3::hash(format!("{LIB_ID_RGB}-{name}").as_bytes())
+        .to_hex()
+        .to_lowercase();
+
+    store(sk, &format!("{hashed_name}.c15"), &data, false, metadata)
+        .await
+        .map_err(|op| StorageError::CarbonadoWrite(name.to_string(), op.to_string()))
+}
+
+async fn save_transfers_v2(
+    sk: &str,
+    name: &str,
+    rgb_transfers: RgbTransfersV2,
 ) -> Result<(), StorageError> {
     store_transfers(sk, name, &rgb_transfers).await
 }
 
-async fn get_transfers_v1(sk: &str, name: &str) -> Result<RgbTransfersV1, StorageError> {
+async fn get_transfers_v2(sk: &str, name: &str) -> Result<RgbTransfersV2, StorageError> {
     retrieve_transfers(sk, name).await
 }
@@ -1,4 +1,18 @@
 #![cfg(not(target_arch = "wasm32"))]
+use bitmask_core::{
+    bitcoin::{new_mnemonic, sign_and_publish_psbt_file},
+    rgb::{
+        accept_transfer, create_watcher, full_transfer_asset, get_contract, import, issue_contract,
+        structs::ContractAmount, watcher_next_address, watcher_next_utxo,
+    },
+    structs::{
+        AcceptRequest, AssetType, FullRgbTransferRequest, ImportRequest, IssueRequest,
+        PsbtFeeRequest, SecretString, SignPsbtRequest, WatcherRequest,
+    },
+};
+
+use crate::rgb::integration::utils::{create_new_invoice_v2, send_some_coins};
+
 // use crate::rgb::integration::utils::{
 //     create_new_invoice, create_new_psbt, create_new_transfer, issuer_issue_contract,
 //     ISSUER_MNEMONIC,
@@ -40,3 +54,124 @@
 //     assert!(resp?.valid);
 //     Ok(())
 // }
+
+#[tokio::test]
+async fn allow_beneficiary_accept_collectible_transfer() -> anyhow::Result<()> {
+    // 1. Create Watchers
+    let whatever_address = "bcrt1p76gtucrxhmn8s5622r859dpnmkj0kgfcel9xy0sz6yj84x6ppz2qk5hpsw";
+    let issuer_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let owner_keys = new_mnemonic(&SecretString("".to_string())).await?;
+
+    let watcher_name = "default";
+    let issuer_sk = issuer_keys.private.nostr_prv.to_string();
+    let owner_sk = owner_keys.private.nostr_prv.to_string();
+    for (sk, keys) in [(&issuer_sk, &issuer_keys), (&owner_sk, &owner_keys)] {
+        let create_watch_req = WatcherRequest {
+            name: watcher_name.to_string(),
+            xpub: keys.public.watcher_xpub.clone(),
+            force: true,
+        };
+        create_watcher(sk, create_watch_req).await?;
+    }
+
+    // RGB25 allocations live in their own terminal
+    let issuer_desc_xpub = issuer_keys
+        .public
+        .rgb_assets_descriptor_xpub
+        .replace("/20/*", "/25/*");
+    let issuer_desc_xprv = issuer_keys
+        .private
+        .rgb_assets_descriptor_xprv
+        .replace("/20/*", "/25/*");
+
+    // 2. Issue Collectible Contract
+    let next_address = watcher_next_address(&issuer_sk, watcher_name, "RGB25").await?;
+    send_some_coins(&next_address.address, "0.1").await;
+    let issue_utxo = watcher_next_utxo(&issuer_sk, watcher_name, "RGB25")
+        .await?
+        .utxo
+        .unwrap()
+        .outpoint;
+
+    let request = IssueRequest {
+        ticker: "DIBA".to_string(),
+        name: "DIBA".to_string(),
+        description: "DIBA".to_string(),
+        precision: 2,
+        supply: ContractAmount::new(5, 2).to_value(),
+        seal: format!("tapret1st:{issue_utxo}"),
+        iface: "RGB25".to_string(),
+        meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
+    };
+    let issuer_resp = issue_contract(&issuer_sk, request).await?;
+
+    // 3. Owner Import Contract and Create Invoice
+    let import_req = ImportRequest {
+        import: AssetType::RGB25,
+        data: issuer_resp.contract.strict.clone(),
+    };
+    import(&owner_sk, import_req).await?;
+
+    let next_address = watcher_next_address(&owner_sk, watcher_name, "RGB25").await?;
+    send_some_coins(&next_address.address, "0.1").await;
+    let owner_utxo = watcher_next_utxo(&owner_sk, watcher_name, "RGB25")
+        .await?
+        .utxo
+        .unwrap()
+        .outpoint;
+    let owner_invoice = create_new_invoice_v2(
+        &issuer_resp.contract_id,
+        &issuer_resp.iface,
+        2.0,
+        &owner_utxo,
+        owner_keys.clone(),
+        None,
+        None,
+    )
+    .await?;
+
+    // 4. Create Transfer
+    let full_transfer_req = FullRgbTransferRequest {
+        contract_id: issuer_resp.contract_id.clone(),
+        iface: issuer_resp.iface.clone(),
+        rgb_invoice: owner_invoice.invoice,
+        descriptor: SecretString(issuer_desc_xpub),
+        change_terminal: "/25/1".to_string(),
+        fee: PsbtFeeRequest::Value(1000),
+        bitcoin_changes: vec![],
+        amount: None,
+    };
+    let transfer_resp = full_transfer_asset(&issuer_sk, full_transfer_req).await?;
+
+    let request = SignPsbtRequest {
+        psbt: transfer_resp.psbt.clone(),
+        descriptors: vec![SecretString(issuer_desc_xprv)],
+    };
+    let resp = sign_and_publish_psbt_file(request).await;
+    assert!(resp.is_ok());
+    send_some_coins(whatever_address, "0.001").await;
+
+    // 5. Accept Consig (Issuer and Owner Side)
+    for sk in [&issuer_sk, &owner_sk] {
+        let request = AcceptRequest {
+            consignment: transfer_resp.consig.clone(),
+            force: false,
+        };
+        let accept_resp = accept_transfer(sk, request).await?;
+        assert!(accept_resp.valid);
+    }
+
+    // 6. Check Balances
+    let issuer_contract = get_contract(&issuer_sk, &issuer_resp.contract_id).await?;
+    assert_eq!(3.0, issuer_contract.balance_normalised);
+
+    let owner_contract = get_contract(&owner_sk, &issuer_resp.contract_id).await?;
+    assert_eq!(2.0, owner_contract.balance_normalised);
+    Ok(())
+}
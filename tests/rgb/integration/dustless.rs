@@ -211,6 +211,7 @@ async fn create_dustless_transfer_with_fee_rate() -> anyhow::Result<()> {
         change_terminal: "/20/1".to_string(),
         fee: PsbtFeeRequest::FeeRate(1.1),
         bitcoin_changes: vec![],
+        amount: None,
     };
 
     let transfer_resp = full_transfer_asset(&sk, request).await?;
@@ -1,5 +1,22 @@
 #![cfg(not(target_arch = "wasm32"))]
-use crate::rgb::integration::utils::{get_uda_data, issuer_issue_contract};
+use amplify::confinement::U32;
+use bitmask_core::{
+    bitcoin::{new_mnemonic, sign_and_publish_psbt_file},
+    rgb::{
+        accept_transfer, create_watcher, fs::retrieve_stock, get_contract, inflate_asset,
+        issue_contract, list_contracts, preview_issue_contract, structs::ContractAmount,
+        watcher_next_address, watcher_next_utxo,
+    },
+    structs::{
+        AcceptRequest, InflationAllowanceRequest, IssueRequest, RgbTransferRequest, SecretString,
+        SignPsbtRequest, WatcherRequest,
+    },
+};
+use strict_encoding::StrictSerialize;
+
+use crate::rgb::integration::utils::{
+    create_new_invoice, create_new_psbt, get_uda_data, issuer_issue_contract, send_some_coins,
+};
 
 #[tokio::test]
 async fn allow_issuer_issue_fungible_contract() -> anyhow::Result<()> {
@@ -16,6 +33,166 @@ async fn allow_issuer_issue_uda_contract() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn allow_issuer_preview_contract_without_store() -> anyhow::Result<()> {
+    let issuer_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let issuer_sk = &issuer_keys.private.nostr_prv;
+
+    let request = IssueRequest {
+        ticker: "DIBA".to_string(),
+        name: "DIBA".to_string(),
+        description: "DIBA".to_string(),
+        precision: 2,
+        supply: 5,
+        seal: "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0"
+            .to_string(),
+        iface: "RGB20".to_string(),
+        meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
+    };
+
+    // 1. Preview the contract, leaving the stock unchanged
+    let stock = retrieve_stock(issuer_sk).await?;
+    let preview_resp = preview_issue_contract(issuer_sk, request.clone()).await?;
+    assert!(!preview_resp.contract.armored.is_empty());
+
+    let preview_stock = retrieve_stock(issuer_sk).await?;
+    assert_eq!(
+        stock.to_strict_serialized::<U32>()?,
+        preview_stock.to_strict_serialized::<U32>()?
+    );
+
+    let contracts_resp = list_contracts(issuer_sk, false).await?;
+    assert!(!contracts_resp
+        .contracts
+        .iter()
+        .any(|contract| contract.contract_id == preview_resp.contract_id));
+
+    // 2. Issue the previewed contract
+    let request = IssueRequest {
+        created: Some(preview_resp.created),
+        ..request
+    };
+    let issue_resp = issue_contract(issuer_sk, request).await?;
+    assert_eq!(issue_resp.contract_id, preview_resp.contract_id);
+    assert_eq!(issue_resp.created, preview_resp.created);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_issuer_inflate_contract() -> anyhow::Result<()> {
+    // 1. Create Watchers
+    let whatever_address = "bcrt1p76gtucrxhmn8s5622r859dpnmkj0kgfcel9xy0sz6yj84x6ppz2qk5hpsw";
+    let issuer_keys = new_mnemonic(&SecretString("".to_string())).await?;
+    let owner_keys = new_mnemonic(&SecretString("".to_string())).await?;
+
+    let watcher_name = "default";
+    let issuer_sk = issuer_keys.private.nostr_prv.to_string();
+    let owner_sk = owner_keys.private.nostr_prv.to_string();
+    for (sk, keys) in [(&issuer_sk, &issuer_keys), (&owner_sk, &owner_keys)] {
+        let create_watch_req = WatcherRequest {
+            name: watcher_name.to_string(),
+            xpub: keys.public.watcher_xpub.clone(),
+            force: true,
+        };
+        create_watcher(sk, create_watch_req).await?;
+    }
+
+    // 2. Issue Inflatable Contract
+    let next_address = watcher_next_address(&issuer_sk, watcher_name, "RGB20").await?;
+    send_some_coins(&next_address.address, "0.1").await;
+    let issue_utxo = watcher_next_utxo(&issuer_sk, watcher_name, "RGB20")
+        .await?
+        .utxo
+        .unwrap()
+        .outpoint;
+    let issue_seal = format!("tapret1st:{issue_utxo}");
+
+    let request = IssueRequest {
+        ticker: "DIBA".to_string(),
+        name: "DIBA".to_string(),
+        description: "DIBA".to_string(),
+        precision: 2,
+        supply: ContractAmount::new(5, 2).to_value(),
+        seal: issue_seal.clone(),
+        iface: "RGB20".to_string(),
+        meta: None,
+        inflation: vec![InflationAllowanceRequest {
+            seal: issue_seal,
+            amount: ContractAmount::new(10, 2).to_value(),
+        }],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
+    };
+    let issuer_resp = issue_contract(&issuer_sk, request).await?;
+
+    // 3. Owner Create Invoice
+    let owner_invoice = create_new_invoice(
+        &issuer_resp.contract_id,
+        &issuer_resp.iface,
+        2.5,
+        owner_keys.clone(),
+        None,
+        Some(issuer_resp.contract.strict.clone()),
+    )
+    .await?;
+
+    // 4. Issue New Supply from the Allowance
+    let psbt_resp = create_new_psbt(
+        &issuer_resp.contract_id,
+        &issuer_resp.iface,
+        vec![issuer_resp.issue_utxo.clone()],
+        issuer_keys.clone(),
+    )
+    .await?;
+    let inflate_req = RgbTransferRequest {
+        rgb_invoice: owner_invoice.invoice,
+        psbt: psbt_resp.psbt,
+        terminal: psbt_resp.terminal,
+        amount: None,
+    };
+    let inflate_resp = inflate_asset(&issuer_sk, inflate_req).await?;
+
+    let request = SignPsbtRequest {
+        psbt: inflate_resp.psbt.clone(),
+        descriptors: vec![SecretString(
+            issuer_keys.private.rgb_assets_descriptor_xprv.clone(),
+        )],
+    };
+    let resp = sign_and_publish_psbt_file(request).await;
+    assert!(resp.is_ok());
+    send_some_coins(whatever_address, "0.001").await;
+
+    // 5. Accept Consig (Issuer and Owner Side)
+    for sk in [&issuer_sk, &owner_sk] {
+        let request = AcceptRequest {
+            consignment: inflate_resp.consig.clone(),
+            force: false,
+        };
+        let accept_resp = accept_transfer(sk, request).await?;
+        assert!(accept_resp.valid);
+    }
+
+    // 6. Check Supply, Allowance and Balances
+    let issuer_contract = get_contract(&issuer_sk, &issuer_resp.contract_id).await?;
+    let expected = ContractAmount::from("7.5".to_string(), 2)?.to_value();
+    assert_eq!(issuer_contract.supply, expected);
+    assert_eq!(issuer_contract.allowance, Some(expected));
+    assert_eq!(5.0, issuer_contract.balance_normalised);
+
+    let owner_contract = get_contract(&owner_sk, &issuer_resp.contract_id).await?;
+    assert_eq!(2.5, owner_contract.balance_normalised);
+    Ok(())
+}
+
 // TODO: Review after support multi-token transfer
 // async fn _allow_issuer_issue_collectible_contract() -> anyhow::Result<()> {
 //     let collectible = Some(get_collectible_data());
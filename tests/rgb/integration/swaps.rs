@@ -13,10 +13,10 @@ use bitmask_core::{
         update_seller_offer, verify_transfers,
     },
     structs::{
-        AcceptRequest, AssetType, ImportRequest, IssueResponse, PsbtFeeRequest, PublishPsbtRequest,
-        RgbBidRequest, RgbBidResponse, RgbOfferRequest, RgbOfferResponse, RgbOfferUpdateRequest,
-        RgbSwapRequest, RgbSwapResponse, SecretString, SignPsbtRequest, SignedPsbtResponse,
-        WatcherRequest,
+        AcceptRequest, AssetType, ImportRequest, IssueResponse, PsbtFeeRequest, PsbtFormat,
+        PublishPsbtRequest, RgbBidRequest, RgbBidResponse, RgbOfferRequest, RgbOfferResponse,
+        RgbOfferUpdateRequest, RgbSwapRequest, RgbSwapResponse, SecretString, SignPsbtRequest,
+        SignedPsbtResponse, WatcherRequest,
     },
 };
 
@@ -148,6 +148,7 @@ async fn create_scriptless_swap() -> anyhow::Result<()> {
         bitcoin_changes: vec![],
         expire_at: Some(expire_at),
         presig: false,
+        psbt_version: PsbtFormat::V0,
     };
 
     let seller_swap_resp = create_seller_offer(&seller_sk, seller_swap_req).await;
@@ -376,6 +377,7 @@ async fn create_scriptless_swap_for_uda() -> anyhow::Result<()> {
         bitcoin_changes: vec![],
         expire_at: Some(expire_at),
         presig: false,
+        psbt_version: PsbtFormat::V0,
     };
 
     let seller_swap_resp = create_seller_offer(&seller_sk, seller_swap_req).await;
@@ -603,6 +605,7 @@ async fn create_presig_scriptless_swap() -> anyhow::Result<()> {
         bitcoin_changes: vec![],
         expire_at: Some(expire_at),
         presig: true,
+        psbt_version: PsbtFormat::V0,
     };
 
     let seller_swap_resp = create_seller_offer(&seller_sk, seller_swap_req).await;
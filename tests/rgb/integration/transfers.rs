@@ -770,6 +770,10 @@ async fn create_transfers_between_2_owners() -> anyhow::Result<()> {
         amount: "1.00".to_string(),
         seal: another_owner_seal,
         params: HashMap::default(),
+        transports: vec![],
+        expire_at: None,
+        min_amount: None,
+        max_amount: None,
     };
     let import_req = ImportRequest {
         import: AssetType::RGB20,
@@ -1669,6 +1673,7 @@ async fn allow_fungible_full_transfer_op() -> anyhow::Result<()> {
         change_terminal: "/20/1".to_string(),
         fee: PsbtFeeRequest::Value(1000),
         bitcoin_changes: vec![],
+        amount: None,
     };
 
     let issue_sk = issuer_keys.private.nostr_prv.to_string();
@@ -1740,6 +1745,7 @@ async fn allow_uda_full_transfer_op() -> anyhow::Result<()> {
         change_terminal: "/21/1".to_string(),
         fee: PsbtFeeRequest::Value(546),
         bitcoin_changes: vec![],
+        amount: None,
     };
 
     let issue_sk = issuer_keys.private.nostr_prv.to_string();
@@ -1895,6 +1901,7 @@ async fn allow_consecutive_full_transfer_bidirectional() -> anyhow::Result<()> {
             change_terminal: "/20/1".to_string(),
             fee: PsbtFeeRequest::Value(546),
             bitcoin_changes: vec![],
+            amount: None,
         };
 
         let full_transfer_resp = full_transfer_asset(wallet_a_sk, self_pay_req).await;
@@ -1959,6 +1966,7 @@ async fn allow_consecutive_full_transfer_bidirectional() -> anyhow::Result<()> {
                 change_terminal: "/20/1".to_string(),
                 fee: PsbtFeeRequest::Value(546),
                 bitcoin_changes: vec![],
+                amount: None,
             };
 
             let full_transfer_resp = full_transfer_asset(wallet_b_sk, self_pay_req).await;
@@ -2075,6 +2083,7 @@ async fn allow_save_transfer_and_verify() -> anyhow::Result<()> {
         change_terminal: "/20/1".to_string(),
         fee: PsbtFeeRequest::Value(1000),
         bitcoin_changes: vec![],
+        amount: None,
     };
 
     let resp = full_transfer_asset(&issuer_sk, self_pay_req).await?;
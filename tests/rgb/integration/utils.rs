@@ -14,7 +14,7 @@ use bitmask_core::{
     structs::{
         AllocationDetail, AssetType, ContractResponse, DecryptedWalletData, ImportRequest,
         InvoiceRequest, InvoiceResponse, IssueMediaRequest, IssueRequest, IssueResponse, MediaInfo,
-        MediaItemRequest, MediaRequest, PsbtFeeRequest, PsbtInputRequest, PsbtRequest,
+        MediaItemRequest, MediaRequest, PsbtFeeRequest, PsbtFormat, PsbtInputRequest, PsbtRequest,
         PsbtResponse, RgbTransferRequest, RgbTransferResponse, SecretString, WatcherRequest,
     },
 };
@@ -244,6 +244,12 @@ pub async fn issuer_issue_contract(
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let resp = issue_contract(sk, request).await?;
@@ -342,13 +348,17 @@ pub async fn create_new_invoice(
 
     let params = params.unwrap_or_default();
 
-    let amount = ContractAmount::from(amount.to_string(), precision);
+    let amount = ContractAmount::from(amount.to_string(), precision)?;
     let invoice_req = InvoiceRequest {
         contract_id: contract_id.to_owned(),
         iface: iface.to_owned(),
         amount: amount.to_string(),
         seal,
         params,
+        transports: vec![],
+        expire_at: None,
+        min_amount: None,
+        max_amount: None,
     };
 
     let resp = create_invoice(&sk, invoice_req).await?;
@@ -509,6 +519,12 @@ pub async fn issuer_issue_contract_v2(
             seal: issue_seal.to_owned(),
             iface: iface.to_string(),
             meta: meta.clone(),
+            inflation: vec![],
+            collection: vec![],
+            beneficiaries: vec![],
+            terms: None,
+            fractional: false,
+            created: None,
         };
         let contract = issue_contract(sk, request).await?;
         contracts.push(contract);
@@ -551,7 +567,7 @@ pub async fn create_new_invoice_v2(
     let seal = format!("tapret1st:{utxo}");
     let params = params.unwrap_or_default();
 
-    let mut amount = ContractAmount::from(amount.to_string(), precision);
+    let mut amount = ContractAmount::from(amount.to_string(), precision)?;
     amount.precision = precision;
 
     let invoice_req = InvoiceRequest {
@@ -560,6 +576,10 @@ pub async fn create_new_invoice_v2(
         amount: amount.to_string(),
         seal,
         params,
+        transports: vec![],
+        expire_at: None,
+        min_amount: None,
+        max_amount: None,
     };
 
     let resp = create_invoice(&sk, invoice_req).await?;
@@ -612,6 +632,7 @@ pub async fn create_new_psbt_v2(
         bitcoin_changes,
         fee: default_fee,
         rbf: true,
+        psbt_version: PsbtFormat::V0,
     };
 
     let resp = create_psbt(&sk, req).await?;
@@ -628,6 +649,7 @@ pub async fn create_new_transfer(
         psbt: psbt_resp.psbt,
         rgb_invoice: invoice_resp.invoice,
         terminal: psbt_resp.terminal,
+        amount: None,
     };
 
     let sk = owner_keys.private.nostr_prv.clone();
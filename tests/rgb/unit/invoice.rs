@@ -1,19 +1,59 @@
 #![cfg(not(target_arch = "wasm32"))]
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
 
-use amplify::{confinement::U32, hex::ToHex};
+use amplify::{
+    confinement::{U16, U32},
+    hex::{FromHex, ToHex},
+    Wrapper,
+};
 use bitmask_core::{
     rgb::{
-        consignment::NewTransferOptions,
-        transfer::{accept_transfer, create_invoice, pay_invoice},
+        consignment::{is_burn_seal, NewTransferOptions},
+        constants::{
+            RGB21_TOKEN_PARAM, RGB_INVOICE_MAX_AMOUNT_PARAM, RGB_INVOICE_MIN_AMOUNT_PARAM,
+        },
+        contract::{contract_burned_supply, contract_issued_supply},
+        internal_decode_invoice,
+        ledger::{record_invoice_payments, RgbInvoiceEntry, RgbInvoicePayment, RgbInvoices},
+        proxy::{transport_endpoints, unsupported_transport},
+        schemata::{
+            check_schema_rules, ifa_supply_is_valid, ifa_transition_is_valid, udc_schema,
+            udc_tokens_are_conserved, udc_transition_is_valid,
+        },
+        structs::{ContractAmount, ContractAmountError},
+        transfer::{
+            accept_transfer, check_invoice_amount, create_burn_invoice, create_invoice,
+            create_witness_invoice, fill_invoice_amount, invoice_amount_range, pay_invoice,
+            remove_seal_secrets, reveal_seal_secrets, transfer_invoice_amount, unused_seal_secrets,
+            validate_transfer, witness_address_vouts, AcceptTransferError, NewPaymentError,
+            SealSecrets,
+        },
     },
+    structs::{InvoiceBeneficiaryType, InvoiceRequest, InvoiceStatus},
     util::init_logging,
+    validators::{parse_invoice_transport, RGBContext, RGBParamsError},
+};
+use garde::Validate;
+use rgbstd::{
+    containers::Transfer,
+    contract::{ContractId, RevealedData, Transition},
+    interface::{
+        rgb20,
+        rgb21::{Allocation, OwnedFraction, TokenIndex},
+        TypedState,
+    },
+    persistence::{Inventory, Stash, Stock},
+    validation::ConsignmentApi,
 };
-use rgbstd::persistence::Stock;
-use strict_encoding::StrictSerialize;
+use rgbwallet::{Beneficiary, RgbInvoice, RgbTransport};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
 
 use crate::rgb::unit::utils::{
-    create_fake_contract, create_fake_invoice, create_fake_psbt, DumbResolve,
+    create_fake_collection, create_fake_contract, create_fake_inflatable_contract,
+    create_fake_invoice, create_fake_psbt, create_fake_uda, DumbResolve,
 };
 
 #[tokio::test]
@@ -35,9 +75,843 @@ async fn allow_create_invoice() -> anyhow::Result<()> {
         "regtest",
         params,
         &mut stock,
+        &mut SealSecrets::default(),
+    );
+
+    assert!(result.is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_create_witness_invoice() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let address = "bcrt1p76gtucrxhmn8s5622r859dpnmkj0kgfcel9xy0sz6yj84x6ppz2qk5hpsw";
+    let amount = 1;
+
+    let mut stock = Stock::default();
+    let params = HashMap::new();
+    let contract_id = create_fake_contract(&mut stock);
+    let result = create_witness_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        address,
+        "regtest",
+        params.clone(),
+        &mut stock,
+    );
+
+    assert!(result.is_ok());
+    assert!(matches!(
+        result.unwrap().beneficiary,
+        Beneficiary::WitnessUtxo(_)
+    ));
+
+    let result = create_witness_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        address,
+        "bitcoin",
+        params,
+        &mut stock,
+    );
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_invoice_transport_endpoints() -> anyhow::Result<()> {
+    let transports = vec![
+        RgbTransport::from_str("rpcs://proxy.host/json-rpc")?,
+        RgbTransport::from_str("rpc://localhost:3000")?,
+        RgbTransport::from_str("wss://relay.host")?,
+    ];
+
+    let endpoints = transport_endpoints(&transports);
+    assert_eq!(
+        endpoints,
+        vec![
+            "https://proxy.host".to_string(),
+            "http://localhost:3000".to_string()
+        ]
+    );
+    assert_eq!(
+        unsupported_transport(&transports),
+        Some("wss://relay.host".to_string())
+    );
+    assert_eq!(unsupported_transport(&transports[..2]), None);
+
+    let mut invoice_req = InvoiceRequest {
+        contract_id: String::new(),
+        iface: "RGB20".to_string(),
+        amount: "1".to_string(),
+        seal: "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0"
+            .to_string(),
+        params: HashMap::new(),
+        transports: vec!["nostr://relay.host".to_string()],
+        expire_at: None,
+        min_amount: None,
+        max_amount: None,
+    };
+    assert!(invoice_req.validate(&RGBContext::default()).is_err());
+
+    invoice_req.transports = vec!["wss://relay.host".to_string()];
+    assert!(invoice_req.validate(&RGBContext::default()).is_err());
+
+    assert_eq!(
+        parse_invoice_transport("nostr://relay.host"),
+        Err(RGBParamsError::NostrTransport(
+            "nostr://relay.host".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_invoice_transport("wss://relay.host"),
+        Err(RGBParamsError::UnsupportedTransport(
+            "wss://relay.host".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_invoice_transport("not a transport"),
+        Err(RGBParamsError::WrongTransport(
+            "not a transport".to_string()
+        ))
+    );
+
+    invoice_req.transports = vec!["rpcs://proxy.host/json-rpc".to_string()];
+    assert!(invoice_req.validate(&RGBContext::default()).is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_remove_unused_seal_secrets() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let amount = 1;
+
+    let mut stock = Stock::default();
+    let mut seal_secrets = SealSecrets::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let mut invoice = create_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        seal,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+        &mut seal_secrets,
+    )?;
+    invoice.expiry = Some(0);
+    assert!(stock.seal_secrets()?.is_empty());
+    assert_eq!(seal_secrets.secrets().len(), 1);
+
+    let unused = unused_seal_secrets(&[invoice.clone()], &seal_secrets, &mut stock)?;
+    assert_eq!(unused.len(), 1);
+
+    remove_seal_secrets(&[invoice], &mut seal_secrets)?;
+    assert!(seal_secrets.secrets().is_empty());
+    assert!(stock.contract_ids()?.contains(&contract_id));
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_track_invoice_payment_status() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let amount = 10;
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let mut invoice = create_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        seal,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+    invoice.expiry = Some(100);
+
+    let outpoint = seal
+        .split_once(':')
+        .map(|(_, outpoint)| outpoint.to_string());
+    let mut entry = RgbInvoiceEntry::with(&invoice, iface, amount, outpoint);
+    assert_eq!(entry.contract_id, contract_id.to_string());
+    assert_eq!(entry.status(50), InvoiceStatus::Open);
+    assert_eq!(entry.status(100), InvoiceStatus::Expired);
+
+    let payment = RgbInvoicePayment {
+        consig_id: "consig".to_string(),
+        txid: "ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e".to_string(),
+        amount: 4,
+    };
+    entry.payments.push(payment.clone());
+    assert_eq!(entry.status(100), InvoiceStatus::PartiallyPaid);
+
+    entry.payments.push(RgbInvoicePayment {
+        amount: 6,
+        ..payment.clone()
+    });
+    assert_eq!(entry.paid(), amount);
+    assert_eq!(entry.status(100), InvoiceStatus::Paid);
+
+    entry.payments.push(payment);
+    assert_eq!(entry.status(100), InvoiceStatus::Overpaid);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_record_invoice_payment_amount() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let amount = 4;
+
+    // The invoice seal is on the genesis outpoint, which already holds state
+    let seal = "tapret1st:5ca6cd1f54c081c8b3a7b4bcc988e55fe3c420ac87512b53a58c55233e15ba4f:1";
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let invoice = create_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        seal,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+
+    let outpoint = seal
+        .split_once(':')
+        .map(|(_, outpoint)| outpoint.to_string());
+    let mut rgb_invoices = RgbInvoices::default();
+    rgb_invoices.invoices.insert(
+        invoice.to_string(),
+        RgbInvoiceEntry::with(&invoice, iface, amount, outpoint),
+    );
+
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::default();
+    let (_, transfer) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer_hex = transfer[0].to_strict_serialized::<U32>().unwrap().to_hex();
+
+    let paid = record_invoice_payments(&transfer_hex, &mut rgb_invoices, &BTreeMap::new());
+    assert_eq!(paid, vec![invoice.to_string()]);
+
+    // The same consignment is recorded once
+    let paid = record_invoice_payments(&transfer_hex, &mut rgb_invoices, &BTreeMap::new());
+    assert!(paid.is_empty());
+
+    let entry = &rgb_invoices.invoices[&invoice.to_string()];
+    assert_eq!(entry.payments.len(), 1);
+    assert_eq!(entry.paid(), amount);
+    assert_eq!(entry.status(0), InvoiceStatus::Paid);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_record_witness_invoice_payment_amount() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let amount = 10;
+
+    // The address of the only output of the fake psbt
+    let address = "bcrt1p92jefmjdcpwj3yu8caayfm3a2sq60mwzd834tu35tsne9k0c6q2qe0vukk";
+    let script = "51202aa594ee4dc05d289387c77a44ee3d5401a7edc269e355f2345c2792d9f8d014";
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let invoice = create_witness_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        address,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+    )?;
+
+    let mut rgb_invoices = RgbInvoices::default();
+    rgb_invoices.invoices.insert(
+        invoice.to_string(),
+        RgbInvoiceEntry::with(&invoice, iface, amount, None),
+    );
+
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::default();
+    let (_, transfer) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer_hex = transfer[0].to_strict_serialized::<U32>().unwrap().to_hex();
+
+    // Without the witness output the payment is not matched
+    let paid = record_invoice_payments(&transfer_hex, &mut rgb_invoices, &BTreeMap::new());
+    assert!(paid.is_empty());
+
+    let witness_scripts = BTreeMap::from([(0, Vec::<u8>::from_hex(script)?)]);
+    let paid = record_invoice_payments(&transfer_hex, &mut rgb_invoices, &witness_scripts);
+    assert_eq!(paid, vec![invoice.to_string()]);
+
+    let entry = &rgb_invoices.invoices[&invoice.to_string()];
+    assert_eq!(entry.paid(), amount);
+    assert_eq!(entry.status(0), InvoiceStatus::Paid);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_witness_invoice_amount_out_of_range() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    // The address of the only output of the fake psbt
+    let address = "bcrt1p92jefmjdcpwj3yu8caayfm3a2sq60mwzd834tu35tsne9k0c6q2qe0vukk";
+    let script = "51202aa594ee4dc05d289387c77a44ee3d5401a7edc269e355f2345c2792d9f8d014";
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let mut invoice = create_witness_invoice(
+        &contract_id.to_string(),
+        "RGB20",
+        0,
+        address,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+    )?;
+    invoice.owned_state = TypedState::Void;
+    invoice
+        .unknown_query
+        .insert(RGB_INVOICE_MIN_AMOUNT_PARAM.to_string(), "5".to_string());
+    invoice
+        .unknown_query
+        .insert(RGB_INVOICE_MAX_AMOUNT_PARAM.to_string(), "10".to_string());
+
+    let mut outside = invoice.clone();
+    outside.owned_state = TypedState::Amount(3);
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::default();
+    let (_, transfer) = pay_invoice(outside.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer = transfer[0].clone().unbindle();
+
+    // The payment is only matched through the witness output paying the address
+    assert_eq!(transfer_invoice_amount(&transfer, &invoice, &[]), None);
+
+    let witness_scripts = BTreeMap::from([(0, Vec::<u8>::from_hex(script)?)]);
+    let witness_vouts = match &invoice.beneficiary {
+        Beneficiary::WitnessUtxo(address) => witness_address_vouts(address, &witness_scripts),
+        _ => vec![],
+    };
+    assert_eq!(witness_vouts, vec![0]);
+
+    let amount = transfer_invoice_amount(&transfer, &invoice, &witness_vouts);
+    assert_eq!(amount, Some(3));
+    assert!(!check_invoice_amount(&invoice, 3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_decode_full_invoice() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let amount = 1;
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let mut invoice = create_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        seal,
+        "regtest",
+        HashMap::from([("note".to_string(), "coffee".to_string())]),
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+    invoice.expiry = Some(1700000000);
+
+    let decoded = internal_decode_invoice(&invoice.to_string(), &mut stock)?;
+    assert_eq!(decoded.contract_id, contract_id.to_string());
+    assert_eq!(decoded.amount, amount);
+    assert_eq!(decoded.iface, Some(iface.to_string()));
+    assert_eq!(decoded.beneficiary_type, InvoiceBeneficiaryType::Blinded);
+    assert_eq!(decoded.chain, Some("regtest".to_string()));
+    assert_eq!(decoded.expiry, Some(1700000000));
+    assert_eq!(
+        decoded.unknown_query.get("note"),
+        Some(&"coffee".to_string())
+    );
+    assert!(decoded.transports.is_empty());
+    assert!(decoded.known_contract);
+    assert!(decoded.precision.is_some());
+    assert!(decoded.decimal_amount.is_some());
+
+    let decoded = internal_decode_invoice(&invoice.to_string(), &mut Stock::default())?;
+    assert!(!decoded.known_contract);
+    assert_eq!(decoded.ticker, None);
+    assert_eq!(decoded.decimal_amount, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_pay_open_amount_invoice() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let mut invoice = create_invoice(
+        &contract_id.to_string(),
+        iface,
+        0,
+        seal,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+    invoice.owned_state = TypedState::Void;
+    invoice
+        .unknown_query
+        .insert(RGB_INVOICE_MIN_AMOUNT_PARAM.to_string(), "5".to_string());
+    invoice
+        .unknown_query
+        .insert(RGB_INVOICE_MAX_AMOUNT_PARAM.to_string(), "10".to_string());
+
+    let invoice = RgbInvoice::from_str(&invoice.to_string())?;
+    assert_eq!(invoice_amount_range(&invoice), (Some(5), Some(10)));
+    assert!(check_invoice_amount(&invoice, 5));
+    assert!(!check_invoice_amount(&invoice, 11));
+
+    let open_invoice = invoice.to_string();
+    assert_eq!(
+        fill_invoice_amount(&open_invoice, None),
+        Err(NewPaymentError::NoAmount)
+    );
+    assert_eq!(
+        fill_invoice_amount(&open_invoice, Some(3)),
+        Err(NewPaymentError::WrongAmount(3))
+    );
+
+    let filled = RgbInvoice::from_str(&fill_invoice_amount(&open_invoice, Some(7))?)?;
+    assert!(matches!(filled.owned_state, TypedState::Amount(7)));
+    assert!(check_invoice_amount(&filled, 7));
+    assert!(!check_invoice_amount(&filled, 8));
+
+    // The payee checks the amount received against the range of the open invoice
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::default();
+    let (_, transfer) = pay_invoice(filled.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer_hex = transfer[0].to_strict_serialized::<U32>().unwrap().to_hex();
+    let result = validate_transfer(transfer_hex, &[invoice.clone()], &mut DumbResolve {});
+    assert!(result.is_ok());
+
+    let mut outside = invoice.clone();
+    outside.owned_state = TypedState::Amount(3);
+    let options = NewTransferOptions::default();
+    let (_, transfer) = pay_invoice(outside.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer_hex = transfer[0].to_strict_serialized::<U32>().unwrap().to_hex();
+    let result = validate_transfer(transfer_hex, &[invoice], &mut DumbResolve {});
+    assert!(matches!(result, Err(AcceptTransferError::InvalidConsig(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_wrong_contract_amount() -> anyhow::Result<()> {
+    assert_eq!(ContractAmount::from("1.5".to_string(), 2)?.to_value(), 150);
+    assert_eq!(ContractAmount::from("7".to_string(), 2)?.to_value(), 700);
+    assert_eq!(
+        ContractAmount::from_raw("1.100".to_string())?.to_value(),
+        1100
+    );
+
+    for amount in [
+        "",
+        "abc",
+        "1.2.3",
+        "-1",
+        ".5",
+        "1,5",
+        "18446744073709551616",
+    ] {
+        assert_eq!(
+            ContractAmount::from_raw(amount.to_string()).err(),
+            Some(ContractAmountError::WrongAmount(amount.to_string()))
+        );
+    }
+
+    // The contract value would not fit in u64
+    let amount = "184467440737095516.16".to_string();
+    assert!(ContractAmount::from(amount, 2).is_err());
+
+    let amount = "1.123".to_string();
+    assert_eq!(
+        ContractAmount::from(amount.clone(), 2).err(),
+        Some(ContractAmountError::WrongPrecision(amount, 2))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_create_burn_invoice() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let iface = "RGB20";
+    let amount = 1;
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let result = create_burn_invoice(
+        &contract_id.to_string(),
+        iface,
+        amount,
+        "regtest",
+        &mut stock,
     );
 
     assert!(result.is_ok());
+    assert_eq!(
+        result.unwrap().operation.map(|op| op.to_string()),
+        Some("Burn".to_string())
+    );
+    Ok(())
+}
+
+fn transfer_transitions(transfer: &Transfer) -> Vec<Transition> {
+    transfer
+        .bundles
+        .iter()
+        .flat_map(|anchored_bundle| {
+            transfer
+                .known_transitions_by_bundle_id(anchored_bundle.bundle.bundle_id())
+                .into_iter()
+                .flatten()
+                .cloned()
+        })
+        .collect()
+}
+
+fn burned_by_transition(transition: &Transition) -> u64 {
+    let mut burned = 0;
+    for (_, assigns) in transition.assignments.iter() {
+        for idx in 0..assigns.to_confidential_seals().len() as u16 {
+            if !matches!(assigns.revealed_seal_at(idx), Ok(Some(seal)) if is_burn_seal(&seal)) {
+                continue;
+            }
+            if let Ok(Some(reveal)) = assigns.as_fungible_state_at(idx) {
+                burned += reveal.value.as_u64();
+            }
+        }
+    }
+    burned
+}
+
+#[tokio::test]
+async fn allow_burn_fungible_contract() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut stock = Stock::default();
+    let psbt = create_fake_psbt();
+    let contract_id = create_fake_contract(&mut stock);
+
+    let invoice = create_burn_invoice(&contract_id.to_string(), "RGB20", 4, "regtest", &mut stock)?;
+    let options = NewTransferOptions {
+        burn: true,
+        ..Default::default()
+    };
+    let (_, transfers) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+
+    // NIA has no burn transition, the amount is burned through a plain transfer
+    let transfer = transfers[0].clone().unbindle();
+    let transitions = transfer_transitions(&transfer);
+    assert_eq!(transitions.len(), 1);
+    assert!(transitions[0].globals.is_empty());
+    assert_eq!(burned_by_transition(&transitions[0]), 4);
+    assert!(ifa_supply_is_valid(&transfer));
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_burn_inflatable_contract() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut stock = Stock::default();
+    let psbt = create_fake_psbt();
+    let contract_id = create_fake_inflatable_contract(&mut stock);
+
+    let invoice = create_burn_invoice(&contract_id.to_string(), "RGB20", 4, "regtest", &mut stock)?;
+    let options = NewTransferOptions {
+        burn: true,
+        ..Default::default()
+    };
+    let (_, transfers) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+
+    let transfer = transfers[0].clone().unbindle();
+    let transitions = transfer_transitions(&transfer);
+    assert_eq!(transitions.len(), 1);
+    assert!(!transitions[0].globals.is_empty());
+    assert_eq!(burned_by_transition(&transitions[0]), 4);
+    assert!(ifa_supply_is_valid(&transfer));
+    assert!(check_schema_rules(&transfer).is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_issued_supply_after_burn_and_replace() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut resolver = DumbResolve {};
+    let mut stock = Stock::default();
+    let psbt = create_fake_psbt();
+    let contract_id = create_fake_inflatable_contract(&mut stock);
+
+    let burn = create_burn_invoice(&contract_id.to_string(), "RGB20", 4, "regtest", &mut stock)?;
+    let options = NewTransferOptions {
+        burn: true,
+        ..Default::default()
+    };
+    let (_, burn) = pay_invoice(burn.to_string(), psbt.to_string(), options, &mut stock)?;
+
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let replace = create_fake_invoice(contract_id, seal, &mut stock, &mut SealSecrets::default());
+    let options = NewTransferOptions {
+        replace: true,
+        ..Default::default()
+    };
+    let (_, replace) = pay_invoice(replace.to_string(), psbt.to_string(), options, &mut stock)?;
+
+    // Both operations spend the genesis allocations, so they are accepted after paying
+    for transfer in [&burn[0], &replace[0]] {
+        let transfer_hex = transfer.to_strict_serialized::<U32>().unwrap().to_hex();
+        accept_transfer(transfer_hex, true, &mut resolver, &mut stock)?;
+    }
+
+    // The replaced supply is issued again and recorded as burned, so the
+    // circulating supply only drops by the burned amount
+    let contract_iface = stock
+        .contract_iface(contract_id, rgb20().iface_id())
+        .expect("invalid contract iface");
+    let issued = contract_issued_supply(&contract_iface).expect("issued supply");
+    let burned = contract_burned_supply(&contract_iface);
+    assert_eq!(issued, 10 + 1);
+    assert_eq!(burned, 4 + 1);
+    assert_eq!(issued - burned, 10 - 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_wrong_burned_supply() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut stock = Stock::default();
+    let psbt = create_fake_psbt();
+    let contract_id = create_fake_inflatable_contract(&mut stock);
+
+    let invoice = create_burn_invoice(&contract_id.to_string(), "RGB20", 4, "regtest", &mut stock)?;
+    let options = NewTransferOptions {
+        burn: true,
+        ..Default::default()
+    };
+    let (_, transfers) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+
+    let transfer = transfers[0].clone().unbindle();
+    let burn = transfer_transitions(&transfer).remove(0);
+    assert!(ifa_transition_is_valid(&burn));
+
+    // Record the burned supply twice, so it exceeds the burned assets
+    let mut tampered = burn.clone();
+    let (ty, value) = tampered
+        .globals
+        .iter()
+        .next()
+        .map(|(ty, values)| (*ty, values[0].clone()))
+        .expect("burned supply");
+    tampered.globals.add_state(ty, value)?;
+    assert!(!ifa_transition_is_valid(&tampered));
+    Ok(())
+}
+
+fn collection_tokens(transition: &Transition) -> Vec<(u32, u64)> {
+    let mut tokens = vec![];
+    for (_, assigns) in transition.assignments.iter() {
+        for idx in 0..assigns.to_confidential_seals().len() as u16 {
+            if let Ok(Some(data)) = assigns.as_structured_state_at(idx) {
+                let allocation = Allocation::from_strict_serialized(data.as_ref().to_owned())
+                    .expect("invalid allocation");
+                tokens.push((
+                    allocation.token_id().into_inner(),
+                    allocation.fraction().into_inner(),
+                ));
+            }
+        }
+    }
+    tokens.sort();
+    tokens
+}
+
+fn pay_uda_token(
+    stock: &mut Stock,
+    contract_id: ContractId,
+    token: u32,
+    amount: u64,
+) -> anyhow::Result<Transfer> {
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let params = HashMap::from([(RGB21_TOKEN_PARAM.to_string(), token.to_string())]);
+    let invoice = create_invoice(
+        &contract_id.to_string(),
+        "RGB21",
+        amount,
+        seal,
+        "regtest",
+        params,
+        stock,
+        &mut SealSecrets::default(),
+    )?;
+
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::default();
+    let (_, transfers) = pay_invoice(invoice.to_string(), psbt.to_string(), options, stock)?;
+    Ok(transfers[0].clone().unbindle())
+}
+
+#[tokio::test]
+async fn allow_transfer_collection_token() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_collection(&mut stock, 10);
+    let transfer = pay_uda_token(&mut stock, contract_id, 1, 4)?;
+
+    // The requested fraction of the token is split, the other tokens are kept by the owner
+    let transitions = transfer_transitions(&transfer);
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(
+        collection_tokens(&transitions[0]),
+        vec![(0, 10), (1, 4), (1, 6), (2, 10)]
+    );
+    assert!(udc_tokens_are_conserved(&transfer));
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_transfer_full_uda() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut resolver = DumbResolve {};
+    let mut stock = Stock::default();
+    let contract_id = create_fake_uda(&mut stock, 1);
+    let transfer = pay_uda_token(&mut stock, contract_id, 0, 1)?;
+
+    let transitions = transfer_transitions(&transfer);
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(collection_tokens(&transitions[0]), vec![(0, 1)]);
+    assert_ne!(transfer.genesis.schema_id, udc_schema().schema_id());
+    assert!(transfer.validate(&mut resolver).is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_transfer_fractional_uda() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut resolver = DumbResolve {};
+    let mut stock = Stock::default();
+    let contract_id = create_fake_uda(&mut stock, 10);
+    let transfer = pay_uda_token(&mut stock, contract_id, 0, 4)?;
+
+    // Divisible UDAs are issued as a one-token collection, so the token can be split
+    let transitions = transfer_transitions(&transfer);
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(collection_tokens(&transitions[0]), vec![(0, 4), (0, 6)]);
+    assert_eq!(transfer.genesis.schema_id, udc_schema().schema_id());
+    assert!(udc_tokens_are_conserved(&transfer));
+    assert!(transfer.validate(&mut resolver).is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_unconserved_collection_transfer() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut stock = Stock::default();
+    let contract_id = create_fake_collection(&mut stock, 10);
+    let transfer = pay_uda_token(&mut stock, contract_id, 1, 4)?;
+    let transition = transfer_transitions(&transfer).remove(0);
+    assert!(udc_transition_is_valid(&transfer, &transition));
+
+    // Assign the fractions of a larger collection while spending the original tokens
+    let mut other_stock = Stock::default();
+    let other_id = create_fake_collection(&mut other_stock, 20);
+    let other = pay_uda_token(&mut other_stock, other_id, 1, 4)?;
+    let other = transfer_transitions(&other).remove(0);
+
+    let mut tampered = transition.clone();
+    tampered.assignments = other.assignments;
+    assert!(!udc_transition_is_valid(&transfer, &tampered));
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_inflating_collection_transfer() -> anyhow::Result<()> {
+    init_logging("rgb_invoice=warn");
+
+    let mut resolver = DumbResolve {};
+    let mut stock = Stock::default();
+    let contract_id = create_fake_collection(&mut stock, 10);
+
+    let params = HashMap::from([(RGB21_TOKEN_PARAM.to_string(), "1".to_string())]);
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let invoice = create_invoice(
+        &contract_id.to_string(),
+        "RGB21",
+        4,
+        seal,
+        "regtest",
+        params.clone(),
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+
+    // A second beneficiary receives the whole token again, without spending it
+    let other_seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:1";
+    let mut other = create_invoice(
+        &contract_id.to_string(),
+        "RGB21",
+        1,
+        other_seal,
+        "regtest",
+        params,
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+    let allocation = Allocation::with(TokenIndex::from_inner(1), OwnedFraction::from_inner(10));
+    other.owned_state = TypedState::Data(RevealedData::from(
+        allocation.to_strict_serialized::<U16>()?,
+    ));
+
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::with(false, vec![other]);
+    let (_, transfers) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer = transfers[0].clone().unbindle();
+    assert!(!udc_tokens_are_conserved(&transfer));
+
+    let transfer_hex = transfers[0].to_strict_serialized::<U32>().unwrap().to_hex();
+    let result = validate_transfer(transfer_hex.clone(), &[], &mut resolver);
+    assert!(matches!(result, Err(AcceptTransferError::InvalidConsig(_))));
+
+    let result = accept_transfer(transfer_hex, true, &mut resolver, &mut stock);
+    assert!(matches!(result, Err(AcceptTransferError::InvalidConsig(_))));
     Ok(())
 }
 
@@ -52,7 +926,7 @@ async fn allow_pay_invoice() -> anyhow::Result<()> {
     let contract_id = create_fake_contract(&mut stock);
 
     let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
-    let invoice = create_fake_invoice(contract_id, seal, &mut stock);
+    let invoice = create_fake_invoice(contract_id, seal, &mut stock, &mut SealSecrets::default());
 
     let options = NewTransferOptions::default();
     let result = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock);
@@ -77,7 +951,8 @@ async fn allow_accept_invoice() -> anyhow::Result<()> {
     let contract_id = create_fake_contract(&mut stock);
 
     let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
-    let invoice = create_fake_invoice(contract_id, seal, &mut stock);
+    let mut seal_secrets = SealSecrets::default();
+    let invoice = create_fake_invoice(contract_id, seal, &mut stock, &mut seal_secrets);
 
     let options = NewTransferOptions::default();
     let result = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock);
@@ -87,6 +962,9 @@ async fn allow_accept_invoice() -> anyhow::Result<()> {
     let transfer = &transfer[0];
     let transfer_hex = transfer.to_strict_serialized::<U32>().unwrap().to_hex();
 
+    reveal_seal_secrets(&transfer_hex, &seal_secrets, &mut stock)?;
+    assert_eq!(stock.seal_secrets()?, seal_secrets.secrets());
+
     let pay_status = accept_transfer(transfer_hex, true, &mut resolver, &mut stock);
     assert!(pay_status.is_ok());
     Ok(())
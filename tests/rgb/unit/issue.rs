@@ -1,9 +1,19 @@
 #![cfg(not(target_arch = "wasm32"))]
 use anyhow::Result;
 use bitmask_core::{
-    rgb::issue::issue_contract, structs::IssueRequest, util::init_logging, validators::RGBContext,
+    rgb::{
+        issue::{issue_contract, IssueContractError, IssueError},
+        schemata::{ifa_schema, udc_schema},
+    },
+    structs::{
+        InflationAllowanceRequest, IssueBeneficiaryRequest, IssueRequest, IssueTermsRequest,
+        MediaInfo,
+    },
+    util::init_logging,
+    validators::RGBContext,
 };
 use garde::Validate;
+use rgb_schemata::{cfa_schema, nia_schema, uda_schema};
 use rgbstd::persistence::Stock;
 
 use crate::rgb::unit::utils::{get_uda_data, DumbResolve};
@@ -31,6 +41,12 @@ async fn issue_request_params_check() -> Result<()> {
         seal: seal.to_string(),
         iface: iface.to_string(),
         meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
     assert!(rgb20.validate(ctx).is_ok());
 
@@ -43,6 +59,12 @@ async fn issue_request_params_check() -> Result<()> {
         seal: seal.to_string(),
         iface: iface.to_string(),
         meta: Some(get_uda_data()),
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
     assert!(rgb21.validate(ctx).is_ok());
 
@@ -76,6 +98,12 @@ async fn issue_contract_test() -> Result<()> {
         seal,
         network,
         None,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        false,
+        None,
         &mut resolver,
         &mut stock,
     );
@@ -83,3 +111,500 @@ async fn issue_contract_test() -> Result<()> {
     assert!(contract.is_ok());
     Ok(())
 }
+
+#[tokio::test]
+async fn issue_inflatable_contract_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let ticker = "DIBA";
+    let name = "DIBA";
+    let description =
+        "1 2 3 testing... 1 2 3 testing... 1 2 3 testing... 1 2 3 testing.... 1 2 3 testing";
+    let precision = 8;
+    let supply = 10;
+    let iface = "RGB20";
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let network = "regtest";
+    let inflation = vec![InflationAllowanceRequest {
+        seal: "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:1"
+            .to_string(),
+        amount: 90,
+    }];
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        ticker,
+        name,
+        description,
+        precision,
+        supply,
+        iface,
+        seal,
+        network,
+        None,
+        inflation,
+        vec![],
+        vec![],
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    )
+    .expect("test inflatable issue_contract failed");
+
+    assert_eq!(contract.schema_id(), ifa_schema().schema_id());
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_distributed_contract_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let ticker = "DIBA";
+    let name = "DIBA";
+    let description =
+        "1 2 3 testing... 1 2 3 testing... 1 2 3 testing... 1 2 3 testing.... 1 2 3 testing";
+    let precision = 8;
+    let supply = 10;
+    let iface = "RGB20";
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let network = "regtest";
+    let beneficiaries = vec![
+        IssueBeneficiaryRequest {
+            seal: "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:1"
+                .to_string(),
+            amount: 4,
+        },
+        IssueBeneficiaryRequest {
+            seal: "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:2"
+                .to_string(),
+            amount: 6,
+        },
+    ];
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        ticker,
+        name,
+        description,
+        precision,
+        supply,
+        iface,
+        seal,
+        network,
+        None,
+        vec![],
+        vec![],
+        beneficiaries,
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    )
+    .expect("test distributed issue_contract failed");
+
+    assert_eq!(contract.schema_id(), nia_schema().schema_id());
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_distributed_contract_wrong_supply_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let beneficiaries = vec![IssueBeneficiaryRequest {
+        seal: "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:1"
+            .to_string(),
+        amount: 4,
+    }];
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let result = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        8,
+        10,
+        "RGB20",
+        seal,
+        "regtest",
+        None,
+        vec![],
+        vec![],
+        beneficiaries,
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+
+    assert!(matches!(
+        result,
+        Err(IssueContractError::WrongBeneficiaries(4, 10))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_distributed_contract_wrong_seal_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let beneficiary = |seal: &str, amount: u64| IssueBeneficiaryRequest {
+        seal: seal.to_string(),
+        amount,
+    };
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    // The amounts overflow before they can match the supply
+    let beneficiaries = vec![beneficiary(seal, u64::MAX), beneficiary(seal, 1)];
+    let result = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        8,
+        10,
+        "RGB20",
+        seal,
+        "regtest",
+        None,
+        vec![],
+        vec![],
+        beneficiaries,
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(matches!(
+        result,
+        Err(IssueContractError::Issue(IssueError::SupplyOverflow))
+    ));
+
+    let beneficiaries = vec![beneficiary("tapret1st:wrong", 10)];
+    let result = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        8,
+        10,
+        "RGB20",
+        seal,
+        "regtest",
+        None,
+        vec![],
+        vec![],
+        beneficiaries,
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(matches!(
+        result,
+        Err(IssueContractError::Issue(IssueError::WrongSeal(_)))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_contract_with_terms_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let ticker = "DIBA";
+    let name = "DIBA";
+    let description = "DIBA";
+    let precision = 8;
+    let supply = 10;
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let network = "regtest";
+    let terms = IssueTermsRequest {
+        text: "1 2 3 testing... 1 2 3 testing... 1 2 3 testing...".to_string(),
+        media: Some(MediaInfo {
+            ty: "application/pdf".to_string(),
+            source: "5ca7815adcb484e9a136c11efe69c1a530176a549b5d8b8b9b8c9b4d6a6b6a6b".to_string(),
+        }),
+    };
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        ticker,
+        name,
+        description,
+        precision,
+        supply,
+        "RGB20",
+        seal,
+        network,
+        None,
+        vec![],
+        vec![],
+        vec![],
+        Some(terms.clone()),
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(contract.is_ok());
+
+    // The terms document is committed as contract media, so RGB21 cannot attach it
+    let contract = issue_contract(
+        ticker,
+        name,
+        description,
+        0,
+        1,
+        "RGB21",
+        seal,
+        network,
+        Some(get_uda_data()),
+        vec![],
+        vec![],
+        vec![],
+        Some(terms),
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(contract.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_contract_with_wrong_digest_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let terms = IssueTermsRequest {
+        text: "1 2 3 testing... 1 2 3 testing... 1 2 3 testing...".to_string(),
+        media: Some(MediaInfo {
+            ty: "application/pdf".to_string(),
+            source: "5ca7815adcb484e9".to_string(),
+        }),
+    };
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        8,
+        10,
+        "RGB20",
+        seal,
+        "regtest",
+        None,
+        vec![],
+        vec![],
+        vec![],
+        Some(terms),
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(matches!(
+        contract,
+        Err(IssueContractError::Issue(IssueError::WrongDigest(8)))
+    ));
+
+    // The token media digest is checked as well
+    let mut meta = get_uda_data();
+    meta.media = Some(MediaInfo {
+        ty: "image/png".to_string(),
+        source: "b9bf23a30bedcc4c".to_string(),
+    });
+    let contract = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        0,
+        1,
+        "RGB21",
+        seal,
+        "regtest",
+        Some(meta),
+        vec![],
+        vec![],
+        vec![],
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(matches!(
+        contract,
+        Err(IssueContractError::Issue(IssueError::WrongDigest(8)))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_collectible_contract_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let ticker = "DIBA";
+    let name = "DIBA";
+    let description =
+        "1 2 3 testing... 1 2 3 testing... 1 2 3 testing... 1 2 3 testing.... 1 2 3 testing";
+    let precision = 2;
+    let supply = 10;
+    let iface = "RGB25";
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let network = "regtest";
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        ticker,
+        name,
+        description,
+        precision,
+        supply,
+        iface,
+        seal,
+        network,
+        None,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    )
+    .expect("test collectible issue_contract failed");
+
+    assert_eq!(contract.schema_id(), cfa_schema().schema_id());
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_collection_contract_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let ticker = "DIBA";
+    let name = "DIBA";
+    let description =
+        "1 2 3 testing... 1 2 3 testing... 1 2 3 testing... 1 2 3 testing.... 1 2 3 testing";
+    let precision = 0;
+    let supply = 1;
+    let iface = "RGB21";
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+    let network = "regtest";
+    let collection = vec![get_uda_data(), get_uda_data(), get_uda_data()];
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        ticker,
+        name,
+        description,
+        precision,
+        supply,
+        iface,
+        seal,
+        network,
+        None,
+        vec![],
+        collection,
+        vec![],
+        None,
+        false,
+        None,
+        &mut resolver,
+        &mut stock,
+    )
+    .expect("test collection issue_contract failed");
+
+    assert_eq!(contract.schema_id(), udc_schema().schema_id());
+    Ok(())
+}
+
+#[tokio::test]
+async fn issue_fractional_contract_test() -> Result<()> {
+    init_logging("rgb_issue=warn");
+
+    let description =
+        "1 2 3 testing... 1 2 3 testing... 1 2 3 testing... 1 2 3 testing.... 1 2 3 testing";
+    let seal = "tapret1st:70339a6b27f55105da2d050babc759f046c21c26b7b75e9394bc1d818e50ff52:0";
+
+    let mut stock = Stock::default();
+    let mut resolver = DumbResolve {};
+
+    // Many units of an UDA are only issued as a collection when asked for
+    for (fractional, schema_id) in [
+        (false, uda_schema().schema_id()),
+        (true, udc_schema().schema_id()),
+    ] {
+        let contract = issue_contract(
+            "DIBA",
+            "DIBA",
+            description,
+            0,
+            10,
+            "RGB21",
+            seal,
+            "regtest",
+            Some(get_uda_data()),
+            vec![],
+            vec![],
+            vec![],
+            None,
+            fractional,
+            None,
+            &mut resolver,
+            &mut stock,
+        )
+        .expect("test fractional issue_contract failed");
+        assert_eq!(contract.schema_id(), schema_id);
+    }
+
+    let result = issue_contract(
+        "DIBA",
+        "DIBA",
+        description,
+        0,
+        10,
+        "RGB20",
+        seal,
+        "regtest",
+        None,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        true,
+        None,
+        &mut resolver,
+        &mut stock,
+    );
+    assert!(matches!(
+        result,
+        Err(IssueContractError::NoFractionalSupport(_))
+    ));
+    Ok(())
+}
@@ -2,15 +2,19 @@
 use crate::rgb::unit::utils::{
     create_fake_contract, create_fake_invoice, create_fake_psbt, DumbResolve,
 };
+use amplify::hex::ToHex;
+use bitcoin::psbt::PartiallySignedTransaction;
 use bitmask_core::{
+    bitcoin::{convert_psbt, decode_psbt},
     rgb::{
         consignment::NewTransferOptions,
         psbt::{create_psbt, extract_output_commit, NewPsbtOptions},
-        transfer::pay_invoice,
+        transfer::{pay_invoice, SealSecrets},
     },
-    structs::{PsbtInputRequest, SecretString},
+    structs::{PsbtFormat, PsbtInputRequest, SecretString},
     util::init_logging,
 };
+use psbt::serialize::Serialize;
 use rgb::persistence::Stock;
 
 #[tokio::test]
@@ -52,7 +56,7 @@ async fn allow_extract_output_commit_from_psbt() -> anyhow::Result<()> {
     let contract_id = create_fake_contract(&mut stock);
 
     let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
-    let invoice = create_fake_invoice(contract_id, seal, &mut stock);
+    let invoice = create_fake_invoice(contract_id, seal, &mut stock, &mut SealSecrets::default());
 
     let options = NewTransferOptions::default();
     let result = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock);
@@ -64,3 +68,23 @@ async fn allow_extract_output_commit_from_psbt() -> anyhow::Result<()> {
     assert!(commit.is_ok());
     Ok(())
 }
+
+#[tokio::test]
+async fn allow_convert_psbt_file_between_versions() -> anyhow::Result<()> {
+    let psbt = create_fake_psbt();
+    let psbt_v0 = Serialize::serialize(&psbt).to_hex();
+
+    let psbt_v2 = convert_psbt(&psbt_v0, PsbtFormat::V2)?;
+    let (_, version) = decode_psbt(&psbt_v2)?;
+    assert_eq!(version, PsbtFormat::V2);
+
+    let psbt_back = convert_psbt(&psbt_v2, PsbtFormat::V0)?;
+    let (psbt_back, version) = decode_psbt(&psbt_back)?;
+    assert_eq!(version, PsbtFormat::V0);
+    assert_eq!(
+        PartiallySignedTransaction::from(psbt_back).unsigned_tx,
+        PartiallySignedTransaction::from(psbt).unsigned_tx
+    );
+
+    Ok(())
+}
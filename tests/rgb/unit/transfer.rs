@@ -0,0 +1,405 @@
+#![cfg(not(target_arch = "wasm32"))]
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use amplify::hex::ToHex;
+use bitmask_core::{
+    rgb::{
+        consignment::NewTransferOptions,
+        crdt::{RawRgbWallet, RawUtxo},
+        exclude_reorged_allocations, exclude_reorged_details, is_reorged_utxo, parse_airdrop_csv,
+        psbt::remove_tap_commit_str,
+        receiver::{is_receiving, start_receiver, stop_receiver},
+        report::{rejection_report, validation_report},
+        structs::{RgbAirdrops, RgbTransferAnchor, RgbTransferLifecycle, RgbTransfersV2},
+        transfer::{create_invoice, pay_invoice, SealSecrets},
+    },
+    structs::{
+        AllocationDetail, AllocationValue, ContractFormats, ContractResponse, GenesisFormats,
+        RgbReceiverRequest, RgbReorgRequest, RgbValidationLevel, TransferState, WatcherDetail,
+    },
+};
+use bp::Txid;
+use rgb::RgbWallet;
+use rgbstd::{
+    persistence::Stock,
+    validation::{Failure, Status, Warning},
+};
+
+use crate::rgb::unit::utils::{create_fake_contract, create_fake_psbt};
+
+#[tokio::test]
+async fn allow_move_transfer_lifecycle() -> anyhow::Result<()> {
+    let mut lifecycle = RgbTransferLifecycle::new(100);
+    assert_eq!(lifecycle.state, TransferState::Draft);
+
+    lifecycle.move_to(TransferState::Mempool, 110);
+    lifecycle.move_to(TransferState::Confirmed(1), 120);
+    lifecycle.move_to(TransferState::Confirmed(3), 130);
+    assert_eq!(lifecycle.state, TransferState::Confirmed(3));
+    assert_eq!(lifecycle.updated_at, 130);
+    assert_eq!(lifecycle.history.len(), 3);
+
+    lifecycle.fail("invalid consignment".to_string(), 140);
+    assert_eq!(lifecycle.state, TransferState::Failed);
+    assert_eq!(lifecycle.error, Some("invalid consignment".to_string()));
+
+    lifecycle.move_to(TransferState::Accepted, 150);
+    lifecycle.move_to(TransferState::Replaced, 160);
+    lifecycle.fail("too late".to_string(), 170);
+    assert_eq!(lifecycle.state, TransferState::Accepted);
+    assert_eq!(lifecycle.error, None);
+    assert_eq!(lifecycle.updated_at, 150);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_filter_transfer_state() -> anyhow::Result<()> {
+    let state = TransferState::from_str("confirmed").expect("valid state");
+    assert!(TransferState::Confirmed(6).is(&state));
+    assert!(!TransferState::Mempool.is(&state));
+    assert!(TransferState::from_str("unknown").is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_cancel_only_unbroadcast_transfers() -> anyhow::Result<()> {
+    let mut lifecycle = RgbTransferLifecycle::new(100);
+    assert!(lifecycle.is_cancellable());
+
+    lifecycle.move_to(TransferState::Signed, 110);
+    assert!(lifecycle.is_cancellable());
+
+    lifecycle.move_to(TransferState::Broadcast, 120);
+    assert!(!lifecycle.is_cancellable());
+
+    lifecycle.move_to(TransferState::Mempool, 130);
+    assert!(!lifecycle.is_cancellable());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_release_unpublished_witness_outputs() -> anyhow::Result<()> {
+    let txid = "1ded068d67c5c16d8ed0c551502c855a5242f22eda06e1e129b396afb3ace9f3";
+    let raw_wallet = RawRgbWallet {
+        xpub: "tpubDCBwP45jcvCdTBZSxn8TcCyQGx5YgietksRRptV9YJ1xnom6edMwb2JcBnNU15t6TmotHETmgnvHQ2Nki7N7CsgFhka6D91UgMaEYpTRuSh".to_string(),
+        taprets: BTreeMap::from([(
+            "20:1".to_string(),
+            vec![
+                "f61p)BbC*-|AJ2W9oM?q<<_s?&Jb6v-+EsVd#`pU00".to_string(),
+                "?~iw^DhjX<zcgK-Ju5wgKGY*I%v~qSVUQv&0?iu000".to_string(),
+            ],
+        )]),
+        utxos: vec![
+            RawUtxo {
+                outpoint: "38de2eb14e917a066cda3283a9409ca4742427b00b070bda055cb82334d2d309:1"
+                    .to_string(),
+                block: 904,
+                amount: 1000,
+                terminal: "20:0".to_string(),
+                tweak: None,
+            },
+            RawUtxo {
+                outpoint: "866c629038b8cca56908f5989a4a2dde7942b41f3093212980c1ce388207ddf0:0"
+                    .to_string(),
+                block: 901,
+                amount: 1000,
+                terminal: "20:0".to_string(),
+                tweak: None,
+            },
+            RawUtxo {
+                outpoint: format!("{txid}:0"),
+                block: 0,
+                amount: 10000000,
+                terminal: "20:1".to_string(),
+                tweak: Some("f61p)BbC*-|AJ2W9oM?q<<_s?&Jb6v-+EsVd#`pU00".to_string()),
+            },
+            RawUtxo {
+                outpoint: "adddea20b187cce1f03c45f7f8023f88541cfdd24725bcf9bbfd9472ea512548:0"
+                    .to_string(),
+                block: 0,
+                amount: 10000000,
+                terminal: "20:1".to_string(),
+                tweak: Some("?~iw^DhjX<zcgK-Ju5wgKGY*I%v~qSVUQv&0?iu000".to_string()),
+            },
+        ],
+    };
+    let mut rgb_wallet = RgbWallet::from(raw_wallet);
+
+    remove_tap_commit_str(txid, &mut rgb_wallet);
+
+    let raw_wallet = RawRgbWallet::from(rgb_wallet);
+    assert_eq!(raw_wallet.utxos.len(), 3);
+    assert!(!raw_wallet
+        .utxos
+        .iter()
+        .any(|x| x.outpoint.starts_with(txid)));
+    assert_eq!(
+        raw_wallet.taprets.get("20:1"),
+        Some(&vec![
+            "?~iw^DhjX<zcgK-Ju5wgKGY*I%v~qSVUQv&0?iu000".to_string()
+        ])
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_start_and_stop_receiver() -> anyhow::Result<()> {
+    let sk = "e8e8a5c0f7e3f2a4d1b6c9d0a3b7e5f1c2d4a6b8e0f2a4c6d8e0a2b4c6d8e0f2";
+
+    assert!(start_receiver(sk).await);
+    assert!(!start_receiver(sk).await);
+    assert!(is_receiving(sk).await);
+
+    assert!(stop_receiver(sk).await);
+    assert!(!is_receiving(sk).await);
+    assert!(!stop_receiver(sk).await);
+
+    let request: RgbReceiverRequest = serde_json::from_str("{}")?;
+    assert_eq!(request.min_confirmations, 1);
+    assert_eq!(request.interval, 60);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_explain_rejected_consignment() -> anyhow::Result<()> {
+    let report = rejection_report("witness transaction is not mined".to_string());
+
+    assert!(!report.valid);
+    assert_eq!(report.items.len(), 1);
+
+    let item = &report.items[0];
+    assert_eq!(item.level, RgbValidationLevel::Failure);
+    assert_eq!(item.code, "Rejected");
+    assert!(item.operation_id.is_none());
+    assert!(report.to_string().contains(&item.hint));
+
+    let json = serde_json::to_string(&report)?;
+    assert!(json.contains("\"level\":\"failure\""));
+    assert!(json.contains("\"anchorTxid\":null"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_explain_validation_failures() -> anyhow::Result<()> {
+    let seal = "tapret1st:ed823b41d8b9309933826b18e4af530363b359f05919c02bbe72f28cec6dec3e:0";
+    let mut stock = Stock::default();
+    let contract_id = create_fake_contract(&mut stock);
+    let invoice = create_invoice(
+        &contract_id.to_string(),
+        "RGB20",
+        1,
+        seal,
+        "regtest",
+        HashMap::new(),
+        &mut stock,
+        &mut SealSecrets::default(),
+    )?;
+
+    let psbt = create_fake_psbt();
+    let options = NewTransferOptions::default();
+    let (_, transfers) = pay_invoice(invoice.to_string(), psbt.to_string(), options, &mut stock)?;
+    let transfer = transfers[0].clone().unbindle();
+    let opid = transfer.genesis.id();
+    let txid = transfer
+        .bundles
+        .iter()
+        .map(|anchored_bundle| anchored_bundle.anchor.txid)
+        .next()
+        .expect("transfer without anchor");
+
+    let mut status = Status::default();
+    status.failures.push(Failure::ScriptFailure(opid));
+    status
+        .warnings
+        .push(Warning::EndpointTransactionMissed(txid));
+
+    let report = validation_report(&status);
+    assert!(!report.valid);
+    assert_eq!(report.items.len(), 2);
+
+    let failure = &report.items[0];
+    assert_eq!(failure.level, RgbValidationLevel::Failure);
+    assert_eq!(failure.code, "ScriptFailure");
+    assert_eq!(failure.operation_id, Some(opid.to_string()));
+    assert_eq!(failure.message, Failure::ScriptFailure(opid).to_string());
+
+    let warning = &report.items[1];
+    assert_eq!(warning.level, RgbValidationLevel::Warning);
+    assert_eq!(warning.code, "EndpointTransactionMissed");
+    assert_eq!(warning.anchor_txid, Some(txid.to_hex()));
+    assert!(warning.operation_id.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_reorg_accepted_transfer() -> anyhow::Result<()> {
+    let mut lifecycle = RgbTransferLifecycle::new(100);
+    lifecycle.move_to(TransferState::Confirmed(1), 110);
+    lifecycle.move_to(TransferState::Accepted, 120);
+
+    // Accepted transfers only leave the state by a reorg
+    lifecycle.move_to(TransferState::Failed, 130);
+    assert_eq!(lifecycle.state, TransferState::Accepted);
+
+    lifecycle.reorg("anchor tx not found".to_string(), 140);
+    lifecycle.reorg("anchor tx not found".to_string(), 150);
+    assert_eq!(lifecycle.state, TransferState::Reorged);
+    assert_eq!(lifecycle.error, Some("anchor tx not found".to_string()));
+    assert_eq!(lifecycle.updated_at, 150);
+    assert_eq!(lifecycle.history.len(), 4);
+
+    // The anchor was mined again and the transfer re-validated
+    lifecycle.move_to(TransferState::Accepted, 160);
+    assert_eq!(lifecycle.state, TransferState::Accepted);
+    assert!(lifecycle.error.is_none());
+
+    let state = TransferState::from_str("reorged").expect("valid state");
+    assert_eq!(state, TransferState::Reorged);
+    let request: RgbReorgRequest = serde_json::from_str("{}")?;
+    assert_eq!(request.depth, 6);
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_exclude_reorged_allocations() -> anyhow::Result<()> {
+    let contract_id = "rgb:2bLwwJT-9Z6TNoa-dLDzdSQ-ZnvASY4-sSmBFpG-fXz8ebz-KVUYLG";
+    let received_txid = "a4d4a7e6a0e7fa1d3ab8fca5a2a5a0b3c8c53c7bd0e2a1b0a3f4a6d5e8f9b0c1";
+    let issued_txid = "5c42e4a3b2e0d8f5f1a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8";
+    let allocation = |utxo: String, value: u64| AllocationDetail {
+        utxo,
+        value: AllocationValue::Value(value),
+        derivation: "/20/0".to_string(),
+        is_mine: true,
+        is_spent: false,
+    };
+    let mut details = vec![WatcherDetail {
+        contract_id: contract_id.to_string(),
+        allocations: vec![
+            allocation(format!("{issued_txid}:0"), 600),
+            allocation(format!("{received_txid}:1"), 400),
+        ],
+    }];
+    let mut contract = ContractResponse {
+        contract_id: contract_id.to_string(),
+        iimpl_id: String::new(),
+        iface: "RGB20".to_string(),
+        ticker: "TEST".to_string(),
+        name: "Test".to_string(),
+        created: 0,
+        description: String::new(),
+        supply: 1_000,
+        precision: 2,
+        balance: 1_000,
+        balance_normalised: 10.0,
+        allocations: vec![
+            allocation(format!("{issued_txid}:0"), 600),
+            allocation(format!("{received_txid}:1"), 400),
+        ],
+        contract: ContractFormats::default(),
+        genesis: GenesisFormats::default(),
+        meta: None,
+        allowance: None,
+        issued: 1_000,
+        burned: 0,
+        circulating: 1_000,
+        tokens: vec![],
+        terms: None,
+    };
+
+    let mut rgb_transfers = RgbTransfersV2::default();
+    rgb_transfers.anchors.insert(
+        "consig".to_string(),
+        RgbTransferAnchor {
+            contract_id: contract_id.to_string(),
+            tx_id: Txid::from_str(received_txid)?,
+            height: 100,
+            utxos: vec![format!("{received_txid}:1")],
+        },
+    );
+    let mut lifecycle = RgbTransferLifecycle::new(100);
+    lifecycle.move_to(TransferState::Accepted, 110);
+    rgb_transfers
+        .lifecycle
+        .insert("consig".to_string(), lifecycle.clone());
+
+    // Allocations of the accepted transfers are kept
+    exclude_reorged_allocations(&mut contract, &rgb_transfers);
+    assert_eq!(contract.balance, 1_000);
+    exclude_reorged_details(&mut details, &rgb_transfers);
+    assert_eq!(details[0].allocations.len(), 2);
+    assert!(!is_reorged_utxo(
+        &format!("{received_txid}:1"),
+        &rgb_transfers
+    ));
+
+    lifecycle.reorg("anchor tx not found".to_string(), 120);
+    rgb_transfers
+        .lifecycle
+        .insert("consig".to_string(), lifecycle);
+
+    exclude_reorged_allocations(&mut contract, &rgb_transfers);
+    assert_eq!(contract.balance, 600);
+    assert_eq!(contract.balance_normalised, 6.0);
+    assert_eq!(contract.allocations.len(), 1);
+
+    // The watcher does not list them to build transfers or swaps either
+    exclude_reorged_details(&mut details, &rgb_transfers);
+    assert_eq!(
+        details[0].allocations,
+        vec![allocation(format!("{issued_txid}:0"), 600)]
+    );
+    assert!(is_reorged_utxo(
+        &format!("{received_txid}:1"),
+        &rgb_transfers
+    ));
+    assert!(!is_reorged_utxo(
+        &format!("{issued_txid}:0"),
+        &rgb_transfers
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_parse_airdrop_csv() -> anyhow::Result<()> {
+    let csv = "invoice,amount\nrgb:first,10\n\"rgb:second\", \nnot an invoice,3\nrgb:third";
+    assert_eq!(
+        parse_airdrop_csv(csv),
+        vec![
+            ("rgb:first".to_string(), Some("10".to_string())),
+            ("rgb:second".to_string(), None),
+            ("rgb:third".to_string(), None),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn allow_resume_airdrop_pending_invoices() -> anyhow::Result<()> {
+    let contract_id = "rgb:contract";
+    let mut airdrops = RgbAirdrops::default();
+    assert_eq!(airdrops.take_pending(contract_id, false), Ok(vec![]));
+
+    // Invoices out of the current batch wait for the next request
+    airdrops.keep_pending(contract_id, vec!["rgb:second".to_string()]);
+    airdrops.keep_pending("rgb:other", vec![]);
+    assert_eq!(airdrops.pending.len(), 1);
+
+    // A new airdrop cannot overwrite them
+    assert_eq!(airdrops.take_pending(contract_id, false), Err(1));
+    assert_eq!(
+        airdrops.pending.get(contract_id),
+        Some(&vec!["rgb:second".to_string()])
+    );
+
+    // Resuming sends them first, only once
+    assert_eq!(
+        airdrops.take_pending(contract_id, true),
+        Ok(vec!["rgb:second".to_string()])
+    );
+    assert!(airdrops.pending.is_empty());
+    assert_eq!(airdrops.take_pending(contract_id, true), Ok(vec![]));
+    Ok(())
+}
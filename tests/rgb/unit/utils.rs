@@ -4,8 +4,8 @@ use amplify::hex::{FromHex, ToHex};
 use bitcoin::Transaction;
 use bitmask_core::{
     rgb::issue::issue_contract,
-    rgb::transfer::create_invoice,
-    structs::{IssueMediaRequest, MediaInfo},
+    rgb::transfer::{create_invoice, SealSecrets},
+    structs::{InflationAllowanceRequest, IssueMediaRequest, MediaInfo},
 };
 use bp::{
     LockTime, Outpoint, Sats, ScriptPubkey, SeqNo, Tx, TxIn, TxOut, TxVer, Txid, VarIntArray,
@@ -108,6 +108,12 @@ pub fn create_fake_contract(stock: &mut Stock) -> ContractId {
         seal,
         network,
         None,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        false,
+        None,
         &mut resolver,
         stock,
     )
@@ -126,7 +132,103 @@ pub fn create_fake_contract(stock: &mut Stock) -> ContractId {
 }
 
 #[allow(dead_code)]
-pub fn create_fake_invoice(contract_id: ContractId, seal: &str, stock: &mut Stock) -> RgbInvoice {
+pub fn create_fake_inflatable_contract(stock: &mut Stock) -> ContractId {
+    let seal = "tapret1st:5ca6cd1f54c081c8b3a7b4bcc988e55fe3c420ac87512b53a58c55233e15ba4f:1";
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        8,
+        10,
+        "RGB20",
+        seal,
+        "regtest",
+        None,
+        vec![InflationAllowanceRequest {
+            seal: seal.to_string(),
+            amount: 10,
+        }],
+        vec![],
+        vec![],
+        None,
+        false,
+        None,
+        &mut resolver,
+        stock,
+    )
+    .expect("test issue_contract failed");
+
+    contract.contract_id()
+}
+
+#[allow(dead_code)]
+pub fn create_fake_uda(stock: &mut Stock, fractions: u64) -> ContractId {
+    let seal = "tapret1st:5ca6cd1f54c081c8b3a7b4bcc988e55fe3c420ac87512b53a58c55233e15ba4f:1";
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        0,
+        fractions,
+        "RGB21",
+        seal,
+        "regtest",
+        Some(get_uda_data()),
+        vec![],
+        vec![],
+        vec![],
+        None,
+        fractions > 1,
+        None,
+        &mut resolver,
+        stock,
+    )
+    .expect("test uda issue_contract failed");
+
+    contract.contract_id()
+}
+
+#[allow(dead_code)]
+pub fn create_fake_collection(stock: &mut Stock, fractions: u64) -> ContractId {
+    let seal = "tapret1st:5ca6cd1f54c081c8b3a7b4bcc988e55fe3c420ac87512b53a58c55233e15ba4f:1";
+    let collection = vec![get_uda_data(), get_uda_data(), get_uda_data()];
+    let mut resolver = DumbResolve {};
+
+    let contract = issue_contract(
+        "DIBA",
+        "DIBA",
+        "DIBA",
+        0,
+        fractions,
+        "RGB21",
+        seal,
+        "regtest",
+        None,
+        vec![],
+        collection,
+        vec![],
+        None,
+        false,
+        None,
+        &mut resolver,
+        stock,
+    )
+    .expect("test collection issue_contract failed");
+
+    contract.contract_id()
+}
+
+#[allow(dead_code)]
+pub fn create_fake_invoice(
+    contract_id: ContractId,
+    seal: &str,
+    stock: &mut Stock,
+    seal_secrets: &mut SealSecrets,
+) -> RgbInvoice {
     let amount = 1;
     let iface = "RGB20";
     let params = HashMap::new();
@@ -138,6 +240,7 @@ pub fn create_fake_invoice(contract_id: ContractId, seal: &str, stock: &mut Stoc
         "regtest",
         params,
         stock,
+        seal_secrets,
     )
     .expect("create_invoice failed")
 }
@@ -102,6 +102,12 @@ async fn allow_issue_and_list_contracts() {
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_req = serde_wasm_bindgen::to_value(&issue_req).expect("");
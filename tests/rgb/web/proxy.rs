@@ -167,6 +167,12 @@ async fn import_and_get_consig_from_proxy() {
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_req = serde_wasm_bindgen::to_value(&issue_req).expect("");
@@ -237,13 +243,18 @@ async fn import_and_get_consig_from_proxy() {
         let params = HashMap::new();
         let receiver_utxo = receiver_next_utxo.utxo.unwrap().outpoint.to_string();
         let receiver_seal = format!("tapret1st:{receiver_utxo}");
-        let invoice_amount = ContractAmount::from(round.send_amount.to_string(), precision);
+        let invoice_amount =
+            ContractAmount::from(round.send_amount.to_string(), precision).expect("invalid amount");
         let invoice_req = InvoiceRequest {
             contract_id: issuer_resp.contract_id.to_string(),
             iface: issuer_resp.iface.to_string(),
             amount: invoice_amount.to_string(),
             seal: receiver_seal,
             params,
+            transports: vec![],
+            expire_at: None,
+            min_amount: None,
+            max_amount: None,
         };
 
         let invoice_req = serde_wasm_bindgen::to_value(&invoice_req).expect("");
@@ -264,6 +275,7 @@ async fn import_and_get_consig_from_proxy() {
             change_terminal: "/20/1".to_string(),
             fee: PsbtFeeRequest::Value(1000),
             bitcoin_changes: vec![],
+            amount: None,
         };
 
         let full_transfer_req = serde_wasm_bindgen::to_value(&full_transfer_req).expect("");
@@ -383,6 +395,12 @@ async fn create_uda_with_medias() {
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta: Some(media_req),
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_req = serde_wasm_bindgen::to_value(&issue_req).expect("");
@@ -14,10 +14,11 @@ use bitmask_core::{
         AssetType, BatchRgbTransferResponse, ContractResponse, ContractsResponse,
         DecryptedWalletData, FullRgbTransferRequest, FundVaultDetails, ImportRequest,
         InvoiceRequest, InvoiceResponse, IssueRequest, IssueResponse, NextAddressResponse,
-        NextUtxoResponse, PsbtFeeRequest, PublishedPsbtResponse, RgbBidRequest, RgbBidResponse,
-        RgbOfferRequest, RgbOfferResponse, RgbSaveTransferRequest, RgbSwapRequest, RgbSwapResponse,
-        RgbTransferRequest, RgbTransferResponse, RgbTransferStatusResponse, SecretString,
-        SignPsbtRequest, SignedPsbtResponse, WalletData, WatcherRequest, WatcherResponse,
+        NextUtxoResponse, PsbtFeeRequest, PsbtFormat, PublishedPsbtResponse, RgbBidRequest,
+        RgbBidResponse, RgbOfferRequest, RgbOfferResponse, RgbSaveTransferRequest, RgbSwapRequest,
+        RgbSwapResponse, RgbTransferRequest, RgbTransferResponse, RgbTransferStatusResponse,
+        SecretString, SignPsbtRequest, SignedPsbtResponse, WalletData, WatcherRequest,
+        WatcherResponse,
     },
     web::{
         bitcoin::{
@@ -189,6 +190,12 @@ async fn create_transfer_swap_flow() {
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_req = serde_wasm_bindgen::to_value(&issue_req).expect("");
@@ -279,6 +286,7 @@ async fn create_transfer_swap_flow() {
             bitcoin_changes: vec![],
             expire_at: Some(expire_at),
             presig: false,
+            psbt_version: PsbtFormat::V0,
         };
         let sender_swap_req = serde_wasm_bindgen::to_value(&sender_swap_req).expect("");
 
@@ -164,6 +164,12 @@ async fn create_transfer_with_fee_value() {
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_req = serde_wasm_bindgen::to_value(&issue_req).expect("");
@@ -240,13 +246,18 @@ async fn create_transfer_with_fee_value() {
         let params = HashMap::new();
         let receiver_utxo = receiver_next_utxo.utxo.unwrap().outpoint.to_string();
         let receiver_seal = format!("tapret1st:{receiver_utxo}");
-        let invoice_amount = ContractAmount::from(round.send_amount.to_string(), precision);
+        let invoice_amount =
+            ContractAmount::from(round.send_amount.to_string(), precision).expect("invalid amount");
         let invoice_req = InvoiceRequest {
             contract_id: issuer_resp.contract_id.to_string(),
             iface: issuer_resp.iface.to_string(),
             amount: invoice_amount.to_string(),
             seal: receiver_seal,
             params,
+            transports: vec![],
+            expire_at: None,
+            min_amount: None,
+            max_amount: None,
         };
 
         let invoice_req = serde_wasm_bindgen::to_value(&invoice_req).expect("");
@@ -267,6 +278,7 @@ async fn create_transfer_with_fee_value() {
             change_terminal: "/20/1".to_string(),
             fee: PsbtFeeRequest::Value(1000),
             bitcoin_changes: vec![],
+            amount: None,
         };
 
         let full_transfer_req = serde_wasm_bindgen::to_value(&full_transfer_req).expect("");
@@ -469,6 +481,12 @@ async fn create_transfer_with_fee_rate() {
         seal: issue_seal.to_owned(),
         iface: iface.to_string(),
         meta: None,
+        inflation: vec![],
+        collection: vec![],
+        beneficiaries: vec![],
+        terms: None,
+        fractional: false,
+        created: None,
     };
 
     let issue_req = serde_wasm_bindgen::to_value(&issue_req).expect("");
@@ -540,7 +558,8 @@ async fn create_transfer_with_fee_rate() {
 
         info!(format!("Create Invoice ({receiver})"));
         let params = HashMap::new();
-        let invoice_amount = ContractAmount::from(round.send_amount.to_string(), precision);
+        let invoice_amount =
+            ContractAmount::from(round.send_amount.to_string(), precision).expect("invalid amount");
         let receiver_utxo = receiver_next_utxo.utxo.unwrap().outpoint.to_string();
         let receiver_seal = format!("tapret1st:{receiver_utxo}");
         let invoice_req = InvoiceRequest {
@@ -549,6 +568,10 @@ async fn create_transfer_with_fee_rate() {
             amount: invoice_amount.to_string(),
             seal: receiver_seal,
             params,
+            transports: vec![],
+            expire_at: None,
+            min_amount: None,
+            max_amount: None,
         };
 
         let invoice_req = serde_wasm_bindgen::to_value(&invoice_req).expect("");
@@ -569,6 +592,7 @@ async fn create_transfer_with_fee_rate() {
             change_terminal: "/20/1".to_string(),
             fee: PsbtFeeRequest::FeeRate(1.1),
             bitcoin_changes: vec![],
+            amount: None,
         };
 
         let full_transfer_req = serde_wasm_bindgen::to_value(&full_transfer_req).expect("");
@@ -0,0 +1,214 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{env, process::Stdio};
+
+use anyhow::Result;
+use bitcoin::Script;
+use bitmask_core::{
+    bitcoin::{
+        get_wallet_data, new_mnemonic, refresh_inheritance_vault, sweep_inheritance_vault,
+        vault_descriptor, vault_tapret_tweak,
+    },
+    constants::switch_network,
+    structs::{SecretString, VaultTimelock},
+    util::init_logging,
+};
+use tokio::process::Command;
+
+#[tokio::test]
+pub async fn inheritance_vault_descriptor() -> Result<()> {
+    init_logging("vault_tests=debug");
+
+    switch_network("regtest").await?;
+
+    let owner = new_mnemonic(&SecretString("".to_owned())).await?;
+    let heir = new_mnemonic(&SecretString("".to_owned())).await?;
+
+    let descriptor = vault_descriptor(
+        &SecretString(owner.public.btc_descriptor_xpub.to_owned()),
+        &SecretString(heir.public.btc_descriptor_xpub.to_owned()),
+        VaultTimelock::Relative(144),
+    )?;
+
+    assert!(descriptor.0.starts_with("tr(["));
+    assert!(descriptor.0.ends_with("),older(144)))"));
+
+    let wallet_data = get_wallet_data(&descriptor, None).await?;
+    assert!(wallet_data.address.starts_with("bcrt1p"));
+
+    let vault = vault_descriptor(
+        &SecretString(owner.public.btc_descriptor_xpub.to_owned()),
+        &SecretString(descriptor.0),
+        VaultTimelock::Absolute(800_000),
+    );
+    assert!(vault.is_err(), "vault keys must be single-key descriptors");
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn inheritance_vault_timelock_range() -> Result<()> {
+    switch_network("regtest").await?;
+
+    let owner = new_mnemonic(&SecretString("".to_owned())).await?;
+    let heir = new_mnemonic(&SecretString("".to_owned())).await?;
+    let owner_xpub = SecretString(owner.public.btc_descriptor_xpub.to_owned());
+    let heir_xpub = SecretString(heir.public.btc_descriptor_xpub.to_owned());
+
+    let descriptor = vault_descriptor(&owner_xpub, &heir_xpub, VaultTimelock::Absolute(800_000))?;
+    assert!(descriptor.0.ends_with("),after(800000)))"));
+
+    // Values from 500_000_000 are timestamps, not block heights
+    for timelock in [
+        VaultTimelock::Absolute(500_000_000),
+        VaultTimelock::Absolute(0),
+        VaultTimelock::Relative(0),
+        // Only 16 bits of block count, without the type or disable flags
+        VaultTimelock::Relative(0x1_0000),
+        VaultTimelock::Relative(1 << 22 | 144),
+        VaultTimelock::Relative(1 << 31 | 144),
+    ] {
+        let vault = vault_descriptor(&owner_xpub, &heir_xpub, timelock);
+        assert!(vault.is_err(), "{timelock:?} must be rejected");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn inheritance_vault_tapret_tweak() -> Result<()> {
+    switch_network("regtest").await?;
+
+    let owner = new_mnemonic(&SecretString("".to_owned())).await?;
+    let heir = new_mnemonic(&SecretString("".to_owned())).await?;
+    let descriptor = vault_descriptor(
+        &SecretString(owner.public.btc_descriptor_xpub.to_owned()),
+        &SecretString(heir.public.btc_descriptor_xpub.to_owned()),
+        VaultTimelock::Relative(144),
+    )?;
+
+    // Tapret leaf: 29 OP_RESERVED, OP_RETURN and the 33 byte commitment push
+    let mut tapret = vec![0x50; 29];
+    tapret.push(0x6a);
+    tapret.push(0x21);
+    tapret.extend([1u8; 33]);
+    let tweak = vault_tapret_tweak(&descriptor, 0, Script::from(tapret.clone()))?;
+    assert_eq!(tweak.len(), 64);
+
+    // The commitment and the derivation index are both part of the script tree
+    tapret[31..].copy_from_slice(&[2u8; 33]);
+    let other = vault_tapret_tweak(&descriptor, 0, Script::from(tapret.clone()))?;
+    assert_ne!(tweak, other);
+    let next = vault_tapret_tweak(&descriptor, 1, Script::from(tapret))?;
+    assert_ne!(other, next);
+
+    let single_key = SecretString(owner.public.btc_descriptor_xpub.to_owned());
+    let tweak = vault_tapret_tweak(&single_key, 0, Script::new());
+    assert!(
+        tweak.is_err(),
+        "a single-key descriptor has no recovery leaf"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn inheritance_vault_owner_refresh() -> Result<()> {
+    init_logging("vault_tests=debug");
+
+    switch_network("regtest").await?;
+
+    let owner = new_mnemonic(&SecretString("".to_owned())).await?;
+    let heir = new_mnemonic(&SecretString("".to_owned())).await?;
+    let owner_xprv = SecretString(owner.private.btc_descriptor_xprv.to_owned());
+    let heir_xpub = SecretString(heir.public.btc_descriptor_xpub.to_owned());
+
+    let timelock = VaultTimelock::Relative(144);
+    let descriptor = vault_descriptor(&owner_xprv, &heir_xpub, timelock)?;
+    let vault_data = get_wallet_data(&descriptor, None).await?;
+    send_some_coins(&vault_data.address, "0.001").await;
+
+    // The owner moves the funds before the heir branch is spendable
+    let details =
+        refresh_inheritance_vault(&owner_xprv, &heir_xpub, timelock, None, Some(1.1)).await?;
+    assert!(details.txid.is_some());
+    assert_ne!(details.address, vault_data.address);
+
+    generate_new_block().await;
+    let new_descriptor = SecretString(details.descriptor.to_owned());
+    let new_vault_data = get_wallet_data(&new_descriptor, None).await?;
+    assert!(new_vault_data.balance.confirmed > 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn inheritance_vault_heir_sweep() -> Result<()> {
+    init_logging("vault_tests=debug");
+
+    switch_network("regtest").await?;
+
+    let owner = new_mnemonic(&SecretString("".to_owned())).await?;
+    let heir = new_mnemonic(&SecretString("".to_owned())).await?;
+    let owner_xpub = SecretString(owner.public.btc_descriptor_xpub.to_owned());
+    let heir_xprv = SecretString(heir.private.btc_descriptor_xprv.to_owned());
+    let heir_xpub = SecretString(heir.public.btc_descriptor_xpub.to_owned());
+    let heir_data = get_wallet_data(&heir_xpub, None).await?;
+
+    // The recovery branch is not spendable before the timelock
+    let locked = VaultTimelock::Absolute(10_000_000);
+    let descriptor = vault_descriptor(&owner_xpub, &heir_xpub, locked)?;
+    let vault_data = get_wallet_data(&descriptor, None).await?;
+    send_some_coins(&vault_data.address, "0.001").await;
+
+    let sweep = sweep_inheritance_vault(
+        &owner_xpub,
+        &heir_xprv,
+        locked,
+        &heir_data.address,
+        Some(1.1),
+    )
+    .await;
+    assert!(sweep.is_err(), "heir cannot sweep before the timelock");
+
+    // The vault output is confirmed by 2 blocks, so older(1) is satisfied
+    let unlocked = VaultTimelock::Relative(1);
+    let descriptor = vault_descriptor(&owner_xpub, &heir_xpub, unlocked)?;
+    let vault_data = get_wallet_data(&descriptor, None).await?;
+    send_some_coins(&vault_data.address, "0.001").await;
+
+    let details = sweep_inheritance_vault(
+        &owner_xpub,
+        &heir_xprv,
+        unlocked,
+        &heir_data.address,
+        Some(1.1),
+    )
+    .await?;
+    assert!(details.transaction.is_some());
+
+    Ok(())
+}
+
+async fn send_some_coins(address: &str, amount: &str) {
+    run_script("send_coins.sh", &[address, amount]).await;
+}
+
+async fn generate_new_block() {
+    run_script("new_blocks.sh", &[]).await;
+}
+
+async fn run_script(script: &str, args: &[&str]) {
+    let path = env::current_dir().expect("oh no!");
+    let path = path.to_str().expect("oh no!");
+    let full_file = format!("{path}/tests/scripts/{script}");
+    Command::new("bash")
+        .arg(full_file)
+        .args(args)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap()
+        .wait()
+        .await
+        .expect("oh no!");
+}